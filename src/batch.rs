@@ -0,0 +1,228 @@
+//! # Batched Mutations Module
+//!
+//! This module decodes the operation arrays accepted by `TaffyTree.applyBatch()`.
+//! A batch lets callers such as UI reconcilers describe many tree mutations in a
+//! single WASM call; `applyBatch()` runs them with the regular `TaffyTree`
+//! methods and reverts the edits of the whole batch if any operation fails.
+//!
+//! ## Operations
+//!
+//! | `op` | Fields | Equivalent method |
+//! |------|--------|-------------------|
+//! | `create` | `style?`, `context?`, `children?` | `newLeaf()` / `newLeafWithContext()` / `newWithChildren()` |
+//! | `insert` | `parent`, `child`, `index?` | `addChild()` / `insertChildAtIndex()` |
+//! | `removeChild` | `parent`, `child` | `removeChild()` |
+//! | `remove` | `node` | `remove()` |
+//! | `move` | `node`, `parent`, `index?` | `moveNode()` |
+//! | `setChildren` | `parent`, `children` | `setChildren()` |
+//! | `setStyle` | `node`, `style` | `setStyle()` |
+//! | `updateStyle` | `node`, `style` | `updateStyle()` |
+//! | `setContext` | `node`, `context` | `setNodeContext()` |
+//! | `markDirty` | `node` | `markDirty()` |
+//!
+//! Styles are given as `Style.set()` property objects rather than `Style`
+//! instances. A node is referenced either by its ID (`bigint`) or by the index
+//! (`number`) of an earlier `create` operation in the same batch.
+
+use crate::error::{JsTaffyError, decode_argument};
+use js_sys::{Array, Reflect};
+use taffy::prelude::NodeId;
+use wasm_bindgen::prelude::*;
+
+// =============================================================================
+// Operations
+// =============================================================================
+
+/// A node referenced by a batch operation
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum NodeRef {
+    /// An existing node
+    Id(NodeId),
+    /// The node created by the `create` operation at this index
+    Created(usize),
+}
+
+/// A decoded batch operation
+///
+/// Style and context values are kept as JavaScript values; `applyBatch()`
+/// decodes the styles of all operations before running the first one.
+pub(crate) enum BatchOp {
+    Create {
+        style: JsValue,
+        context: Option<JsValue>,
+        children: Vec<NodeRef>,
+    },
+    Insert {
+        parent: NodeRef,
+        child: NodeRef,
+        index: Option<usize>,
+    },
+    RemoveChild {
+        parent: NodeRef,
+        child: NodeRef,
+    },
+    Remove {
+        node: NodeRef,
+    },
+    Move {
+        node: NodeRef,
+        parent: NodeRef,
+        index: Option<usize>,
+    },
+    SetChildren {
+        parent: NodeRef,
+        children: Vec<NodeRef>,
+    },
+    SetStyle {
+        node: NodeRef,
+        style: JsValue,
+    },
+    UpdateStyle {
+        node: NodeRef,
+        style: JsValue,
+    },
+    SetContext {
+        node: NodeRef,
+        context: JsValue,
+    },
+    MarkDirty {
+        node: NodeRef,
+    },
+}
+
+/// TypeScript type of a node reference, for error messages
+const NODE_REF: &str = "bigint | index of an earlier create op";
+
+// =============================================================================
+// Decoding
+// =============================================================================
+
+/// Decodes the operation at `index` of a batch
+///
+/// `creates` lists, for every earlier operation, whether it is a `create`, so
+/// that `number` node references can be checked before anything runs.
+///
+/// @returns - The operation name and the decoded operation
+pub(crate) fn decode_op(
+    value: &JsValue,
+    index: usize,
+    creates: &[bool],
+) -> Result<(String, BatchOp), JsTaffyError> {
+    let path = format!("ops[{index}]");
+    if !value.is_object() {
+        return Err(JsTaffyError::invalid_argument(path, value, "BatchOp"));
+    }
+    let field = |name: &str| Reflect::get(value, &name.into()).unwrap_or(JsValue::UNDEFINED);
+    let node = |name: &str| node_ref(&format!("{path}.{name}"), &field(name), creates);
+    let node_refs = |name: &str| {
+        let list = field(name);
+        if list.is_undefined() && name == "children" {
+            return Ok(Vec::new());
+        }
+        let list = list.dyn_into::<Array>().map_err(|list| {
+            JsTaffyError::invalid_argument(format!("{path}.{name}"), &list, "BatchNodeRef[]")
+        })?;
+        list.iter()
+            .enumerate()
+            .map(|(i, item)| node_ref(&format!("{path}.{name}[{i}]"), &item, creates))
+            .collect()
+    };
+    let child_index = || -> Result<Option<usize>, JsTaffyError> {
+        let index = field("index");
+        if index.is_undefined() {
+            return Ok(None);
+        }
+        decode_argument(&format!("{path}.index"), index, "number").map(Some)
+    };
+    let style = |required: bool| {
+        let style = field("style");
+        if style.is_object() || (!required && style.is_undefined()) {
+            Ok(style)
+        } else {
+            Err(JsTaffyError::invalid_argument(
+                format!("{path}.style"),
+                &style,
+                "StylePropertyValues",
+            ))
+        }
+    };
+
+    let name = field("op");
+    let Some(name) = name.as_string() else {
+        return Err(JsTaffyError::invalid_argument(
+            format!("{path}.op"),
+            &name,
+            "BatchOp[\"op\"]",
+        ));
+    };
+    let op = match name.as_str() {
+        "create" => {
+            let context = field("context");
+            BatchOp::Create {
+                style: style(false)?,
+                context: (!context.is_undefined()).then_some(context),
+                children: node_refs("children")?,
+            }
+        }
+        "insert" => BatchOp::Insert {
+            parent: node("parent")?,
+            child: node("child")?,
+            index: child_index()?,
+        },
+        "removeChild" => BatchOp::RemoveChild {
+            parent: node("parent")?,
+            child: node("child")?,
+        },
+        "remove" => BatchOp::Remove {
+            node: node("node")?,
+        },
+        "move" => BatchOp::Move {
+            node: node("node")?,
+            parent: node("parent")?,
+            index: child_index()?,
+        },
+        "setChildren" => BatchOp::SetChildren {
+            parent: node("parent")?,
+            children: node_refs("children")?,
+        },
+        "setStyle" => BatchOp::SetStyle {
+            node: node("node")?,
+            style: style(true)?,
+        },
+        "updateStyle" => BatchOp::UpdateStyle {
+            node: node("node")?,
+            style: style(true)?,
+        },
+        "setContext" => BatchOp::SetContext {
+            node: node("node")?,
+            context: field("context"),
+        },
+        "markDirty" => BatchOp::MarkDirty {
+            node: node("node")?,
+        },
+        _ => {
+            return Err(JsTaffyError::invalid_argument(
+                format!("{path}.op"),
+                &JsValue::from_str(&name),
+                "BatchOp[\"op\"]",
+            ));
+        }
+    };
+    Ok((name, op))
+}
+
+/// Decodes a node ID or the index of an earlier `create` operation
+fn node_ref(path: &str, value: &JsValue, creates: &[bool]) -> Result<NodeRef, JsTaffyError> {
+    if value.is_bigint() {
+        return u64::try_from(value.clone())
+            .map(|id| NodeRef::Id(NodeId::from(id)))
+            .map_err(|_| JsTaffyError::invalid_argument(path, value, NODE_REF));
+    }
+    value
+        .as_f64()
+        .filter(|n| n.fract() == 0.0 && *n >= 0.0)
+        .map(|n| n as usize)
+        .filter(|&i| creates.get(i) == Some(&true))
+        .map(NodeRef::Created)
+        .ok_or_else(|| JsTaffyError::invalid_argument(path, value, NODE_REF))
+}
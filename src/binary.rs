@@ -0,0 +1,965 @@
+//! # Binary Snapshot Module
+//!
+//! This module implements the compact binary format used by
+//! `TaffyTree.toBytes()` and `TaffyTree.fromBytes()`. It stores the same
+//! information as a JSON snapshot without contexts or layouts (tree structure
+//! and styles) but avoids building and parsing JS objects.
+//!
+//! ## Layout
+//!
+//! All integers and floats are little-endian.
+//!
+//! | Field | Type | Description |
+//! |-------|------|-------------|
+//! | magic | `[u8; 4]` | `"TAFY"` |
+//! | version | `u16` | Format version, currently `1` |
+//! | node count | `u32` | Number of node records that follow |
+//! | nodes | record[] | Node records in depth-first pre-order |
+//!
+//! Each node record is:
+//!
+//! | Field | Type | Description |
+//! |-------|------|-------------|
+//! | child count | `u32` | Children follow as the next subtrees in pre-order |
+//! | field mask | `u64` | Bit `n` is set when style field `n` differs from its default |
+//! | fields | varies | Payload of each set field, in bit order |
+//!
+//! Boolean fields carry no payload: their bit is their value. Optional fields
+//! are only written when set, so they carry the inner value only. Lengths are a
+//! one-byte tag followed by an `f32` when the tag carries a value. Enums are a
+//! one-byte index into the variant tables below.
+
+use crate::error::JsTaffyError;
+use taffy::geometry::{Line, Point, Rect, Size};
+use taffy::prelude::TaffyGridLine;
+use taffy::prelude::TaffyGridSpan;
+use taffy::style::{
+    AlignContent, AlignItems, BoxSizing, CompactLength, Dimension, Display, FlexDirection,
+    FlexWrap, GridAutoFlow, GridPlacement, GridTemplateArea, GridTemplateComponent,
+    GridTemplateRepetition, LengthPercentage, LengthPercentageAuto, MaxTrackSizingFunction,
+    MinTrackSizingFunction, Overflow, Position, RepetitionCount, Style, TextAlign,
+    TrackSizingFunction,
+};
+
+/// Magic bytes at the start of every binary snapshot
+pub(crate) const BINARY_MAGIC: [u8; 4] = *b"TAFY";
+
+/// Current binary format version
+pub(crate) const BINARY_VERSION: u16 = 1;
+
+/// Number of style fields covered by the field mask
+const FIELD_COUNT: u32 = 38;
+
+// Length tags
+const LENGTH: u8 = 0;
+const PERCENT: u8 = 1;
+const AUTO: u8 = 2;
+const FR: u8 = 3;
+const MIN_CONTENT: u8 = 4;
+const MAX_CONTENT: u8 = 5;
+const FIT_CONTENT_PX: u8 = 6;
+const FIT_CONTENT_PERCENT: u8 = 7;
+
+// Variant tables
+const DISPLAY: [Display; 4] = [Display::Block, Display::Flex, Display::Grid, Display::None];
+const POSITION: [Position; 2] = [Position::Relative, Position::Absolute];
+const BOX_SIZING: [BoxSizing; 2] = [BoxSizing::BorderBox, BoxSizing::ContentBox];
+const OVERFLOW: [Overflow; 4] = [
+    Overflow::Visible,
+    Overflow::Clip,
+    Overflow::Hidden,
+    Overflow::Scroll,
+];
+const ALIGN_ITEMS: [AlignItems; 7] = [
+    AlignItems::Start,
+    AlignItems::End,
+    AlignItems::FlexStart,
+    AlignItems::FlexEnd,
+    AlignItems::Center,
+    AlignItems::Baseline,
+    AlignItems::Stretch,
+];
+const ALIGN_CONTENT: [AlignContent; 9] = [
+    AlignContent::Start,
+    AlignContent::End,
+    AlignContent::FlexStart,
+    AlignContent::FlexEnd,
+    AlignContent::Center,
+    AlignContent::Stretch,
+    AlignContent::SpaceBetween,
+    AlignContent::SpaceEvenly,
+    AlignContent::SpaceAround,
+];
+const TEXT_ALIGN: [TextAlign; 4] = [
+    TextAlign::Auto,
+    TextAlign::LegacyLeft,
+    TextAlign::LegacyRight,
+    TextAlign::LegacyCenter,
+];
+const FLEX_DIRECTION: [FlexDirection; 4] = [
+    FlexDirection::Row,
+    FlexDirection::Column,
+    FlexDirection::RowReverse,
+    FlexDirection::ColumnReverse,
+];
+const FLEX_WRAP: [FlexWrap; 3] = [FlexWrap::NoWrap, FlexWrap::Wrap, FlexWrap::WrapReverse];
+const GRID_AUTO_FLOW: [GridAutoFlow; 4] = [
+    GridAutoFlow::Row,
+    GridAutoFlow::Column,
+    GridAutoFlow::RowDense,
+    GridAutoFlow::ColumnDense,
+];
+
+// =============================================================================
+// Encoding
+// =============================================================================
+
+/// Appends values to a binary snapshot
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u16(&mut self, v: u16) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn i16(&mut self, v: i16) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn variant<T: PartialEq>(&mut self, table: &[T], value: &T) {
+        let index = table.iter().position(|v| v == value).unwrap_or(0);
+        self.u8(index as u8);
+    }
+
+    fn length(&mut self, raw: CompactLength) {
+        let (tag, value) = match raw.tag() {
+            CompactLength::LENGTH_TAG => (LENGTH, Some(raw.value())),
+            CompactLength::PERCENT_TAG => (PERCENT, Some(raw.value())),
+            CompactLength::FR_TAG => (FR, Some(raw.value())),
+            CompactLength::MIN_CONTENT_TAG => (MIN_CONTENT, None),
+            CompactLength::MAX_CONTENT_TAG => (MAX_CONTENT, None),
+            CompactLength::FIT_CONTENT_PX_TAG => (FIT_CONTENT_PX, Some(raw.value())),
+            CompactLength::FIT_CONTENT_PERCENT_TAG => (FIT_CONTENT_PERCENT, Some(raw.value())),
+            // `auto`, and `calc()` which the bindings never produce
+            _ => (AUTO, None),
+        };
+        self.u8(tag);
+        if let Some(value) = value {
+            self.f32(value);
+        }
+    }
+
+    fn size<T: Copy>(&mut self, size: &Size<T>, f: fn(T) -> CompactLength) {
+        self.length(f(size.width));
+        self.length(f(size.height));
+    }
+
+    fn rect<T: Copy>(&mut self, rect: &Rect<T>, f: fn(T) -> CompactLength) {
+        self.length(f(rect.left));
+        self.length(f(rect.right));
+        self.length(f(rect.top));
+        self.length(f(rect.bottom));
+    }
+
+    fn track(&mut self, track: &TrackSizingFunction) {
+        self.length(track.min.into_raw());
+        self.length(track.max.into_raw());
+    }
+
+    fn tracks(&mut self, tracks: &[TrackSizingFunction]) {
+        self.len(tracks.len());
+        for track in tracks {
+            self.track(track);
+        }
+    }
+
+    fn names(&mut self, names: &[Vec<String>]) {
+        self.len(names.len());
+        for line in names {
+            self.len(line.len());
+            for name in line {
+                self.str(name);
+            }
+        }
+    }
+
+    fn template(&mut self, components: &[GridTemplateComponent<String>]) {
+        self.len(components.len());
+        for component in components {
+            match component {
+                GridTemplateComponent::Single(track) => {
+                    self.u8(0);
+                    self.track(track);
+                }
+                GridTemplateComponent::Repeat(repetition) => {
+                    self.u8(1);
+                    match repetition.count {
+                        RepetitionCount::AutoFill => self.u8(0),
+                        RepetitionCount::AutoFit => self.u8(1),
+                        RepetitionCount::Count(count) => {
+                            self.u8(2);
+                            self.u16(count);
+                        }
+                    }
+                    self.tracks(&repetition.tracks);
+                    self.names(&repetition.line_names);
+                }
+            }
+        }
+    }
+
+    fn areas(&mut self, areas: &[GridTemplateArea<String>]) {
+        self.len(areas.len());
+        for area in areas {
+            self.str(&area.name);
+            self.u16(area.row_start);
+            self.u16(area.row_end);
+            self.u16(area.column_start);
+            self.u16(area.column_end);
+        }
+    }
+
+    fn placement(&mut self, placement: &GridPlacement<String>) {
+        match placement {
+            GridPlacement::Auto => self.u8(0),
+            GridPlacement::Line(line) => {
+                self.u8(1);
+                self.i16(line.as_i16());
+            }
+            GridPlacement::Span(span) => {
+                self.u8(2);
+                self.u16(*span);
+            }
+            GridPlacement::NamedLine(name, index) => {
+                self.u8(3);
+                self.str(name);
+                self.i16(*index);
+            }
+            GridPlacement::NamedSpan(name, span) => {
+                self.u8(4);
+                self.str(name);
+                self.u16(*span);
+            }
+        }
+    }
+
+    fn line(&mut self, line: &Line<GridPlacement<String>>) {
+        self.placement(&line.start);
+        self.placement(&line.end);
+    }
+
+    /// Writes the field mask followed by every non-default field
+    fn style(&mut self, style: &Style) {
+        let d = &Style::DEFAULT;
+        let mask_at = self.buf.len();
+        self.buf.extend_from_slice(&0u64.to_le_bytes());
+        let mut mask = 0u64;
+        let mut set = |bit: u32, changed: bool| {
+            if changed {
+                mask |= 1 << bit;
+            }
+            changed
+        };
+
+        if set(0, style.display != d.display) {
+            self.variant(&DISPLAY, &style.display);
+        }
+        set(1, style.item_is_table);
+        set(2, style.item_is_replaced);
+        if set(3, style.box_sizing != d.box_sizing) {
+            self.variant(&BOX_SIZING, &style.box_sizing);
+        }
+        if set(4, style.overflow != d.overflow) {
+            self.variant(&OVERFLOW, &style.overflow.x);
+            self.variant(&OVERFLOW, &style.overflow.y);
+        }
+        if set(5, style.scrollbar_width != d.scrollbar_width) {
+            self.f32(style.scrollbar_width);
+        }
+        if set(6, style.position != d.position) {
+            self.variant(&POSITION, &style.position);
+        }
+        if set(7, style.inset != d.inset) {
+            self.rect(&style.inset, LengthPercentageAuto::into_raw);
+        }
+        if set(8, style.size != d.size) {
+            self.size(&style.size, Dimension::into_raw);
+        }
+        if set(9, style.min_size != d.min_size) {
+            self.size(&style.min_size, Dimension::into_raw);
+        }
+        if set(10, style.max_size != d.max_size) {
+            self.size(&style.max_size, Dimension::into_raw);
+        }
+        if let Some(ratio) = style.aspect_ratio.filter(|_| set(11, true)) {
+            self.f32(ratio);
+        }
+        if set(12, style.margin != d.margin) {
+            self.rect(&style.margin, LengthPercentageAuto::into_raw);
+        }
+        if set(13, style.padding != d.padding) {
+            self.rect(&style.padding, LengthPercentage::into_raw);
+        }
+        if set(14, style.border != d.border) {
+            self.rect(&style.border, LengthPercentage::into_raw);
+        }
+        if let Some(v) = style.align_items.filter(|_| set(15, true)) {
+            self.variant(&ALIGN_ITEMS, &v);
+        }
+        if let Some(v) = style.align_self.filter(|_| set(16, true)) {
+            self.variant(&ALIGN_ITEMS, &v);
+        }
+        if let Some(v) = style.justify_items.filter(|_| set(17, true)) {
+            self.variant(&ALIGN_ITEMS, &v);
+        }
+        if let Some(v) = style.justify_self.filter(|_| set(18, true)) {
+            self.variant(&ALIGN_ITEMS, &v);
+        }
+        if let Some(v) = style.align_content.filter(|_| set(19, true)) {
+            self.variant(&ALIGN_CONTENT, &v);
+        }
+        if let Some(v) = style.justify_content.filter(|_| set(20, true)) {
+            self.variant(&ALIGN_CONTENT, &v);
+        }
+        if set(21, style.gap != d.gap) {
+            self.size(&style.gap, LengthPercentage::into_raw);
+        }
+        if set(22, style.text_align != d.text_align) {
+            self.variant(&TEXT_ALIGN, &style.text_align);
+        }
+        if set(23, style.flex_direction != d.flex_direction) {
+            self.variant(&FLEX_DIRECTION, &style.flex_direction);
+        }
+        if set(24, style.flex_wrap != d.flex_wrap) {
+            self.variant(&FLEX_WRAP, &style.flex_wrap);
+        }
+        if set(25, style.flex_basis != d.flex_basis) {
+            self.length(style.flex_basis.into_raw());
+        }
+        if set(26, style.flex_grow != d.flex_grow) {
+            self.f32(style.flex_grow);
+        }
+        if set(27, style.flex_shrink != d.flex_shrink) {
+            self.f32(style.flex_shrink);
+        }
+        if set(28, !style.grid_template_rows.is_empty()) {
+            self.template(&style.grid_template_rows);
+        }
+        if set(29, !style.grid_template_columns.is_empty()) {
+            self.template(&style.grid_template_columns);
+        }
+        if set(30, !style.grid_auto_rows.is_empty()) {
+            self.tracks(&style.grid_auto_rows);
+        }
+        if set(31, !style.grid_auto_columns.is_empty()) {
+            self.tracks(&style.grid_auto_columns);
+        }
+        if set(32, style.grid_auto_flow != d.grid_auto_flow) {
+            self.variant(&GRID_AUTO_FLOW, &style.grid_auto_flow);
+        }
+        if set(33, !style.grid_template_areas.is_empty()) {
+            self.areas(&style.grid_template_areas);
+        }
+        if set(34, !style.grid_template_column_names.is_empty()) {
+            self.names(&style.grid_template_column_names);
+        }
+        if set(35, !style.grid_template_row_names.is_empty()) {
+            self.names(&style.grid_template_row_names);
+        }
+        if set(36, style.grid_row != d.grid_row) {
+            self.line(&style.grid_row);
+        }
+        if set(37, style.grid_column != d.grid_column) {
+            self.line(&style.grid_column);
+        }
+
+        self.buf[mask_at..mask_at + 8].copy_from_slice(&mask.to_le_bytes());
+    }
+}
+
+/// Encodes a subtree given as `(style, child count)` pairs in pre-order
+pub(crate) fn encode(nodes: &[(&Style, usize)]) -> Vec<u8> {
+    let mut w = Writer::default();
+    w.buf.extend_from_slice(&BINARY_MAGIC);
+    w.u16(BINARY_VERSION);
+    w.len(nodes.len());
+    for &(style, child_count) in nodes {
+        w.len(child_count);
+        w.style(style);
+    }
+    w.buf
+}
+
+// =============================================================================
+// Decoding
+// =============================================================================
+
+/// A node read from a binary snapshot
+pub(crate) struct BinaryNode {
+    /// The node's style
+    pub style: Style,
+    /// Indices of the node's children
+    pub children: Vec<usize>,
+}
+
+/// Reads values from a binary snapshot, failing on truncated input
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], JsTaffyError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len());
+        let Some(end) = end else {
+            return Err(JsTaffyError::invalid_snapshot(format!(
+                "unexpected end of input at byte {}",
+                self.bytes.len()
+            )));
+        };
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], JsTaffyError> {
+        Ok(self.take(N)?.try_into().expect("slice has N bytes"))
+    }
+
+    fn invalid(&self, what: &str, at: usize) -> JsTaffyError {
+        JsTaffyError::invalid_snapshot(format!("invalid {what} at byte {at}"))
+    }
+
+    fn u8(&mut self) -> Result<u8, JsTaffyError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, JsTaffyError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, JsTaffyError> {
+        Ok(i16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, JsTaffyError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, JsTaffyError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, JsTaffyError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn len(&mut self) -> Result<usize, JsTaffyError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn str(&mut self) -> Result<String, JsTaffyError> {
+        let len = self.len()?;
+        let at = self.pos;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| self.invalid("string", at))
+    }
+
+    /// Reads a length-prefixed list
+    ///
+    /// Items are pushed one at a time so a corrupt length cannot trigger a
+    /// large allocation before the input runs out.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, JsTaffyError>,
+    ) -> Result<Vec<T>, JsTaffyError> {
+        let len = self.len()?;
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn variant<T: Copy>(&mut self, table: &[T], what: &str) -> Result<T, JsTaffyError> {
+        let at = self.pos;
+        let index = self.u8()? as usize;
+        table
+            .get(index)
+            .copied()
+            .ok_or_else(|| self.invalid(what, at))
+    }
+
+    /// Reads a length tag and its value, if the tag carries one
+    fn length(&mut self) -> Result<(u8, f32), JsTaffyError> {
+        let at = self.pos;
+        let tag = self.u8()?;
+        match tag {
+            LENGTH | PERCENT | FR | FIT_CONTENT_PX | FIT_CONTENT_PERCENT => Ok((tag, self.f32()?)),
+            AUTO | MIN_CONTENT | MAX_CONTENT => Ok((tag, 0.0)),
+            _ => Err(self.invalid("length", at)),
+        }
+    }
+
+    fn dimension(&mut self) -> Result<Dimension, JsTaffyError> {
+        let at = self.pos;
+        Ok(match self.length()? {
+            (LENGTH, v) => Dimension::length(v),
+            (PERCENT, v) => Dimension::percent(v),
+            (AUTO, _) => Dimension::auto(),
+            _ => return Err(self.invalid("dimension", at)),
+        })
+    }
+
+    fn length_percentage(&mut self) -> Result<LengthPercentage, JsTaffyError> {
+        let at = self.pos;
+        Ok(match self.length()? {
+            (LENGTH, v) => LengthPercentage::length(v),
+            (PERCENT, v) => LengthPercentage::percent(v),
+            _ => return Err(self.invalid("length-percentage", at)),
+        })
+    }
+
+    fn length_percentage_auto(&mut self) -> Result<LengthPercentageAuto, JsTaffyError> {
+        let at = self.pos;
+        Ok(match self.length()? {
+            (LENGTH, v) => LengthPercentageAuto::length(v),
+            (PERCENT, v) => LengthPercentageAuto::percent(v),
+            (AUTO, _) => LengthPercentageAuto::auto(),
+            _ => return Err(self.invalid("length-percentage-auto", at)),
+        })
+    }
+
+    fn size<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, JsTaffyError>,
+    ) -> Result<Size<T>, JsTaffyError> {
+        Ok(Size {
+            width: f(self)?,
+            height: f(self)?,
+        })
+    }
+
+    fn rect<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, JsTaffyError>,
+    ) -> Result<Rect<T>, JsTaffyError> {
+        Ok(Rect {
+            left: f(self)?,
+            right: f(self)?,
+            top: f(self)?,
+            bottom: f(self)?,
+        })
+    }
+
+    fn track(&mut self) -> Result<TrackSizingFunction, JsTaffyError> {
+        let at = self.pos;
+        let min = match self.length()? {
+            (LENGTH, v) => MinTrackSizingFunction::length(v),
+            (PERCENT, v) => MinTrackSizingFunction::percent(v),
+            (AUTO, _) => MinTrackSizingFunction::auto(),
+            (MIN_CONTENT, _) => MinTrackSizingFunction::min_content(),
+            (MAX_CONTENT, _) => MinTrackSizingFunction::max_content(),
+            _ => return Err(self.invalid("track sizing function", at)),
+        };
+        let max = match self.length()? {
+            (LENGTH, v) => MaxTrackSizingFunction::length(v),
+            (PERCENT, v) => MaxTrackSizingFunction::percent(v),
+            (AUTO, _) => MaxTrackSizingFunction::auto(),
+            (FR, v) => MaxTrackSizingFunction::fr(v),
+            (MIN_CONTENT, _) => MaxTrackSizingFunction::min_content(),
+            (MAX_CONTENT, _) => MaxTrackSizingFunction::max_content(),
+            (FIT_CONTENT_PX, v) => MaxTrackSizingFunction::fit_content_px(v),
+            (FIT_CONTENT_PERCENT, v) => MaxTrackSizingFunction::fit_content_percent(v),
+            _ => unreachable!("length() only returns known tags"),
+        };
+        Ok(TrackSizingFunction { min, max })
+    }
+
+    fn names(&mut self) -> Result<Vec<Vec<String>>, JsTaffyError> {
+        self.list(|r| r.list(Self::str))
+    }
+
+    fn template(&mut self) -> Result<Vec<GridTemplateComponent<String>>, JsTaffyError> {
+        self.list(|r| {
+            let at = r.pos;
+            match r.u8()? {
+                0 => Ok(GridTemplateComponent::Single(r.track()?)),
+                1 => {
+                    let at = r.pos;
+                    let count = match r.u8()? {
+                        0 => RepetitionCount::AutoFill,
+                        1 => RepetitionCount::AutoFit,
+                        2 => RepetitionCount::Count(r.u16()?),
+                        _ => return Err(r.invalid("repetition count", at)),
+                    };
+                    Ok(GridTemplateComponent::Repeat(GridTemplateRepetition {
+                        count,
+                        tracks: r.list(Self::track)?,
+                        line_names: r.names()?,
+                    }))
+                }
+                _ => Err(r.invalid("grid template component", at)),
+            }
+        })
+    }
+
+    fn areas(&mut self) -> Result<Vec<GridTemplateArea<String>>, JsTaffyError> {
+        self.list(|r| {
+            Ok(GridTemplateArea {
+                name: r.str()?,
+                row_start: r.u16()?,
+                row_end: r.u16()?,
+                column_start: r.u16()?,
+                column_end: r.u16()?,
+            })
+        })
+    }
+
+    fn placement(&mut self) -> Result<GridPlacement<String>, JsTaffyError> {
+        let at = self.pos;
+        Ok(match self.u8()? {
+            0 => GridPlacement::Auto,
+            1 => GridPlacement::from_line_index(self.i16()?),
+            2 => GridPlacement::from_span(self.u16()?),
+            3 => GridPlacement::NamedLine(self.str()?, self.i16()?),
+            4 => GridPlacement::NamedSpan(self.str()?, self.u16()?),
+            _ => return Err(self.invalid("grid placement", at)),
+        })
+    }
+
+    fn line(&mut self) -> Result<Line<GridPlacement<String>>, JsTaffyError> {
+        Ok(Line {
+            start: self.placement()?,
+            end: self.placement()?,
+        })
+    }
+
+    /// Reads a field mask and the fields it marks
+    fn style(&mut self) -> Result<Style, JsTaffyError> {
+        let at = self.pos;
+        let mask = self.u64()?;
+        if mask >> FIELD_COUNT != 0 {
+            return Err(self.invalid("style field mask", at));
+        }
+        let has = |bit: u32| mask & (1 << bit) != 0;
+        let mut s = Style::DEFAULT;
+
+        if has(0) {
+            s.display = self.variant(&DISPLAY, "display")?;
+        }
+        s.item_is_table = has(1);
+        s.item_is_replaced = has(2);
+        if has(3) {
+            s.box_sizing = self.variant(&BOX_SIZING, "box sizing")?;
+        }
+        if has(4) {
+            s.overflow = Point {
+                x: self.variant(&OVERFLOW, "overflow")?,
+                y: self.variant(&OVERFLOW, "overflow")?,
+            };
+        }
+        if has(5) {
+            s.scrollbar_width = self.f32()?;
+        }
+        if has(6) {
+            s.position = self.variant(&POSITION, "position")?;
+        }
+        if has(7) {
+            s.inset = self.rect(Self::length_percentage_auto)?;
+        }
+        if has(8) {
+            s.size = self.size(Self::dimension)?;
+        }
+        if has(9) {
+            s.min_size = self.size(Self::dimension)?;
+        }
+        if has(10) {
+            s.max_size = self.size(Self::dimension)?;
+        }
+        if has(11) {
+            s.aspect_ratio = Some(self.f32()?);
+        }
+        if has(12) {
+            s.margin = self.rect(Self::length_percentage_auto)?;
+        }
+        if has(13) {
+            s.padding = self.rect(Self::length_percentage)?;
+        }
+        if has(14) {
+            s.border = self.rect(Self::length_percentage)?;
+        }
+        if has(15) {
+            s.align_items = Some(self.variant(&ALIGN_ITEMS, "alignment")?);
+        }
+        if has(16) {
+            s.align_self = Some(self.variant(&ALIGN_ITEMS, "alignment")?);
+        }
+        if has(17) {
+            s.justify_items = Some(self.variant(&ALIGN_ITEMS, "alignment")?);
+        }
+        if has(18) {
+            s.justify_self = Some(self.variant(&ALIGN_ITEMS, "alignment")?);
+        }
+        if has(19) {
+            s.align_content = Some(self.variant(&ALIGN_CONTENT, "alignment")?);
+        }
+        if has(20) {
+            s.justify_content = Some(self.variant(&ALIGN_CONTENT, "alignment")?);
+        }
+        if has(21) {
+            s.gap = self.size(Self::length_percentage)?;
+        }
+        if has(22) {
+            s.text_align = self.variant(&TEXT_ALIGN, "text align")?;
+        }
+        if has(23) {
+            s.flex_direction = self.variant(&FLEX_DIRECTION, "flex direction")?;
+        }
+        if has(24) {
+            s.flex_wrap = self.variant(&FLEX_WRAP, "flex wrap")?;
+        }
+        if has(25) {
+            s.flex_basis = self.dimension()?;
+        }
+        if has(26) {
+            s.flex_grow = self.f32()?;
+        }
+        if has(27) {
+            s.flex_shrink = self.f32()?;
+        }
+        if has(28) {
+            s.grid_template_rows = self.template()?;
+        }
+        if has(29) {
+            s.grid_template_columns = self.template()?;
+        }
+        if has(30) {
+            s.grid_auto_rows = self.list(Self::track)?;
+        }
+        if has(31) {
+            s.grid_auto_columns = self.list(Self::track)?;
+        }
+        if has(32) {
+            s.grid_auto_flow = self.variant(&GRID_AUTO_FLOW, "grid auto flow")?;
+        }
+        if has(33) {
+            s.grid_template_areas = self.areas()?;
+        }
+        if has(34) {
+            s.grid_template_column_names = self.names()?;
+        }
+        if has(35) {
+            s.grid_template_row_names = self.names()?;
+        }
+        if has(36) {
+            s.grid_row = self.line()?;
+        }
+        if has(37) {
+            s.grid_column = self.line()?;
+        }
+        Ok(s)
+    }
+}
+
+/// Decodes and validates a binary snapshot
+///
+/// The root is always the first node. The child counts are guaranteed to
+/// describe a single tree covering exactly the stored nodes.
+pub(crate) fn decode(bytes: &[u8]) -> Result<Vec<BinaryNode>, JsTaffyError> {
+    let mut r = Reader { bytes, pos: 0 };
+    if bytes.len() < BINARY_MAGIC.len() || bytes[..BINARY_MAGIC.len()] != BINARY_MAGIC {
+        return Err(JsTaffyError::invalid_snapshot("missing binary header"));
+    }
+    r.pos = BINARY_MAGIC.len();
+    let version = r.u16()?;
+    if version != BINARY_VERSION {
+        return Err(JsTaffyError::invalid_snapshot(format!(
+            "unsupported version {version}, expected {BINARY_VERSION}"
+        )));
+    }
+    let count = r.len()?;
+    if count == 0 {
+        return Err(JsTaffyError::invalid_snapshot("no nodes"));
+    }
+
+    let mut nodes: Vec<BinaryNode> = Vec::new();
+    // Parents that still expect children, with the number still expected
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    for i in 0..count {
+        let child_count = r.len()?;
+        let style = r.style()?;
+        if i > 0 {
+            let Some((parent, remaining)) = open.last_mut() else {
+                return Err(JsTaffyError::invalid_snapshot(format!(
+                    "node {i} is not reachable from the root"
+                )));
+            };
+            nodes[*parent].children.push(i);
+            *remaining -= 1;
+            if *remaining == 0 {
+                open.pop();
+            }
+        }
+        if child_count > 0 {
+            open.push((i, child_count));
+        }
+        nodes.push(BinaryNode {
+            style,
+            children: Vec::new(),
+        });
+    }
+    if !open.is_empty() {
+        return Err(JsTaffyError::invalid_snapshot(
+            "child counts exceed the number of nodes",
+        ));
+    }
+    if r.pos != bytes.len() {
+        return Err(JsTaffyError::invalid_snapshot(format!(
+            "unexpected trailing data at byte {}",
+            r.pos
+        )));
+    }
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use taffy::prelude::{auto, fr, length, line, minmax, percent, span};
+
+    fn sample_style() -> Style {
+        Style {
+            display: Display::Grid,
+            item_is_table: true,
+            overflow: Point {
+                x: Overflow::Hidden,
+                y: Overflow::Scroll,
+            },
+            position: Position::Absolute,
+            inset: Rect {
+                left: length(1.0),
+                right: percent(0.5),
+                top: auto(),
+                bottom: length(-2.0),
+            },
+            size: Size {
+                width: length(100.0),
+                height: percent(0.25),
+            },
+            aspect_ratio: Some(1.5),
+            padding: Rect {
+                left: length(4.0),
+                right: length(4.0),
+                top: percent(0.1),
+                bottom: length(0.0),
+            },
+            align_self: Some(AlignItems::Baseline),
+            justify_content: Some(AlignContent::SpaceEvenly),
+            flex_grow: 2.0,
+            grid_template_columns: vec![
+                GridTemplateComponent::Single(minmax(length(10.0), fr(1.0))),
+                GridTemplateComponent::Repeat(GridTemplateRepetition {
+                    count: RepetitionCount::AutoFill,
+                    tracks: vec![TrackSizingFunction {
+                        min: MinTrackSizingFunction::min_content(),
+                        max: MaxTrackSizingFunction::fit_content_px(40.0),
+                    }],
+                    line_names: vec![vec!["a".into()], vec![]],
+                }),
+            ],
+            grid_template_areas: vec![GridTemplateArea {
+                name: "header".into(),
+                row_start: 1,
+                row_end: 2,
+                column_start: 1,
+                column_end: 3,
+            }],
+            grid_row: Line {
+                start: line(2),
+                end: span(3),
+            },
+            grid_column: Line {
+                start: GridPlacement::NamedLine("main".into(), 1),
+                end: GridPlacement::NamedSpan("side".into(), 2),
+            },
+            ..Style::default()
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let style = sample_style();
+        let default = Style::default();
+        let bytes = encode(&[(&style, 2), (&default, 0), (&style, 0)]);
+        let nodes = decode(&bytes).unwrap_or_else(|e| panic!("{}", e.message()));
+
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].style, style);
+        assert_eq!(nodes[0].children, vec![1, 2]);
+        assert_eq!(nodes[1].style, default);
+        assert_eq!(nodes[2].style, style);
+    }
+
+    #[test]
+    fn test_default_style_is_compact() {
+        let bytes = encode(&[(&Style::default(), 0)]);
+        // Header, child count and an empty field mask
+        assert_eq!(bytes.len(), 4 + 2 + 4 + 4 + 8);
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let style = sample_style();
+        let bytes = encode(&[(&style, 1), (&style, 0)]);
+
+        for len in 0..bytes.len() {
+            assert!(decode(&bytes[..len]).is_err(), "truncated to {len} bytes");
+        }
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(decode(&bad_magic).is_err());
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 2;
+        assert_eq!(
+            decode(&bad_version).err().unwrap().message(),
+            "Invalid snapshot: unsupported version 2, expected 1"
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode(&trailing).is_err());
+
+        let orphan = encode(&[(&style, 0), (&style, 0)]);
+        assert!(decode(&orphan).is_err());
+    }
+}
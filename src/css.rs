@@ -0,0 +1,1780 @@
+//! # CSS Declaration Module
+//!
+//! This module parses CSS declaration lists such as
+//! `"display: flex; flex-direction: column; gap: 8px 4px"` into the fields of a
+//! `taffy::Style`. It backs `Style.fromCss()` and `style.applyCss()`.
+//!
+//! ## Supported Syntax
+//!
+//! - Standard (kebab-case) property names for every layout property the `Style`
+//!   class exposes, plus the shorthands `margin`, `padding`, `border-width`,
+//!   `inset`, `flex`, `flex-flow`, `gap`, `overflow`, `place-items`,
+//!   `place-self`, `place-content`, `grid-row`, `grid-column` and `grid-area`
+//! - Lengths in `px` or `%` (a bare `0` is also accepted), `fr` in grid tracks
+//! - Grid track lists with `[line names]`, `repeat()`, `minmax()` and
+//!   `fit-content()`, and `grid-template-areas` strings
+//! - `/* comments */` and a trailing `!important`, which is ignored
+//!
+//! Each declaration is parsed and applied on its own: a declaration that
+//! cannot be parsed leaves the style unchanged and is reported as a
+//! [`CssIssue`] with its 1-based line and column.
+//!
+//! [`serialize`] goes the other way for `style.toCss()`.
+
+use serde::Deserialize;
+use taffy::geometry::{Line, Point, Rect, Size};
+use taffy::prelude::{TaffyAuto, TaffyGridLine, TaffyGridSpan};
+use taffy::style::{
+    AlignContent, AlignItems, BoxSizing, Dimension, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, GridTemplateArea, GridTemplateComponent, GridTemplateRepetition,
+    LengthPercentage, LengthPercentageAuto, MaxTrackSizingFunction, MinTrackSizingFunction,
+    Overflow, Position, RepetitionCount, Style, TextAlign, TrackSizingFunction,
+};
+
+use crate::types::{
+    DimensionDto, LengthPercentageAutoDto, LengthPercentageDto, MaxTrackSizingFunctionDto,
+    MinTrackSizingFunctionDto, percent_string,
+};
+
+// =============================================================================
+// Declarations
+// =============================================================================
+
+/// A declaration that could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CssIssue {
+    /// 1-based line of the start of the declaration
+    pub(crate) line: usize,
+    /// 1-based column (in characters) of the start of the declaration
+    pub(crate) column: usize,
+    /// What is wrong with the declaration
+    pub(crate) reason: String,
+}
+
+impl std::fmt::Display for CssIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.reason)
+    }
+}
+
+/// Applies every declaration in `css` to `style`
+///
+/// Returns the declarations that could not be applied, in source order.
+pub(crate) fn apply(style: &mut Style, css: &str) -> Vec<CssIssue> {
+    let source = strip_comments(css);
+    let mut issues = Vec::new();
+    for (offset, declaration) in split_declarations(&source) {
+        let trimmed = declaration.trim_start();
+        if trimmed.trim_end().is_empty() {
+            continue;
+        }
+        let start = offset + declaration.len() - trimmed.len();
+        if let Err(reason) = apply_declaration(style, trimmed.trim_end()) {
+            let (line, column) = line_column(&source, start);
+            issues.push(CssIssue {
+                line,
+                column,
+                reason,
+            });
+        }
+    }
+    issues
+}
+
+/// Replaces `/* comments */` with spaces, keeping line breaks
+///
+/// Every character maps to one character, so lines and columns in the
+/// result match the original; byte offsets do not.
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        let comment_len = rest[start + 2..]
+            .find("*/")
+            .map_or(rest.len() - start, |end| end + 4);
+        let comment = &rest[start..start + comment_len];
+        out.extend(comment.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+        rest = &rest[start + comment_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Splits a declaration list on top-level semicolons
+///
+/// Yields each declaration with its byte offset in `source`.
+fn split_declarations(source: &str) -> Vec<(usize, &str)> {
+    let mut declarations = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in source.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push((start, &source[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push((start, &source[start..]));
+    declarations
+}
+
+/// Converts a byte offset into a 1-based line and character column
+fn line_column(css: &str, offset: usize) -> (usize, usize) {
+    let before = &css[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Parses a single `property: value` declaration and applies it
+fn apply_declaration(style: &mut Style, declaration: &str) -> Result<(), String> {
+    let Some((property, value)) = declaration.split_once(':') else {
+        return Err(format!("expected `property: value`, found `{declaration}`"));
+    };
+    let property = property.trim().to_ascii_lowercase();
+    let mut value = value.trim();
+    if let Some(stripped) = strip_suffix_ignore_case(value, "!important") {
+        value = stripped.trim_end();
+    }
+    if value.is_empty() {
+        return Err(format!("{property}: missing value"));
+    }
+    let parts = components(value).map_err(|reason| format!("{property}: {reason}"))?;
+    apply_property(style, &property, &parts).map_err(|reason| match reason {
+        Invalid::UnknownProperty => format!("unknown property `{property}`"),
+        Invalid::Value(reason) => format!("{property}: {reason}"),
+    })
+}
+
+/// Why a property could not be applied
+enum Invalid {
+    /// The property name is not supported
+    UnknownProperty,
+    /// The value is malformed, with a description
+    Value(String),
+}
+
+impl From<String> for Invalid {
+    fn from(reason: String) -> Self {
+        Invalid::Value(reason)
+    }
+}
+
+// =============================================================================
+// Properties
+// =============================================================================
+
+/// Applies a parsed value to the style field(s) behind `property`
+///
+/// Every value is parsed before any field is written, so a failure leaves the
+/// style unchanged.
+fn apply_property(style: &mut Style, property: &str, v: &[&str]) -> Result<(), Invalid> {
+    match property {
+        // Layout Mode
+        "display" => style.display = single(v, display)?,
+        "position" => style.position = single(v, position)?,
+        "box-sizing" => style.box_sizing = single(v, box_sizing)?,
+
+        // Overflow
+        "overflow" => {
+            let (x, y) = pair(v, overflow)?;
+            style.overflow = Point { x, y };
+        }
+        "overflow-x" => style.overflow.x = single(v, overflow)?,
+        "overflow-y" => style.overflow.y = single(v, overflow)?,
+
+        // Flexbox
+        "flex-direction" => style.flex_direction = single(v, flex_direction)?,
+        "flex-wrap" => style.flex_wrap = single(v, flex_wrap)?,
+        "flex-flow" => {
+            let (direction, wrap) = flex_flow(v)?;
+            style.flex_direction = direction.unwrap_or(FlexDirection::Row);
+            style.flex_wrap = wrap.unwrap_or(FlexWrap::NoWrap);
+        }
+        "flex-grow" => style.flex_grow = single(v, non_negative)?,
+        "flex-shrink" => style.flex_shrink = single(v, non_negative)?,
+        "flex-basis" => style.flex_basis = single(v, dimension)?,
+        "flex" => {
+            let (grow, shrink, basis) = flex(v)?;
+            style.flex_grow = grow;
+            style.flex_shrink = shrink;
+            style.flex_basis = basis;
+        }
+
+        // Alignment
+        "align-items" => style.align_items = single(v, align_items)?,
+        "align-self" => style.align_self = single(v, align_self)?,
+        "align-content" => style.align_content = single(v, align_content)?,
+        "justify-content" => style.justify_content = single(v, align_content)?,
+        "justify-items" => style.justify_items = single(v, align_items)?,
+        "justify-self" => style.justify_self = single(v, align_self)?,
+        "place-items" => {
+            let (align, justify) = pair(v, align_items)?;
+            style.align_items = align;
+            style.justify_items = justify;
+        }
+        "place-self" => {
+            let (align, justify) = pair(v, align_self)?;
+            style.align_self = align;
+            style.justify_self = justify;
+        }
+        "place-content" => {
+            let (align, justify) = pair(v, align_content)?;
+            style.align_content = align;
+            style.justify_content = justify;
+        }
+
+        // Sizing
+        "aspect-ratio" => style.aspect_ratio = aspect_ratio(v)?,
+        "width" => style.size.width = single(v, dimension)?,
+        "height" => style.size.height = single(v, dimension)?,
+        "min-width" => style.min_size.width = single(v, dimension)?,
+        "min-height" => style.min_size.height = single(v, dimension)?,
+        "max-width" => style.max_size.width = single(v, max_dimension)?,
+        "max-height" => style.max_size.height = single(v, max_dimension)?,
+
+        // Spacing
+        "margin" => style.margin = sides(v, length_percentage_auto)?,
+        "margin-top" => style.margin.top = single(v, length_percentage_auto)?,
+        "margin-right" => style.margin.right = single(v, length_percentage_auto)?,
+        "margin-bottom" => style.margin.bottom = single(v, length_percentage_auto)?,
+        "margin-left" => style.margin.left = single(v, length_percentage_auto)?,
+        "padding" => style.padding = sides(v, length_percentage)?,
+        "padding-top" => style.padding.top = single(v, length_percentage)?,
+        "padding-right" => style.padding.right = single(v, length_percentage)?,
+        "padding-bottom" => style.padding.bottom = single(v, length_percentage)?,
+        "padding-left" => style.padding.left = single(v, length_percentage)?,
+        "border-width" => style.border = sides(v, length_percentage)?,
+        "border-top-width" => style.border.top = single(v, length_percentage)?,
+        "border-right-width" => style.border.right = single(v, length_percentage)?,
+        "border-bottom-width" => style.border.bottom = single(v, length_percentage)?,
+        "border-left-width" => style.border.left = single(v, length_percentage)?,
+        "inset" => style.inset = sides(v, length_percentage_auto)?,
+        "top" => style.inset.top = single(v, length_percentage_auto)?,
+        "right" => style.inset.right = single(v, length_percentage_auto)?,
+        "bottom" => style.inset.bottom = single(v, length_percentage_auto)?,
+        "left" => style.inset.left = single(v, length_percentage_auto)?,
+        "gap" => {
+            let (row, column) = pair(v, length_percentage)?;
+            style.gap = Size {
+                width: column,
+                height: row,
+            };
+        }
+        "row-gap" => style.gap.height = single(v, length_percentage)?,
+        "column-gap" => style.gap.width = single(v, length_percentage)?,
+
+        // Block layout
+        "text-align" => style.text_align = single(v, text_align)?,
+
+        // Grid layout
+        "grid-auto-flow" => style.grid_auto_flow = grid_auto_flow(v)?,
+        "grid-row" => style.grid_row = grid_line(v)?,
+        "grid-column" => style.grid_column = grid_line(v)?,
+        "grid-row-start" => style.grid_row.start = grid_placement(v)?,
+        "grid-row-end" => style.grid_row.end = grid_placement(v)?,
+        "grid-column-start" => style.grid_column.start = grid_placement(v)?,
+        "grid-column-end" => style.grid_column.end = grid_placement(v)?,
+        "grid-area" => {
+            let (row, column) = grid_area(v)?;
+            style.grid_row = row;
+            style.grid_column = column;
+        }
+        "grid-template-rows" => {
+            let (tracks, names) = track_list(v)?;
+            style.grid_template_rows = tracks;
+            style.grid_template_row_names = names;
+        }
+        "grid-template-columns" => {
+            let (tracks, names) = track_list(v)?;
+            style.grid_template_columns = tracks;
+            style.grid_template_column_names = names;
+        }
+        "grid-auto-rows" => style.grid_auto_rows = auto_tracks(v)?,
+        "grid-auto-columns" => style.grid_auto_columns = auto_tracks(v)?,
+        "grid-template-areas" => style.grid_template_areas = template_areas(v)?,
+
+        _ => return Err(Invalid::UnknownProperty),
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Value Tokenizing
+// =============================================================================
+
+/// Splits a value into its space-separated components
+///
+/// Functions such as `minmax(10px, 1fr)` and quoted strings stay whole, and
+/// `/` is always a component of its own.
+fn components(value: &str) -> Result<Vec<&str>, String> {
+    let mut parts = Vec::new();
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in value.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                start.get_or_insert(i);
+            }
+            '(' => {
+                depth += 1;
+                start.get_or_insert(i);
+            }
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| "unbalanced parentheses".to_string())?;
+            }
+            c if depth == 0 && (c.is_whitespace() || c == '/') => {
+                if let Some(s) = start.take() {
+                    parts.push(&value[s..i]);
+                }
+                if c == '/' {
+                    parts.push("/");
+                }
+            }
+            _ => {
+                start.get_or_insert(i);
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated string".to_string());
+    }
+    if depth != 0 {
+        return Err("unbalanced parentheses".to_string());
+    }
+    if let Some(s) = start {
+        parts.push(&value[s..]);
+    }
+    Ok(parts)
+}
+
+/// Strips an ASCII case-insensitive suffix
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = value.len().checked_sub(suffix.len())?;
+    (value.is_char_boundary(split) && value[split..].eq_ignore_ascii_case(suffix))
+        .then(|| &value[..split])
+}
+
+/// Splits `name(arguments)` into its name and comma-separated arguments
+fn function<'a>(token: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let open = token.find('(')?;
+    if !token[..open].eq_ignore_ascii_case(name) || !token.ends_with(')') {
+        return None;
+    }
+    let inner = &token[open + 1..token.len() - 1];
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(inner[start..].trim());
+    Some(args)
+}
+
+// =============================================================================
+// Value Arity
+// =============================================================================
+
+/// Parses a value made of exactly one component
+fn single<T>(v: &[&str], parse: impl Fn(&str) -> Result<T, String>) -> Result<T, String> {
+    match v {
+        [value] => parse(value),
+        _ => Err(format!("expected a single value, found `{}`", v.join(" "))),
+    }
+}
+
+/// Parses one or two components, where a missing second value copies the first
+fn pair<T: Clone>(v: &[&str], parse: impl Fn(&str) -> Result<T, String>) -> Result<(T, T), String> {
+    match v {
+        [a] => {
+            let a = parse(a)?;
+            Ok((a.clone(), a))
+        }
+        [a, b] => Ok((parse(a)?, parse(b)?)),
+        _ => Err(format!("expected 1 or 2 values, found {}", v.len())),
+    }
+}
+
+/// Parses a 1-4 value box shorthand in `top right bottom left` order
+fn sides<T: Clone>(
+    v: &[&str],
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Rect<T>, String> {
+    let values = v.iter().map(|s| parse(s)).collect::<Result<Vec<_>, _>>()?;
+    let (top, right, bottom, left) = match values.as_slice() {
+        [all] => (all, all, all, all),
+        [y, x] => (y, x, y, x),
+        [top, x, bottom] => (top, x, bottom, x),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return Err(format!("expected 1 to 4 values, found {}", v.len())),
+    };
+    Ok(Rect {
+        left: left.clone(),
+        right: right.clone(),
+        top: top.clone(),
+        bottom: bottom.clone(),
+    })
+}
+
+// =============================================================================
+// Numbers and Lengths
+// =============================================================================
+
+/// Parses a finite CSS number
+fn number(token: &str) -> Result<f32, String> {
+    let looks_numeric = token
+        .trim_start_matches(['+', '-'])
+        .starts_with(|c: char| c.is_ascii_digit() || c == '.');
+    token
+        .parse::<f32>()
+        .ok()
+        .filter(|n| looks_numeric && n.is_finite())
+        .ok_or_else(|| format!("`{token}` is not a number"))
+}
+
+/// Parses a number that must not be negative
+fn non_negative(token: &str) -> Result<f32, String> {
+    let n = number(token)?;
+    if n < 0.0 {
+        return Err(format!("`{token}` must not be negative"));
+    }
+    Ok(n)
+}
+
+/// Parses an integer, as used by grid lines and repeat counts
+fn integer(token: &str) -> Result<i32, String> {
+    let n = number(token)?;
+    if n.fract() != 0.0 || n.abs() > i16::MAX as f32 {
+        return Err(format!("`{token}` is not an integer"));
+    }
+    Ok(n as i32)
+}
+
+/// Parses a `px` or `%` length
+fn length_percentage(token: &str) -> Result<LengthPercentage, String> {
+    if let Some(n) = token.strip_suffix('%') {
+        return Ok(LengthPercentage::percent(number(n)? / 100.0));
+    }
+    if let Some(n) = strip_suffix_ignore_case(token, "px") {
+        return Ok(LengthPercentage::length(number(n)?));
+    }
+    match number(token) {
+        Ok(0.0) => Ok(LengthPercentage::length(0.0)),
+        Ok(_) => Err(format!("`{token}` needs a unit (px or %)")),
+        Err(_) => Err(format!("`{token}` is not a length (px or %)")),
+    }
+}
+
+/// Parses a length or `auto`
+fn length_percentage_auto(token: &str) -> Result<LengthPercentageAuto, String> {
+    if token.eq_ignore_ascii_case("auto") {
+        return Ok(LengthPercentageAuto::AUTO);
+    }
+    length_percentage(token).map(Into::into)
+}
+
+/// Parses a size: a length or `auto`
+fn dimension(token: &str) -> Result<Dimension, String> {
+    length_percentage_auto(token).map(Into::into)
+}
+
+/// Parses a maximum size, where `none` means no limit
+fn max_dimension(token: &str) -> Result<Dimension, String> {
+    if token.eq_ignore_ascii_case("none") {
+        return Ok(Dimension::AUTO);
+    }
+    dimension(token)
+}
+
+// =============================================================================
+// Keywords
+// =============================================================================
+
+/// Matches an ASCII case-insensitive keyword against a table
+fn keyword<T: Copy>(token: &str, table: &[(&str, T)]) -> Result<T, String> {
+    table
+        .iter()
+        .find(|(name, _)| token.eq_ignore_ascii_case(name))
+        .map(|&(_, value)| value)
+        .ok_or_else(|| {
+            let names: Vec<&str> = table.iter().map(|(name, _)| *name).collect();
+            format!("`{token}` is not one of {}", names.join(", "))
+        })
+}
+
+/// The keyword of `value` in a table; the first entry wins when several share it
+fn keyword_name<T: PartialEq>(value: &T, table: &[(&'static str, T)]) -> &'static str {
+    table
+        .iter()
+        .find(|(_, v)| v == value)
+        .map_or("", |(name, _)| name)
+}
+
+const DISPLAY: &[(&str, Display)] = &[
+    ("block", Display::Block),
+    ("flex", Display::Flex),
+    ("grid", Display::Grid),
+    ("none", Display::None),
+];
+
+const POSITION: &[(&str, Position)] = &[
+    ("relative", Position::Relative),
+    ("absolute", Position::Absolute),
+];
+
+const BOX_SIZING: &[(&str, BoxSizing)] = &[
+    ("border-box", BoxSizing::BorderBox),
+    ("content-box", BoxSizing::ContentBox),
+];
+
+const OVERFLOW: &[(&str, Overflow)] = &[
+    ("visible", Overflow::Visible),
+    ("clip", Overflow::Clip),
+    ("hidden", Overflow::Hidden),
+    ("scroll", Overflow::Scroll),
+];
+
+const FLEX_DIRECTION: &[(&str, FlexDirection)] = &[
+    ("row", FlexDirection::Row),
+    ("row-reverse", FlexDirection::RowReverse),
+    ("column", FlexDirection::Column),
+    ("column-reverse", FlexDirection::ColumnReverse),
+];
+
+const FLEX_WRAP: &[(&str, FlexWrap)] = &[
+    ("nowrap", FlexWrap::NoWrap),
+    ("wrap", FlexWrap::Wrap),
+    ("wrap-reverse", FlexWrap::WrapReverse),
+];
+
+/// Keywords shared by `align-items`, `justify-items` and the `*-self` properties
+const ALIGN_ITEMS: &[(&str, Option<AlignItems>)] = &[
+    ("normal", None),
+    ("start", Some(AlignItems::Start)),
+    ("end", Some(AlignItems::End)),
+    ("flex-start", Some(AlignItems::FlexStart)),
+    ("flex-end", Some(AlignItems::FlexEnd)),
+    ("center", Some(AlignItems::Center)),
+    ("baseline", Some(AlignItems::Baseline)),
+    ("stretch", Some(AlignItems::Stretch)),
+];
+
+/// Keywords shared by `align-content` and `justify-content`
+const ALIGN_CONTENT: &[(&str, Option<AlignContent>)] = &[
+    ("normal", None),
+    ("start", Some(AlignContent::Start)),
+    ("end", Some(AlignContent::End)),
+    ("flex-start", Some(AlignContent::FlexStart)),
+    ("flex-end", Some(AlignContent::FlexEnd)),
+    ("center", Some(AlignContent::Center)),
+    ("stretch", Some(AlignContent::Stretch)),
+    ("space-between", Some(AlignContent::SpaceBetween)),
+    ("space-around", Some(AlignContent::SpaceAround)),
+    ("space-evenly", Some(AlignContent::SpaceEvenly)),
+];
+
+/// Standard `text-align` keywords do not affect layout; only the legacy
+/// `-webkit-*` alignments used for block children do
+const TEXT_ALIGN: &[(&str, TextAlign)] = &[
+    ("-webkit-left", TextAlign::LegacyLeft),
+    ("-webkit-right", TextAlign::LegacyRight),
+    ("-webkit-center", TextAlign::LegacyCenter),
+    ("start", TextAlign::Auto),
+    ("end", TextAlign::Auto),
+    ("left", TextAlign::Auto),
+    ("right", TextAlign::Auto),
+    ("center", TextAlign::Auto),
+    ("justify", TextAlign::Auto),
+];
+
+fn display(token: &str) -> Result<Display, String> {
+    keyword(token, DISPLAY)
+}
+
+fn position(token: &str) -> Result<Position, String> {
+    keyword(token, POSITION)
+}
+
+fn box_sizing(token: &str) -> Result<BoxSizing, String> {
+    keyword(token, BOX_SIZING)
+}
+
+fn overflow(token: &str) -> Result<Overflow, String> {
+    keyword(token, OVERFLOW)
+}
+
+fn flex_direction(token: &str) -> Result<FlexDirection, String> {
+    keyword(token, FLEX_DIRECTION)
+}
+
+fn flex_wrap(token: &str) -> Result<FlexWrap, String> {
+    keyword(token, FLEX_WRAP)
+}
+
+fn align_items(token: &str) -> Result<Option<AlignItems>, String> {
+    keyword(token, ALIGN_ITEMS)
+}
+
+/// `align-self` additionally accepts `auto`, which (like `normal`) defers to the parent
+fn align_self(token: &str) -> Result<Option<AlignItems>, String> {
+    if token.eq_ignore_ascii_case("auto") {
+        return Ok(None);
+    }
+    align_items(token)
+}
+
+fn align_content(token: &str) -> Result<Option<AlignContent>, String> {
+    keyword(token, ALIGN_CONTENT)
+}
+
+fn text_align(token: &str) -> Result<TextAlign, String> {
+    keyword(token, TEXT_ALIGN)
+}
+
+// =============================================================================
+// Shorthands
+// =============================================================================
+
+/// Parses `flex-flow: <direction> || <wrap>`
+fn flex_flow(v: &[&str]) -> Result<(Option<FlexDirection>, Option<FlexWrap>), String> {
+    if v.is_empty() || v.len() > 2 {
+        return Err(format!("expected 1 or 2 values, found {}", v.len()));
+    }
+    let (mut direction, mut wrap) = (None, None);
+    for token in v {
+        if direction.is_none() {
+            if let Ok(d) = flex_direction(token) {
+                direction = Some(d);
+                continue;
+            }
+        }
+        if wrap.is_none() {
+            if let Ok(w) = flex_wrap(token) {
+                wrap = Some(w);
+                continue;
+            }
+        }
+        return Err(format!("`{token}` is not a flex direction or wrap mode"));
+    }
+    Ok((direction, wrap))
+}
+
+/// Parses `flex: none | auto | initial | <grow> [<shrink>] || <basis>`
+fn flex(v: &[&str]) -> Result<(f32, f32, Dimension), String> {
+    if let [token] = v {
+        let keywords = [
+            ("none", (0.0, 0.0)),
+            ("auto", (1.0, 1.0)),
+            ("initial", (0.0, 1.0)),
+        ];
+        if let Some(&(_, (grow, shrink))) = keywords
+            .iter()
+            .find(|(name, _)| token.eq_ignore_ascii_case(name))
+        {
+            return Ok((grow, shrink, Dimension::AUTO));
+        }
+    }
+    let mut factors = Vec::new();
+    let mut basis = None;
+    for token in v {
+        // The two flex factors must be adjacent, before or after the basis
+        let factors_open = factors.len() < 2 && (basis.is_none() || factors.is_empty());
+        match non_negative(token) {
+            Ok(n) if factors_open => factors.push(n),
+            _ if basis.is_none() => basis = Some(dimension(token)?),
+            _ => return Err(format!("unexpected `{token}`")),
+        }
+    }
+    let grow = factors.first().copied().unwrap_or(1.0);
+    let shrink = factors.get(1).copied().unwrap_or(1.0);
+    let basis = basis.unwrap_or(if factors.is_empty() {
+        Dimension::AUTO
+    } else {
+        Dimension::length(0.0)
+    });
+    Ok((grow, shrink, basis))
+}
+
+/// Parses `aspect-ratio: auto | <number> | <number> / <number>`
+fn aspect_ratio(v: &[&str]) -> Result<Option<f32>, String> {
+    match v {
+        [token] if token.eq_ignore_ascii_case("auto") => Ok(None),
+        [ratio] => Ok(Some(non_negative(ratio)?)),
+        [width, "/", height] => {
+            let (width, height) = (non_negative(width)?, non_negative(height)?);
+            if height == 0.0 {
+                return Err("the height of the ratio must not be zero".to_string());
+            }
+            Ok(Some(width / height))
+        }
+        _ => Err(format!("`{}` is not a ratio", v.join(" "))),
+    }
+}
+
+/// Parses `grid-auto-flow: [row | column] || dense`
+fn grid_auto_flow(v: &[&str]) -> Result<GridAutoFlow, String> {
+    let mut column = None;
+    let mut dense = false;
+    for token in v {
+        match token.to_ascii_lowercase().as_str() {
+            "row" if column.is_none() => column = Some(false),
+            "column" if column.is_none() => column = Some(true),
+            "dense" if !dense => dense = true,
+            _ => return Err(format!("unexpected `{token}`")),
+        }
+    }
+    if v.is_empty() {
+        return Err("missing value".to_string());
+    }
+    Ok(match (column.unwrap_or(false), dense) {
+        (false, false) => GridAutoFlow::Row,
+        (false, true) => GridAutoFlow::RowDense,
+        (true, false) => GridAutoFlow::Column,
+        (true, true) => GridAutoFlow::ColumnDense,
+    })
+}
+
+// =============================================================================
+// Grid Placement
+// =============================================================================
+
+/// Parses one grid line: `auto | <integer> [<name>] | span [<integer>] [<name>] | <name>`
+fn grid_placement(v: &[&str]) -> Result<GridPlacement, String> {
+    if let [token] = v {
+        if token.eq_ignore_ascii_case("auto") {
+            return Ok(GridPlacement::Auto);
+        }
+    }
+    let mut span = false;
+    let mut index = None;
+    let mut name = None;
+    for token in v {
+        if token.eq_ignore_ascii_case("span") && !span {
+            span = true;
+        } else if let (None, Ok(n)) = (index, integer(token)) {
+            index = Some(n);
+        } else if name.is_none() && is_custom_ident(token) {
+            name = Some(token.to_string());
+        } else {
+            return Err(format!("`{}` is not a grid line", v.join(" ")));
+        }
+    }
+    match (span, index, name) {
+        (false, Some(0), _) => Err("grid line 0 is invalid".to_string()),
+        (false, Some(n), None) => Ok(GridPlacement::from_line_index(n as i16)),
+        (false, n, Some(name)) => Ok(GridPlacement::NamedLine(name, n.unwrap_or(1) as i16)),
+        (true, n, _) if n.is_some_and(|n| n < 1) => Err("span must be positive".to_string()),
+        (true, n, None) => Ok(GridPlacement::from_span(n.unwrap_or(1) as u16)),
+        (true, n, Some(name)) => Ok(GridPlacement::NamedSpan(name, n.unwrap_or(1) as u16)),
+        _ => Err(format!("`{}` is not a grid line", v.join(" "))),
+    }
+}
+
+/// Whether a token can name a grid line or area
+fn is_custom_ident(token: &str) -> bool {
+    let reserved = ["auto", "span", "inherit", "initial", "unset", "default"];
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '-')
+        && token
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        && !reserved.iter().any(|r| token.eq_ignore_ascii_case(r))
+}
+
+/// Splits components on `/` separators
+fn slash_groups<'a, 'b>(v: &'b [&'a str]) -> Vec<&'b [&'a str]> {
+    v.split(|token| *token == "/").collect()
+}
+
+/// The value of an omitted grid line after `start`: the same name, or `auto`
+fn omitted_line(start: &GridPlacement) -> GridPlacement {
+    match start {
+        GridPlacement::NamedLine(name, _) => GridPlacement::NamedLine(name.clone(), 1),
+        _ => GridPlacement::Auto,
+    }
+}
+
+/// Parses `grid-row`/`grid-column`: `<line> [/ <line>]`
+fn grid_line(v: &[&str]) -> Result<Line<GridPlacement>, String> {
+    match slash_groups(v).as_slice() {
+        [start] => {
+            let start = grid_placement(start)?;
+            let end = omitted_line(&start);
+            Ok(Line { start, end })
+        }
+        [start, end] => Ok(Line {
+            start: grid_placement(start)?,
+            end: grid_placement(end)?,
+        }),
+        _ => Err("expected `<start> / <end>`".to_string()),
+    }
+}
+
+/// Parses `grid-area: <row-start> [/ <column-start> [/ <row-end> [/ <column-end>]]]`
+fn grid_area(v: &[&str]) -> Result<(Line<GridPlacement>, Line<GridPlacement>), String> {
+    let groups = slash_groups(v);
+    if groups.len() > 4 {
+        return Err("expected at most 4 grid lines".to_string());
+    }
+    let lines = groups
+        .iter()
+        .map(|group| grid_placement(group))
+        .collect::<Result<Vec<_>, _>>()?;
+    let row_start = lines[0].clone();
+    let column_start = lines
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| omitted_line(&row_start));
+    let row_end = lines
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| omitted_line(&row_start));
+    let column_end = lines
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| omitted_line(&column_start));
+    Ok((
+        Line {
+            start: row_start,
+            end: row_end,
+        },
+        Line {
+            start: column_start,
+            end: column_end,
+        },
+    ))
+}
+
+// =============================================================================
+// Grid Tracks
+// =============================================================================
+
+/// Parses the minimum of a track: a length, `auto`, `min-content` or `max-content`
+fn min_track(token: &str) -> Result<MinTrackSizingFunction, String> {
+    match token.to_ascii_lowercase().as_str() {
+        "auto" => Ok(MinTrackSizingFunction::auto()),
+        "min-content" => Ok(MinTrackSizingFunction::min_content()),
+        "max-content" => Ok(MinTrackSizingFunction::max_content()),
+        _ => length_percentage(token).map(Into::into),
+    }
+}
+
+/// Parses the maximum of a track, which may also be a flexible `fr` size
+fn max_track(token: &str) -> Result<MaxTrackSizingFunction, String> {
+    match token.to_ascii_lowercase().as_str() {
+        "auto" => Ok(MaxTrackSizingFunction::auto()),
+        "min-content" => Ok(MaxTrackSizingFunction::min_content()),
+        "max-content" => Ok(MaxTrackSizingFunction::max_content()),
+        _ => match strip_suffix_ignore_case(token, "fr") {
+            Some(n) => Ok(MaxTrackSizingFunction::fr(non_negative(n)?)),
+            None => length_percentage(token).map(Into::into),
+        },
+    }
+}
+
+/// Parses a track size: a breadth, `minmax()` or `fit-content()`
+fn track_size(token: &str) -> Result<TrackSizingFunction, String> {
+    if let Some(args) = function(token, "minmax") {
+        let [min, max] = args.as_slice() else {
+            return Err("minmax() takes two arguments".to_string());
+        };
+        return Ok(TrackSizingFunction {
+            min: min_track(min)?,
+            max: max_track(max)?,
+        });
+    }
+    if let Some(args) = function(token, "fit-content") {
+        let [limit] = args.as_slice() else {
+            return Err("fit-content() takes one argument".to_string());
+        };
+        let limit = length_percentage(limit)?;
+        let raw = limit.into_raw();
+        let max = if raw.tag() == taffy::style::CompactLength::PERCENT_TAG {
+            MaxTrackSizingFunction::fit_content_percent(raw.value())
+        } else {
+            MaxTrackSizingFunction::fit_content_px(raw.value())
+        };
+        return Ok(TrackSizingFunction {
+            min: MinTrackSizingFunction::auto(),
+            max,
+        });
+    }
+    let max = max_track(token)?;
+    let min = if max.is_fr() {
+        MinTrackSizingFunction::auto()
+    } else {
+        min_track(token)?
+    };
+    Ok(TrackSizingFunction { min, max })
+}
+
+/// The names of each line of a track list
+type LineNames = Vec<Vec<String>>;
+
+/// Parses a `[name ...]` line name group
+fn line_names(token: &str) -> Option<Result<Vec<String>, String>> {
+    let inner = token.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        inner
+            .split_whitespace()
+            .map(|name| {
+                if is_custom_ident(name) {
+                    Ok(name.to_string())
+                } else {
+                    Err(format!("`{name}` is not a valid line name"))
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Parses a track list with optional line names between the tracks
+///
+/// Returns the tracks and one name list per line (one more than the
+/// tracks), or no name lists at all when no names are given.
+fn names_and_tracks<T>(
+    v: &[&str],
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<(Vec<T>, LineNames), String> {
+    let mut tracks = Vec::new();
+    let mut names = vec![Vec::new()];
+    for token in bracket_groups(v)? {
+        match line_names(&token) {
+            Some(group) => names.last_mut().unwrap().extend(group?),
+            None => {
+                tracks.push(parse(&token)?);
+                names.push(Vec::new());
+            }
+        }
+    }
+    if names.iter().all(Vec::is_empty) {
+        names.clear();
+    }
+    Ok((tracks, names))
+}
+
+/// Joins `[`...`]` line name groups that span several components
+fn bracket_groups(v: &[&str]) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut open: Option<String> = None;
+    for token in v {
+        match open.as_mut() {
+            Some(group) => {
+                group.push(' ');
+                group.push_str(token);
+            }
+            None if token.starts_with('[') => open = Some(token.to_string()),
+            None => {
+                tokens.push(token.to_string());
+                continue;
+            }
+        }
+        if token.ends_with(']') {
+            tokens.extend(open.take());
+        }
+    }
+    if open.is_some() {
+        return Err("unterminated `[` line names".to_string());
+    }
+    Ok(tokens)
+}
+
+/// Parses `grid-template-rows`/`grid-template-columns`
+fn track_list(v: &[&str]) -> Result<(Vec<GridTemplateComponent<String>>, LineNames), String> {
+    if let [token] = v {
+        if token.eq_ignore_ascii_case("none") {
+            return Ok((Vec::new(), Vec::new()));
+        }
+    }
+    names_and_tracks(v, |token| {
+        let Some(args) = function(token, "repeat") else {
+            return track_size(token).map(GridTemplateComponent::Single);
+        };
+        let [count, tracks] = args.as_slice() else {
+            return Err("repeat() takes a count and a track list".to_string());
+        };
+        let count = match count.to_ascii_lowercase().as_str() {
+            "auto-fill" => RepetitionCount::AutoFill,
+            "auto-fit" => RepetitionCount::AutoFit,
+            _ => match integer(count)? {
+                n if n >= 1 => RepetitionCount::Count(n as u16),
+                _ => return Err("the repeat() count must be positive".to_string()),
+            },
+        };
+        let (tracks, line_names) = names_and_tracks(&components(tracks)?, track_size)?;
+        if tracks.is_empty() {
+            return Err("repeat() needs at least one track".to_string());
+        }
+        Ok(GridTemplateComponent::Repeat(GridTemplateRepetition {
+            count,
+            tracks,
+            line_names,
+        }))
+    })
+}
+
+/// Parses `grid-auto-rows`/`grid-auto-columns`
+fn auto_tracks(v: &[&str]) -> Result<Vec<TrackSizingFunction>, String> {
+    v.iter().map(|token| track_size(token)).collect()
+}
+
+/// Parses `grid-template-areas: none | <string>+`
+fn template_areas(v: &[&str]) -> Result<Vec<GridTemplateArea<String>>, String> {
+    if let [token] = v {
+        if token.eq_ignore_ascii_case("none") {
+            return Ok(Vec::new());
+        }
+    }
+    let mut areas: Vec<GridTemplateArea<String>> = Vec::new();
+    let mut columns = None;
+    for (row, token) in v.iter().enumerate() {
+        let inner = token
+            .strip_prefix(['"', '\''])
+            .and_then(|t| t.strip_suffix(['"', '\'']))
+            .ok_or_else(|| format!("expected a quoted row, found `{token}`"))?;
+        let cells: Vec<&str> = inner.split_whitespace().collect();
+        if *columns.get_or_insert(cells.len()) != cells.len() || cells.is_empty() {
+            return Err("every row must have the same, non-zero number of cells".to_string());
+        }
+        for (column, name) in cells.iter().enumerate() {
+            if name.chars().all(|c| c == '.') {
+                continue;
+            }
+            let (row, column) = (row as u16 + 1, column as u16 + 1);
+            match areas.iter_mut().find(|a| a.name == *name) {
+                Some(area) => {
+                    area.row_end = area.row_end.max(row + 1);
+                    area.column_start = area.column_start.min(column);
+                    area.column_end = area.column_end.max(column + 1);
+                }
+                None => areas.push(GridTemplateArea {
+                    name: name.to_string(),
+                    row_start: row,
+                    row_end: row + 1,
+                    column_start: column,
+                    column_end: column + 1,
+                }),
+            }
+        }
+    }
+    for area in &areas {
+        let cells = (area.row_end - area.row_start) * (area.column_end - area.column_start);
+        let count = v
+            .iter()
+            .flat_map(|token| token[1..token.len() - 1].split_whitespace())
+            .filter(|name| *name == area.name)
+            .count();
+        if count != cells as usize {
+            return Err(format!("grid area `{}` is not a rectangle", area.name));
+        }
+    }
+    Ok(areas)
+}
+
+// =============================================================================
+// Serialization
+// =============================================================================
+
+/// Options accepted by `style.toCss()`
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct CssOptionsDto {
+    /// Whether properties equal to their default are left out
+    pub only_non_default: bool,
+}
+
+/// Formats `style` as a CSS declaration block, one `property: value;` per line
+///
+/// With `only_non_default`, properties equal to `Style::default()` are left
+/// out. Shorthands are used when every value they set is written anyway, and
+/// the output parses back with [`apply`] to the same style. `item_is_table`,
+/// `item_is_replaced` and `scrollbar_width` have no CSS property and are not
+/// written.
+pub(crate) fn serialize(style: &Style, only_non_default: bool) -> String {
+    let d = Style::default();
+    let mut out = Declarations {
+        lines: Vec::new(),
+        all: !only_non_default,
+    };
+
+    // Layout Mode
+    out.push(
+        style.display != d.display,
+        "display",
+        keyword_name(&style.display, DISPLAY),
+    );
+    out.push(
+        style.position != d.position,
+        "position",
+        keyword_name(&style.position, POSITION),
+    );
+    out.push(
+        style.box_sizing != d.box_sizing,
+        "box-sizing",
+        keyword_name(&style.box_sizing, BOX_SIZING),
+    );
+    out.push_pair(
+        "overflow",
+        (
+            "overflow-x",
+            style.overflow.x != d.overflow.x,
+            keyword_name(&style.overflow.x, OVERFLOW),
+        ),
+        (
+            "overflow-y",
+            style.overflow.y != d.overflow.y,
+            keyword_name(&style.overflow.y, OVERFLOW),
+        ),
+    );
+
+    // Flexbox
+    out.push_pair(
+        "flex-flow",
+        (
+            "flex-direction",
+            style.flex_direction != d.flex_direction,
+            keyword_name(&style.flex_direction, FLEX_DIRECTION),
+        ),
+        (
+            "flex-wrap",
+            style.flex_wrap != d.flex_wrap,
+            keyword_name(&style.flex_wrap, FLEX_WRAP),
+        ),
+    );
+    out.push(
+        style.flex_grow != d.flex_grow
+            || style.flex_shrink != d.flex_shrink
+            || style.flex_basis != d.flex_basis,
+        "flex",
+        format!(
+            "{} {} {}",
+            style.flex_grow,
+            style.flex_shrink,
+            DimensionDto::from(style.flex_basis).to_css()
+        ),
+    );
+
+    // Alignment
+    out.push_pair(
+        "place-content",
+        (
+            "align-content",
+            style.align_content != d.align_content,
+            keyword_name(&style.align_content, ALIGN_CONTENT),
+        ),
+        (
+            "justify-content",
+            style.justify_content != d.justify_content,
+            keyword_name(&style.justify_content, ALIGN_CONTENT),
+        ),
+    );
+    out.push_pair(
+        "place-items",
+        (
+            "align-items",
+            style.align_items != d.align_items,
+            keyword_name(&style.align_items, ALIGN_ITEMS),
+        ),
+        (
+            "justify-items",
+            style.justify_items != d.justify_items,
+            keyword_name(&style.justify_items, ALIGN_ITEMS),
+        ),
+    );
+    out.push_pair(
+        "place-self",
+        (
+            "align-self",
+            style.align_self != d.align_self,
+            self_alignment_name(&style.align_self),
+        ),
+        (
+            "justify-self",
+            style.justify_self != d.justify_self,
+            self_alignment_name(&style.justify_self),
+        ),
+    );
+
+    // Sizing
+    out.push(
+        style.aspect_ratio != d.aspect_ratio,
+        "aspect-ratio",
+        style
+            .aspect_ratio
+            .map_or_else(|| "auto".to_string(), |ratio| ratio.to_string()),
+    );
+    let sizes = [
+        ("width", style.size.width, d.size.width),
+        ("height", style.size.height, d.size.height),
+        ("min-width", style.min_size.width, d.min_size.width),
+        ("min-height", style.min_size.height, d.min_size.height),
+    ];
+    for (property, value, default) in sizes {
+        out.push(
+            value != default,
+            property,
+            DimensionDto::from(value).to_css(),
+        );
+    }
+    let max_sizes = [
+        ("max-width", style.max_size.width, d.max_size.width),
+        ("max-height", style.max_size.height, d.max_size.height),
+    ];
+    for (property, value, default) in max_sizes {
+        let css = match DimensionDto::from(value) {
+            DimensionDto::Auto => "none".to_string(),
+            value => value.to_css(),
+        };
+        out.push(value != default, property, css);
+    }
+
+    // Spacing
+    out.push_sides(
+        "margin",
+        ["margin-top", "margin-right", "margin-bottom", "margin-left"],
+        style.margin,
+        d.margin,
+        |v| LengthPercentageAutoDto::from(v).to_css(),
+    );
+    out.push_sides(
+        "padding",
+        [
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+        ],
+        style.padding,
+        d.padding,
+        |v| LengthPercentageDto::from(v).to_css(),
+    );
+    out.push_sides(
+        "border-width",
+        [
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+        ],
+        style.border,
+        d.border,
+        |v| LengthPercentageDto::from(v).to_css(),
+    );
+    out.push_sides(
+        "inset",
+        ["top", "right", "bottom", "left"],
+        style.inset,
+        d.inset,
+        |v| LengthPercentageAutoDto::from(v).to_css(),
+    );
+    out.push_pair(
+        "gap",
+        (
+            "row-gap",
+            style.gap.height != d.gap.height,
+            LengthPercentageDto::from(style.gap.height).to_css(),
+        ),
+        (
+            "column-gap",
+            style.gap.width != d.gap.width,
+            LengthPercentageDto::from(style.gap.width).to_css(),
+        ),
+    );
+
+    // Block layout
+    out.push(
+        style.text_align != d.text_align,
+        "text-align",
+        keyword_name(&style.text_align, TEXT_ALIGN),
+    );
+
+    // Grid layout
+    out.push(
+        style.grid_auto_flow != d.grid_auto_flow,
+        "grid-auto-flow",
+        match style.grid_auto_flow {
+            GridAutoFlow::Row => "row",
+            GridAutoFlow::RowDense => "row dense",
+            GridAutoFlow::Column => "column",
+            GridAutoFlow::ColumnDense => "column dense",
+        },
+    );
+    out.push(
+        style.grid_row != d.grid_row,
+        "grid-row",
+        grid_line_css(&style.grid_row),
+    );
+    out.push(
+        style.grid_column != d.grid_column,
+        "grid-column",
+        grid_line_css(&style.grid_column),
+    );
+    out.push(
+        style.grid_template_rows != d.grid_template_rows
+            || style.grid_template_row_names != d.grid_template_row_names,
+        "grid-template-rows",
+        track_list_css(&style.grid_template_rows, &style.grid_template_row_names),
+    );
+    out.push(
+        style.grid_template_columns != d.grid_template_columns
+            || style.grid_template_column_names != d.grid_template_column_names,
+        "grid-template-columns",
+        track_list_css(
+            &style.grid_template_columns,
+            &style.grid_template_column_names,
+        ),
+    );
+    out.push(
+        style.grid_auto_rows != d.grid_auto_rows,
+        "grid-auto-rows",
+        auto_tracks_css(&style.grid_auto_rows),
+    );
+    out.push(
+        style.grid_auto_columns != d.grid_auto_columns,
+        "grid-auto-columns",
+        auto_tracks_css(&style.grid_auto_columns),
+    );
+    out.push(
+        style.grid_template_areas != d.grid_template_areas,
+        "grid-template-areas",
+        template_areas_css(&style.grid_template_areas),
+    );
+
+    out.lines.join("\n")
+}
+
+/// Collects the declarations written by [`serialize`]
+struct Declarations {
+    lines: Vec<String>,
+    /// Whether unchanged properties are written too
+    all: bool,
+}
+
+impl Declarations {
+    fn push(&mut self, changed: bool, property: &str, value: impl std::fmt::Display) {
+        if changed || self.all {
+            self.lines.push(format!("{property}: {value};"));
+        }
+    }
+
+    /// Writes a two-value shorthand when both longhands are written, collapsing
+    /// equal values, or else the changed longhands on their own
+    fn push_pair(
+        &mut self,
+        shorthand: &str,
+        (first, first_changed, first_value): (&str, bool, impl std::fmt::Display),
+        (second, second_changed, second_value): (&str, bool, impl std::fmt::Display),
+    ) {
+        let (first_value, second_value) = (first_value.to_string(), second_value.to_string());
+        if self.all || (first_changed && second_changed) {
+            if first_value == second_value {
+                self.push(true, shorthand, first_value);
+            } else {
+                self.push(true, shorthand, format!("{first_value} {second_value}"));
+            }
+        } else {
+            self.push(first_changed, first, first_value);
+            self.push(second_changed, second, second_value);
+        }
+    }
+
+    /// Writes the shortest `top right bottom left` shorthand when more than one
+    /// side is written, or else the single changed longhand
+    fn push_sides<T: PartialEq + Copy>(
+        &mut self,
+        shorthand: &str,
+        longhands: [&str; 4],
+        value: Rect<T>,
+        default: Rect<T>,
+        format: impl Fn(T) -> String,
+    ) {
+        let values = [value.top, value.right, value.bottom, value.left];
+        let defaults = [default.top, default.right, default.bottom, default.left];
+        let changed: Vec<usize> = (0..4).filter(|&i| values[i] != defaults[i]).collect();
+        if let ([side], false) = (changed.as_slice(), self.all) {
+            self.push(true, longhands[*side], format(values[*side]));
+            return;
+        }
+        let [top, right, bottom, left] = values.map(&format);
+        let value = if left != right {
+            format!("{top} {right} {bottom} {left}")
+        } else if bottom != top {
+            format!("{top} {right} {bottom}")
+        } else if right != top {
+            format!("{top} {right}")
+        } else {
+            top
+        };
+        self.push(!changed.is_empty(), shorthand, value);
+    }
+}
+
+/// `align-self`/`justify-self` write their unset value as `auto`
+fn self_alignment_name(value: &Option<AlignItems>) -> &'static str {
+    match value {
+        None => "auto",
+        Some(_) => keyword_name(value, ALIGN_ITEMS),
+    }
+}
+
+/// Formats one grid line
+fn grid_placement_css(placement: &GridPlacement) -> String {
+    match placement {
+        GridPlacement::Auto => "auto".to_string(),
+        GridPlacement::Line(line) => line.as_i16().to_string(),
+        GridPlacement::NamedLine(name, 1) => name.clone(),
+        GridPlacement::NamedLine(name, n) => format!("{n} {name}"),
+        GridPlacement::Span(n) => format!("span {n}"),
+        GridPlacement::NamedSpan(name, 1) => format!("span {name}"),
+        GridPlacement::NamedSpan(name, n) => format!("span {n} {name}"),
+    }
+}
+
+/// Formats `grid-row`/`grid-column`, leaving out an end that would be implied
+fn grid_line_css(line: &Line<GridPlacement>) -> String {
+    let start = grid_placement_css(&line.start);
+    if line.end == omitted_line(&line.start) {
+        start
+    } else {
+        format!("{start} / {}", grid_placement_css(&line.end))
+    }
+}
+
+/// Formats the minimum of a track
+fn min_track_css(min: MinTrackSizingFunction) -> String {
+    match MinTrackSizingFunctionDto::from(min) {
+        MinTrackSizingFunctionDto::Length(l) => format!("{}px", l),
+        // Track DTOs keep percentages as fractions
+        MinTrackSizingFunctionDto::Percent(p) => percent_string(p * 100.0),
+        MinTrackSizingFunctionDto::Auto => "auto".to_string(),
+        MinTrackSizingFunctionDto::MinContent => "min-content".to_string(),
+        MinTrackSizingFunctionDto::MaxContent => "max-content".to_string(),
+    }
+}
+
+/// Formats the maximum of a track
+fn max_track_css(max: MaxTrackSizingFunction) -> String {
+    match MaxTrackSizingFunctionDto::from(max) {
+        MaxTrackSizingFunctionDto::Length(l) => format!("{}px", l),
+        MaxTrackSizingFunctionDto::Percent(p) => percent_string(p * 100.0),
+        MaxTrackSizingFunctionDto::Fraction(f) => format!("{}fr", f),
+        MaxTrackSizingFunctionDto::FitContent(l) => format!("fit-content({}px)", l),
+        MaxTrackSizingFunctionDto::FitContentPercent(p) => {
+            format!("fit-content({})", percent_string(p * 100.0))
+        }
+        MaxTrackSizingFunctionDto::Auto => "auto".to_string(),
+        MaxTrackSizingFunctionDto::MinContent => "min-content".to_string(),
+        MaxTrackSizingFunctionDto::MaxContent => "max-content".to_string(),
+    }
+}
+
+/// Formats a track size in the shortest form [`track_size`] reads back
+fn track_size_css(track: &TrackSizingFunction) -> String {
+    let (min, max) = (min_track_css(track.min), max_track_css(track.max));
+    let implied_min = if track.max.is_fr() || max.starts_with("fit-content") {
+        "auto".to_string()
+    } else {
+        max.clone()
+    };
+    if min == implied_min {
+        max
+    } else {
+        format!("minmax({min}, {max})")
+    }
+}
+
+/// Formats tracks with the `[line names]` before each of them and after the last
+fn names_and_tracks_css<T>(
+    tracks: &[T],
+    names: &[Vec<String>],
+    format: impl Fn(&T) -> String,
+) -> String {
+    let group = |line: usize| {
+        names
+            .get(line)
+            .filter(|names| !names.is_empty())
+            .map(|names| format!("[{}]", names.join(" ")))
+    };
+    let mut parts = Vec::new();
+    for (line, track) in tracks.iter().enumerate() {
+        parts.extend(group(line));
+        parts.push(format(track));
+    }
+    parts.extend(group(tracks.len()));
+    parts.join(" ")
+}
+
+/// Formats `grid-template-rows`/`grid-template-columns`
+fn track_list_css(tracks: &[GridTemplateComponent<String>], names: &[Vec<String>]) -> String {
+    let value = names_and_tracks_css(tracks, names, |component| match component {
+        GridTemplateComponent::Single(track) => track_size_css(track),
+        GridTemplateComponent::Repeat(repeat) => {
+            let count = match repeat.count {
+                RepetitionCount::AutoFill => "auto-fill".to_string(),
+                RepetitionCount::AutoFit => "auto-fit".to_string(),
+                RepetitionCount::Count(n) => n.to_string(),
+            };
+            let tracks = names_and_tracks_css(&repeat.tracks, &repeat.line_names, track_size_css);
+            format!("repeat({count}, {tracks})")
+        }
+    });
+    if value.is_empty() {
+        "none".to_string()
+    } else {
+        value
+    }
+}
+
+/// Formats `grid-auto-rows`/`grid-auto-columns`, whose initial value is `auto`
+fn auto_tracks_css(tracks: &[TrackSizingFunction]) -> String {
+    if tracks.is_empty() {
+        return "auto".to_string();
+    }
+    let tracks: Vec<String> = tracks.iter().map(track_size_css).collect();
+    tracks.join(" ")
+}
+
+/// Formats `grid-template-areas` as one quoted string per row
+fn template_areas_css(areas: &[GridTemplateArea<String>]) -> String {
+    let rows = areas
+        .iter()
+        .map(|a| a.row_end)
+        .max()
+        .unwrap_or(1)
+        .saturating_sub(1);
+    let columns = areas
+        .iter()
+        .map(|a| a.column_end)
+        .max()
+        .unwrap_or(1)
+        .saturating_sub(1);
+    if rows == 0 || columns == 0 {
+        return "none".to_string();
+    }
+    let mut cells = vec![vec!["."; columns as usize]; rows as usize];
+    for area in areas {
+        for row in area.row_start.max(1)..area.row_end {
+            for column in area.column_start.max(1)..area.column_end {
+                cells[row as usize - 1][column as usize - 1] = &area.name;
+            }
+        }
+    }
+    let rows: Vec<String> = cells
+        .iter()
+        .map(|row| format!("\"{}\"", row.join(" ")))
+        .collect();
+    rows.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use taffy::prelude::{auto, fr, length};
+
+    fn parse(css: &str) -> (Style, Vec<CssIssue>) {
+        let mut style = Style::default();
+        let issues = apply(&mut style, css);
+        (style, issues)
+    }
+
+    #[test]
+    fn applies_longhands_and_shorthands() {
+        let (style, issues) =
+            parse("display:flex; flex-direction:column; gap: 8px 4px; margin: 0 auto");
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(style.display, Display::Flex);
+        assert_eq!(style.flex_direction, FlexDirection::Column);
+        assert_eq!(style.gap.height, LengthPercentage::length(8.0));
+        assert_eq!(style.gap.width, LengthPercentage::length(4.0));
+        assert_eq!(style.margin.top, LengthPercentageAuto::length(0.0));
+        assert_eq!(style.margin.left, LengthPercentageAuto::AUTO);
+        assert_eq!(style.margin.right, LengthPercentageAuto::AUTO);
+    }
+
+    #[test]
+    fn expands_flex_and_place_shorthands() {
+        let (style, issues) = parse(
+            "flex: 2 1 50%; place-items: center end; overflow: hidden scroll; inset: 1px 2px 3px",
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(
+            (style.flex_grow, style.flex_shrink, style.flex_basis),
+            (2.0, 1.0, Dimension::percent(0.5))
+        );
+        assert_eq!(style.align_items, Some(AlignItems::Center));
+        assert_eq!(style.justify_items, Some(AlignItems::End));
+        assert_eq!(style.overflow.x, Overflow::Hidden);
+        assert_eq!(style.overflow.y, Overflow::Scroll);
+        assert_eq!(style.inset.left, LengthPercentageAuto::length(2.0));
+        assert_eq!(style.inset.bottom, LengthPercentageAuto::length(3.0));
+
+        let (style, _) = parse("flex: none");
+        assert_eq!((style.flex_grow, style.flex_shrink), (0.0, 0.0));
+        let (style, _) = parse("flex: 3");
+        assert_eq!(style.flex_basis, Dimension::length(0.0));
+    }
+
+    #[test]
+    fn parses_grid_properties() {
+        let (style, issues) = parse(
+            r#"grid-template-columns: [full-start] 100px repeat(2, minmax(10px, 1fr)) [full-end];
+               grid-template-areas: "head head" "side main";
+               grid-area: 2 / 1 / span 2 / 3;
+               grid-auto-flow: column dense"#,
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(style.grid_template_columns.len(), 2);
+        assert_eq!(
+            style.grid_template_columns[0],
+            GridTemplateComponent::Single(length(100.0))
+        );
+        let GridTemplateComponent::Repeat(repeat) = &style.grid_template_columns[1] else {
+            panic!("expected a repeat()");
+        };
+        assert_eq!(repeat.count, RepetitionCount::Count(2));
+        assert_eq!(repeat.tracks[0].min, MinTrackSizingFunction::length(10.0));
+        assert_eq!(repeat.tracks[0].max, fr(1.0));
+        assert_eq!(
+            style.grid_template_column_names,
+            vec![
+                vec!["full-start".to_string()],
+                vec![],
+                vec!["full-end".to_string()]
+            ]
+        );
+        assert_eq!(style.grid_template_areas.len(), 3);
+        assert_eq!(style.grid_template_areas[0].column_end, 3);
+        assert_eq!(style.grid_row.start, GridPlacement::from_line_index(2));
+        assert_eq!(style.grid_row.end, GridPlacement::from_span(2));
+        assert_eq!(style.grid_column.end, GridPlacement::from_line_index(3));
+        assert_eq!(style.grid_auto_flow, GridAutoFlow::ColumnDense);
+
+        let (style, _) = parse("grid-template-rows: auto fit-content(40%); grid-row: header");
+        assert_eq!(
+            style.grid_template_rows[0],
+            GridTemplateComponent::Single(auto())
+        );
+        assert_eq!(
+            style.grid_template_rows[1],
+            GridTemplateComponent::Single(TrackSizingFunction {
+                min: MinTrackSizingFunction::auto(),
+                max: MaxTrackSizingFunction::fit_content_percent(0.4),
+            })
+        );
+        assert_eq!(
+            style.grid_row.end,
+            GridPlacement::NamedLine("header".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn reports_bad_declarations_with_positions() {
+        let (style, issues) = parse(
+            "display: flex;\n  flex-direction: sideways; width: 10em;\nwidht: 1px; /* ok */ height 5px",
+        );
+        assert_eq!(style.display, Display::Flex);
+        assert_eq!(style.size.width, Dimension::AUTO);
+        let positions: Vec<(usize, usize)> = issues.iter().map(|i| (i.line, i.column)).collect();
+        assert_eq!(positions, vec![(2, 3), (2, 29), (3, 1), (3, 22)]);
+        assert_eq!(
+            issues[0].to_string(),
+            "2:3: flex-direction: `sideways` is not one of row, row-reverse, column, column-reverse"
+        );
+        assert_eq!(issues[2].reason, "unknown property `widht`");
+        assert!(issues[3].reason.starts_with("expected `property: value`"));
+    }
+
+    #[test]
+    fn reports_positions_after_non_ascii_comments() {
+        let (_, issues) = parse("/*é*/\u{a0}bogus");
+        assert_eq!((issues[0].line, issues[0].column), (1, 7));
+        let (_, issues) = parse("/* größe */ width: 1px;\n/* ü */ widht: 2px");
+        let positions: Vec<(usize, usize)> = issues.iter().map(|i| (i.line, i.column)).collect();
+        assert_eq!(positions, vec![(2, 9)]);
+    }
+
+    #[test]
+    fn rejects_non_rectangular_areas() {
+        let (_, issues) = parse(r#"grid-template-areas: "a a" "a b""#);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].reason.contains("not a rectangle"));
+    }
+
+    #[test]
+    fn serializes_only_changed_properties_with_shorthands() {
+        let (style, _) = parse(
+            "display: grid; margin: 0 auto; padding-left: 4px; place-items: center; \
+             align-self: end; max-width: 50%; overflow: hidden",
+        );
+        assert_eq!(
+            serialize(&style, true),
+            "display: grid;\noverflow: hidden;\nplace-items: center;\nalign-self: end;\n\
+             max-width: 50%;\nmargin: 0px auto;\npadding-left: 4px;"
+        );
+        assert_eq!(serialize(&Style::default(), true), "");
+    }
+
+    #[test]
+    fn serialized_css_parses_back_to_the_same_style() {
+        let sources = [
+            "flex-flow: column wrap; flex: 2 0 10px; gap: 4px 8%; inset: 1px 2px 3px 4px",
+            "position: absolute; aspect-ratio: 16 / 9; box-sizing: content-box; \
+             text-align: -webkit-center; border-width: 1px 2px; justify-content: space-between",
+            r#"display: grid; grid-auto-flow: column dense;
+               grid-template-columns: [full-start] 100px repeat(auto-fill, [a] minmax(10px, 1fr)) fit-content(40%) [full-end];
+               grid-template-rows: auto min-content 25%;
+               grid-auto-rows: minmax(auto, 30px) 2fr;
+               grid-template-areas: "head head" ". main";
+               grid-row: 2 / span 3; grid-column: side; place-self: start stretch"#,
+            "grid-area: 1 edge / span 2 / span 2 tail / -1",
+        ];
+        for source in sources {
+            let (style, issues) = parse(source);
+            assert!(issues.is_empty(), "{issues:?}");
+            for only_non_default in [true, false] {
+                let css = serialize(&style, only_non_default);
+                let (reparsed, issues) = parse(&css);
+                assert!(issues.is_empty(), "{css}: {issues:?}");
+                let mut expected = style.clone();
+                // An empty implicit track list is written as its CSS initial value `auto`
+                if !only_non_default && expected.grid_auto_columns.is_empty() {
+                    expected.grid_auto_columns = vec![auto()];
+                }
+                if !only_non_default && expected.grid_auto_rows.is_empty() {
+                    expected.grid_auto_rows = vec![auto()];
+                }
+                assert_eq!(reparsed, expected, "{css}");
+            }
+        }
+    }
+}
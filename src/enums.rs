@@ -15,7 +15,7 @@
 //! Each enum implements:
 //! - `From<JsEnum> for taffy::style::Enum` - Convert from JS to Taffy
 //! - `From<taffy::style::Enum> for JsEnum` - Convert from Taffy to JS
-//! - `TryFrom<u32> for JsEnum` - Convert from raw number (for setter handling)
+//! - `TryFrom<u32> for JsEnum` - Checked conversion from a raw number (for setter handling)
 
 use wasm_bindgen::prelude::*;
 
@@ -691,3 +691,74 @@ impl From<taffy::style::GridAutoFlow> for JsGridAutoFlow {
         }
     }
 }
+
+// =============================================================================
+// Checked Conversions
+// =============================================================================
+
+/// An enum that JavaScript passes around as its numeric discriminant
+///
+/// `TryFrom<u32>` rejects numbers that are not a discriminant of the enum,
+/// returning the number unchanged as the error.
+pub(crate) trait JsEnum: TryFrom<u32, Error = u32> + Copy {
+    /// The TypeScript name of the enum, used in error messages
+    const NAME: &'static str;
+}
+
+/// Implements `TryFrom<u32>` and [`JsEnum`] for the listed enums
+macro_rules! js_enum {
+    ($($ty:ident as $name:literal { $($variant:ident),+ $(,)? })+) => {$(
+        impl TryFrom<u32> for $ty {
+            type Error = u32;
+
+            fn try_from(value: u32) -> Result<Self, u32> {
+                $(
+                    if value == $ty::$variant as u32 {
+                        return Ok($ty::$variant);
+                    }
+                )+
+                Err(value)
+            }
+        }
+
+        impl JsEnum for $ty {
+            const NAME: &'static str = $name;
+        }
+    )+};
+}
+
+js_enum! {
+    JsDisplay as "Display" { Block, Flex, Grid, None }
+    JsPosition as "Position" { Relative, Absolute }
+    JsFlexDirection as "FlexDirection" { Row, Column, RowReverse, ColumnReverse }
+    JsFlexWrap as "FlexWrap" { NoWrap, Wrap, WrapReverse }
+    JsAlignItems as "AlignItems" { Start, End, FlexStart, FlexEnd, Center, Baseline, Stretch }
+    JsAlignSelf as "AlignSelf" { Auto, Start, End, FlexStart, FlexEnd, Center, Baseline, Stretch }
+    JsAlignContent as "AlignContent" {
+        Start, End, FlexStart, FlexEnd, Center, Stretch, SpaceBetween, SpaceAround, SpaceEvenly
+    }
+    JsJustifyContent as "JustifyContent" {
+        Start, End, FlexStart, FlexEnd, Center, Stretch, SpaceBetween, SpaceAround, SpaceEvenly
+    }
+    JsOverflow as "Overflow" { Visible, Clip, Hidden, Scroll }
+    JsBoxSizing as "BoxSizing" { BorderBox, ContentBox }
+    JsTextAlign as "TextAlign" { Auto, LegacyLeft, LegacyRight, LegacyCenter }
+    JsGridAutoFlow as "GridAutoFlow" { Row, Column, RowDense, ColumnDense }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_accepts_discriminants_only() {
+        assert_eq!(JsDisplay::try_from(2), Ok(JsDisplay::Grid));
+        assert_eq!(JsDisplay::try_from(4), Err(4));
+        assert_eq!(
+            JsJustifyContent::try_from(JsJustifyContent::SpaceEvenly as u32),
+            Ok(JsJustifyContent::SpaceEvenly)
+        );
+        assert_eq!(JsAlignSelf::try_from(8), Err(8));
+        assert_eq!(JsBoxSizing::NAME, "BoxSizing");
+    }
+}
@@ -6,8 +6,10 @@
 //!
 //! ## Error Type
 //!
-//! The [`JsTaffyError`] struct wraps a native `taffy::TaffyError` and exposes a `message`
-//! property to JavaScript for error introspection.
+//! The [`JsTaffyError`] struct wraps a native `taffy::TaffyError` (or an error raised by
+//! the bindings themselves) and exposes a `message`, a machine-readable `code` and the
+//! offending node IDs and indices to JavaScript for error introspection. Its prototype
+//! chain includes `Error.prototype`, so `e instanceof Error` holds and `e.stack` is set.
 //!
 //! ## Result Mapping
 //!
@@ -57,14 +59,94 @@ use wasm_bindgen::prelude::*;
 /// ```
 ///
 /// @remarks
-/// The underlying Taffy errors include:
+/// The `code` property identifies the failure. The underlying Taffy errors are:
 /// - `InvalidInputNode`: Node ID doesn't exist in the tree
 /// - `InvalidParentNode`: Specified parent node doesn't exist
+/// - `InvalidChildNode`: Specified child node doesn't exist
 /// - `ChildIndexOutOfBounds`: Child index exceeds available children
+///
+/// The bindings add their own failures on top:
+/// - `InvalidSnapshot`: A malformed snapshot passed to `TaffyTree.fromSnapshot()`
+/// - `WouldCreateCycle`: A `TaffyTree.moveNode()` call that would create a cycle
+/// - `IntegrityViolation`: An edit rejected by strict mode, or a subtree copy
+///   of a node reachable through more than one parent
+/// - `InvalidAvailableSpace`: A malformed `availableSpace` passed to `computeLayout()`
+/// - `InvalidArgument`: Any other argument that does not match its TypeScript type
+/// - `InvalidStyle`: Style properties rejected by strict validation (see `issues`)
+/// - `BatchFailed`: An operation of `TaffyTree.applyBatch()` failed (see `opIndex` and `cause`)
+/// - `NoTransaction`: `TaffyTree.commit()` or `rollback()` without an open transaction
+/// - `MeasureFailed`: A measure function threw or returned an invalid size (see `node` and `cause`)
 #[wasm_bindgen(js_name = TaffyError)]
 pub struct JsTaffyError {
-    /// The wrapped native Taffy error
-    inner: TaffyError,
+    /// What went wrong
+    kind: ErrorKind,
+    /// JavaScript stack trace captured when the error was created
+    stack: String,
+}
+
+/// The failure wrapped by a [`JsTaffyError`]
+#[derive(Debug, Clone)]
+pub(crate) enum ErrorKind {
+    /// An error reported by Taffy itself
+    Native(TaffyError),
+    /// A tree snapshot could not be created or restored
+    InvalidSnapshot(String),
+    /// A node would become its own ancestor
+    WouldCreateCycle { node: NodeId, parent: NodeId },
+    /// A strict-mode edit would break the tree structure
+    IntegrityViolation(String),
+    /// The `availableSpace` argument of a layout computation is malformed
+    InvalidAvailableSpace(ArgumentError),
+    /// Any other argument could not be decoded
+    InvalidArgument(ArgumentError),
+    /// Style properties rejected by strict validation, one entry per property
+    InvalidStyle(Vec<String>),
+    /// An operation of `applyBatch()` failed and the batch was rolled back
+    BatchFailed {
+        index: usize,
+        op: Option<String>,
+        reason: String,
+        cause: JsValue,
+    },
+    /// `commit()` or `rollback()` was called without an open transaction
+    NoTransaction(&'static str),
+    /// A measure function threw or returned an invalid size
+    MeasureFailed {
+        node: NodeId,
+        reason: String,
+        cause: JsValue,
+    },
+}
+
+/// Details of a JavaScript argument that could not be decoded
+#[derive(Debug, Clone)]
+pub(crate) struct ArgumentError {
+    /// The argument name, with a field path where useful (e.g. `availableSpace.width`)
+    path: String,
+    /// The rejected value, as formatted by [`describe_value`]
+    value: String,
+    /// The expected TypeScript type
+    expected: &'static str,
+}
+
+impl ArgumentError {
+    pub(crate) fn new(path: impl Into<String>, value: &JsValue, expected: &'static str) -> Self {
+        ArgumentError {
+            path: path.into(),
+            value: describe_value(value),
+            expected,
+        }
+    }
+}
+
+impl std::fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} is not a valid {}",
+            self.path, self.value, self.expected
+        )
+    }
 }
 
 #[wasm_bindgen(js_class = "TaffyError")]
@@ -79,13 +161,326 @@ impl JsTaffyError {
     /// - "Index 5 is out of bounds for node with 3 children"
     #[wasm_bindgen(getter)]
     pub fn message(&self) -> String {
-        self.inner.to_string()
+        match &self.kind {
+            ErrorKind::Native(e) => e.to_string(),
+            ErrorKind::InvalidSnapshot(reason) => format!("Invalid snapshot: {reason}"),
+            ErrorKind::WouldCreateCycle { node, parent } => format!(
+                "Cannot move node {} into {}, which would create a cycle",
+                u64::from(*node),
+                u64::from(*parent)
+            ),
+            ErrorKind::IntegrityViolation(reason) => format!("Tree integrity violation: {reason}"),
+            ErrorKind::InvalidAvailableSpace(arg) | ErrorKind::InvalidArgument(arg) => {
+                arg.to_string()
+            }
+            ErrorKind::InvalidStyle(issues) => format!("Invalid style: {}", issues.join("; ")),
+            ErrorKind::BatchFailed {
+                index,
+                op: Some(op),
+                reason,
+                ..
+            } => format!("Batch operation {index} ({op}) failed: {reason}"),
+            ErrorKind::BatchFailed { index, reason, .. } => {
+                format!("Batch operation {index} failed: {reason}")
+            }
+            ErrorKind::NoTransaction(method) => {
+                format!("{method}() called without an open transaction")
+            }
+            ErrorKind::MeasureFailed { node, reason, .. } => format!(
+                "Measure function failed for node {}: {reason}",
+                u64::from(*node)
+            ),
+        }
+    }
+
+    /// Gets the machine-readable error code
+    ///
+    /// @returns - One of the `TaffyErrorCode` values, e.g. `"InvalidInputNode"`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const parent = tree.newLeaf(new Style());
+    /// try {
+    ///   tree.getChildAtIndex(parent, 3);
+    /// } catch (e) {
+    ///   if (e instanceof TaffyError && e.code === "ChildIndexOutOfBounds") {
+    ///     console.log(e.childIndex, e.childCount); // 3 0
+    ///   }
+    /// }
+    /// ```
+    #[wasm_bindgen(getter, unchecked_return_type = "TaffyErrorCode")]
+    pub fn code(&self) -> String {
+        let code = match &self.kind {
+            ErrorKind::Native(TaffyError::ChildIndexOutOfBounds { .. }) => "ChildIndexOutOfBounds",
+            ErrorKind::Native(TaffyError::InvalidParentNode(_)) => "InvalidParentNode",
+            ErrorKind::Native(TaffyError::InvalidChildNode(_)) => "InvalidChildNode",
+            ErrorKind::Native(TaffyError::InvalidInputNode(_)) => "InvalidInputNode",
+            ErrorKind::InvalidSnapshot(_) => "InvalidSnapshot",
+            ErrorKind::WouldCreateCycle { .. } => "WouldCreateCycle",
+            ErrorKind::IntegrityViolation(_) => "IntegrityViolation",
+            ErrorKind::InvalidAvailableSpace(_) => "InvalidAvailableSpace",
+            ErrorKind::InvalidArgument(_) => "InvalidArgument",
+            ErrorKind::InvalidStyle(_) => "InvalidStyle",
+            ErrorKind::BatchFailed { .. } => "BatchFailed",
+            ErrorKind::NoTransaction(_) => "NoTransaction",
+            ErrorKind::MeasureFailed { .. } => "MeasureFailed",
+        };
+        code.to_string()
+    }
+
+    /// Gets the ID of the node the error is about, if any
+    ///
+    /// Set for `InvalidInputNode`, `InvalidChildNode`, `WouldCreateCycle` and
+    /// `MeasureFailed`.
+    #[wasm_bindgen(getter)]
+    pub fn node(&self) -> Option<u64> {
+        match &self.kind {
+            ErrorKind::Native(TaffyError::InvalidInputNode(node))
+            | ErrorKind::Native(TaffyError::InvalidChildNode(node))
+            | ErrorKind::WouldCreateCycle { node, .. }
+            | ErrorKind::MeasureFailed { node, .. } => Some(u64::from(*node)),
+            _ => None,
+        }
+    }
+
+    /// Gets the ID of the parent node the error is about, if any
+    ///
+    /// Set for `InvalidParentNode`, `ChildIndexOutOfBounds` and
+    /// `WouldCreateCycle` (the requested new parent).
+    #[wasm_bindgen(getter)]
+    pub fn parent(&self) -> Option<u64> {
+        match &self.kind {
+            ErrorKind::Native(TaffyError::InvalidParentNode(parent))
+            | ErrorKind::Native(TaffyError::ChildIndexOutOfBounds { parent, .. })
+            | ErrorKind::WouldCreateCycle { parent, .. } => Some(u64::from(*parent)),
+            _ => None,
+        }
+    }
+
+    /// Gets the requested child index for `ChildIndexOutOfBounds`
+    #[wasm_bindgen(getter = childIndex)]
+    pub fn child_index(&self) -> Option<usize> {
+        match &self.kind {
+            ErrorKind::Native(TaffyError::ChildIndexOutOfBounds { child_index, .. }) => {
+                Some(*child_index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the parent's child count for `ChildIndexOutOfBounds`
+    #[wasm_bindgen(getter = childCount)]
+    pub fn child_count(&self) -> Option<usize> {
+        match &self.kind {
+            ErrorKind::Native(TaffyError::ChildIndexOutOfBounds { child_count, .. }) => {
+                Some(*child_count)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the rejected style properties for `InvalidStyle`
+    ///
+    /// @returns - One description per invalid key or value, in the order they were set
+    ///
+    /// @example
+    /// ```typescript
+    /// const style = new Style();
+    /// try {
+    ///   style.set({ display: 42, flexGrow: "1" } as never, true);
+    /// } catch (e) {
+    ///   if (e instanceof TaffyError && e.code === "InvalidStyle") {
+    ///     console.log(e.issues); // ["display: 42 is not a valid Display", ...]
+    ///   }
+    /// }
+    /// ```
+    #[wasm_bindgen(getter)]
+    pub fn issues(&self) -> Option<Vec<String>> {
+        match &self.kind {
+            ErrorKind::InvalidStyle(issues) => Some(issues.clone()),
+            _ => None,
+        }
+    }
+
+    /// Gets the index of the failed operation for `BatchFailed`
+    #[wasm_bindgen(getter = opIndex)]
+    pub fn op_index(&self) -> Option<usize> {
+        match &self.kind {
+            ErrorKind::BatchFailed { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Gets the underlying error for `BatchFailed` and `MeasureFailed`
+    ///
+    /// For `BatchFailed`, the error thrown by the failed operation. For
+    /// `MeasureFailed`, the value thrown by the measure function, or a
+    /// `TaffyError` with code `InvalidArgument` for an invalid result.
+    ///
+    /// @returns - The original error, usually a `TaffyError`, or `undefined`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const root = tree.newLeaf(new Style());
+    /// try {
+    ///   tree.applyBatch([{ op: "create" }, { op: "insert", parent: root, child: 0, index: 5 }]);
+    /// } catch (e) {
+    ///   if (e instanceof TaffyError && e.code === "BatchFailed") {
+    ///     console.log(e.opIndex, (e.cause as TaffyError).code); // 1 "ChildIndexOutOfBounds"
+    ///   }
+    /// }
+    /// ```
+    #[wasm_bindgen(getter)]
+    pub fn cause(&self) -> JsValue {
+        match &self.kind {
+            ErrorKind::BatchFailed { cause, .. } | ErrorKind::MeasureFailed { cause, .. } => {
+                cause.clone()
+            }
+            _ => JsValue::UNDEFINED,
+        }
+    }
+
+    /// Gets the error name, `"TaffyError"`
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        "TaffyError".to_string()
+    }
+
+    /// Gets the stack trace captured when the error was created
+    #[wasm_bindgen(getter)]
+    pub fn stack(&self) -> String {
+        self.stack.clone()
     }
 }
 
+impl JsTaffyError {
+    /// Creates an error and captures the current JavaScript stack
+    fn new(kind: ErrorKind) -> Self {
+        let mut error = JsTaffyError {
+            kind,
+            stack: String::new(),
+        };
+        error.stack = capture_stack(&error.message());
+        error
+    }
+
+    /// Creates an error for a snapshot that cannot be created or restored
+    pub(crate) fn invalid_snapshot(reason: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidSnapshot(reason.into()))
+    }
+
+    /// Creates an error for a move that would make `node` its own ancestor
+    pub(crate) fn would_create_cycle(node: NodeId, parent: NodeId) -> Self {
+        Self::new(ErrorKind::WouldCreateCycle { node, parent })
+    }
+
+    /// Creates an error for an edit rejected by strict mode or a malformed subtree
+    pub(crate) fn integrity_violation(reason: impl Into<String>) -> Self {
+        Self::new(ErrorKind::IntegrityViolation(reason.into()))
+    }
+
+    /// Creates an error for a malformed `availableSpace` field
+    pub(crate) fn invalid_available_space(path: &str, value: &JsValue) -> Self {
+        Self::new(ErrorKind::InvalidAvailableSpace(ArgumentError::new(
+            path,
+            value,
+            "AvailableSpace",
+        )))
+    }
+
+    /// Creates an error listing the style properties rejected by strict validation
+    pub(crate) fn invalid_style(issues: Vec<String>) -> Self {
+        Self::new(ErrorKind::InvalidStyle(issues))
+    }
+
+    /// Creates an error for `method` called without an open transaction
+    pub(crate) fn no_transaction(method: &'static str) -> Self {
+        Self::new(ErrorKind::NoTransaction(method))
+    }
+
+    /// Creates an error for a failed `applyBatch()` operation
+    ///
+    /// The message of `cause` is repeated in the error's own message.
+    pub(crate) fn batch_failed(index: usize, op: Option<String>, cause: JsValue) -> Self {
+        Self::new(ErrorKind::BatchFailed {
+            index,
+            op,
+            reason: cause_message(&cause),
+            cause,
+        })
+    }
+
+    /// Creates an error for a measure function that threw or returned an invalid size
+    ///
+    /// The message of `cause` is repeated in the error's own message.
+    pub(crate) fn measure_failed(node: NodeId, cause: JsValue) -> Self {
+        Self::new(ErrorKind::MeasureFailed {
+            node,
+            reason: cause_message(&cause),
+            cause,
+        })
+    }
+
+    /// Creates an error for an argument that does not match its expected type
+    pub(crate) fn invalid_argument(
+        path: impl Into<String>,
+        value: &JsValue,
+        expected: &'static str,
+    ) -> Self {
+        Self::new(ErrorKind::InvalidArgument(ArgumentError::new(
+            path, value, expected,
+        )))
+    }
+}
+
+/// Gets the `message` of a thrown value, or describes the value itself
+fn cause_message(cause: &JsValue) -> String {
+    js_sys::Reflect::get(cause, &"message".into())
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| describe_value(cause))
+}
+
+/// Builds a stack trace in the format of the host's `Error.prototype.stack`
+///
+/// The frames come from a fresh `Error`, with its header line (if any)
+/// replaced by `TaffyError: <message>`.
+#[cfg(target_arch = "wasm32")]
+fn capture_stack(message: &str) -> String {
+    let error = js_sys::Error::new("");
+    let stack = js_sys::Reflect::get(&error, &"stack".into())
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_default();
+    let frames = stack.strip_prefix("Error\n").unwrap_or(&stack);
+    format!("TaffyError: {message}\n{frames}")
+}
+
+/// Stack traces are only available when running in a JavaScript host
+#[cfg(not(target_arch = "wasm32"))]
+fn capture_stack(message: &str) -> String {
+    format!("TaffyError: {message}")
+}
+
+/// Links `TaffyError.prototype` to `Error.prototype` when the module loads
+///
+/// This makes `e instanceof Error` true for thrown `TaffyError`s and lets
+/// `Error.prototype.toString()` format them as `TaffyError: <message>`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(start)]
+fn install_error_prototype() {
+    use js_sys::Object;
+    let sample = JsValue::from(JsTaffyError::invalid_snapshot(""));
+    let prototype = Object::get_prototype_of(&sample);
+    let error_prototype = Object::get_prototype_of(&js_sys::Error::new(""));
+    Object::set_prototype_of(&prototype, &error_prototype);
+}
+
 impl From<TaffyError> for JsTaffyError {
     fn from(inner: TaffyError) -> Self {
-        JsTaffyError { inner }
+        JsTaffyError::new(ErrorKind::Native(inner))
     }
 }
 
@@ -105,6 +500,42 @@ pub(crate) fn to_js_error(e: TaffyError) -> JsValue {
     JsValue::from(JsTaffyError::from(e))
 }
 
+/// Formats a JavaScript value for an error message
+///
+/// Uses `JSON.stringify()`, falling back to a debug representation for
+/// values it cannot format, such as `undefined` and `bigint`s.
+pub(crate) fn describe_value(value: &JsValue) -> String {
+    js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_else(|| {
+            if value.is_undefined() {
+                "undefined".to_string()
+            } else if let Ok(n) = i128::try_from(value.clone()) {
+                format!("{n}n")
+            } else {
+                format!("{value:?}")
+            }
+        })
+}
+
+/// Decodes an argument with `serde-wasm-bindgen`
+///
+/// A value that does not decode fails with an `InvalidArgument` error naming
+/// the argument, the rejected value and the expected TypeScript type.
+///
+/// @param path - The argument name, e.g. `"rect"`
+/// @param value - The value received from JavaScript
+/// @param expected - The expected TypeScript type, e.g. `"BoundingBox"`
+pub(crate) fn decode_argument<T: serde::de::DeserializeOwned>(
+    path: &str,
+    value: JsValue,
+    expected: &'static str,
+) -> Result<T, JsTaffyError> {
+    serde_wasm_bindgen::from_value(value.clone())
+        .map_err(|_| JsTaffyError::invalid_argument(path, &value, expected))
+}
+
 /// Maps a NodeId Result to a JavaScript bigint
 ///
 /// Specialized version that converts the `NodeId` to a `u64` (BigInt in JavaScript).
@@ -0,0 +1,505 @@
+//! # Edit History Module
+//!
+//! This module records the inverse of tree edits for `TaffyTree`'s opt-in
+//! undo/redo history (`enableHistory()`, `undo()`, `redo()`).
+//!
+//! Each recorded edit stores the state it replaced and the state it produced,
+//! so it can be reverted and reapplied without copying the tree:
+//!
+//! - Style and context changes keep the old and new value.
+//! - Structural edits keep the child lists of every parent they touch.
+//! - Removed nodes stay allocated in the tree, detached and hidden, so undoing
+//!   the removal brings them back under their original IDs. They are freed
+//!   once the step that removed them leaves the history.
+//!
+//! Edits accumulate until `checkpoint()` seals them into a step, which
+//! `undo()` and `redo()` revert and reapply as a whole.
+//!
+//! The same edits are recorded in a [`Journal`] while a transaction or an
+//! `applyBatch()` call is open, so that rolling it back only reverts what it
+//! changed instead of restoring a copy of the tree.
+
+use js_sys::Function;
+use std::collections::{HashMap, HashSet, VecDeque};
+use taffy::TaffyTree;
+use taffy::prelude::*;
+use wasm_bindgen::JsValue;
+
+/// Number of steps kept when `enableHistory()` is called without a depth
+pub(crate) const DEFAULT_DEPTH: usize = 100;
+
+// =============================================================================
+// Edits
+// =============================================================================
+
+/// The parts of a tree that edits change
+pub(crate) struct Target<'a> {
+    pub(crate) tree: &'a mut TaffyTree<JsValue>,
+    pub(crate) nodes: &'a mut HashSet<NodeId>,
+    pub(crate) measure_funcs: &'a mut HashMap<NodeId, Function>,
+    /// Whether rounding is enabled, which `TaffyTree` does not expose
+    pub(crate) rounding: &'a mut bool,
+}
+
+/// A recorded edit with the state before and after it
+#[derive(Clone)]
+pub(crate) enum Edit {
+    /// A node's style was replaced
+    Style {
+        node: NodeId,
+        before: Box<Style>,
+        after: Box<Style>,
+    },
+    /// A node's context was replaced
+    Context {
+        node: NodeId,
+        before: Option<JsValue>,
+        after: Option<JsValue>,
+    },
+    /// The child lists of these parents changed
+    Children {
+        before: Vec<(NodeId, Vec<NodeId>)>,
+        after: Vec<(NodeId, Vec<NodeId>)>,
+    },
+    /// A detached node was removed from the tree's node set
+    Removed(NodeId),
+    /// A node was added to the tree's node set, by creating it or by undoing
+    /// its removal
+    Added(NodeId),
+    /// A node's measure function was replaced
+    Measure {
+        node: NodeId,
+        before: Option<Function>,
+        after: Option<Function>,
+    },
+    /// Rounding was enabled or disabled
+    Rounding { before: bool, after: bool },
+}
+
+impl Edit {
+    /// Reapplies (`forward`) or reverts the edit
+    pub(crate) fn apply(&self, target: &mut Target, forward: bool) {
+        let Target {
+            tree,
+            nodes,
+            measure_funcs,
+            rounding,
+        } = target;
+        // Recorded nodes stay allocated while an edit can be applied, so these cannot fail
+        match self {
+            Edit::Style {
+                node,
+                before,
+                after,
+            } => {
+                let _ = tree.set_style(*node, Style::clone(pick(forward, before, after)));
+            }
+            Edit::Context {
+                node,
+                before,
+                after,
+            } => {
+                let _ = tree.set_node_context(*node, pick(forward, before, after).clone());
+            }
+            Edit::Children { before, after } => {
+                for (parent, children) in pick(forward, before, after) {
+                    let _ = tree.set_children(*parent, children);
+                }
+            }
+            Edit::Removed(node) | Edit::Added(node) => {
+                if forward == matches!(self, Edit::Removed(_)) {
+                    nodes.remove(node);
+                } else {
+                    nodes.insert(*node);
+                }
+            }
+            Edit::Measure {
+                node,
+                before,
+                after,
+            } => match pick(forward, before, after) {
+                Some(func) => {
+                    measure_funcs.insert(*node, func.clone());
+                }
+                None => {
+                    measure_funcs.remove(node);
+                }
+            },
+            Edit::Rounding { before, after } => {
+                **rounding = *pick(forward, before, after);
+                if **rounding {
+                    tree.enable_rounding();
+                } else {
+                    tree.disable_rounding();
+                }
+            }
+        }
+    }
+
+    /// Gets the edit that reverts this one
+    fn inverted(self) -> Edit {
+        match self {
+            Edit::Style {
+                node,
+                before,
+                after,
+            } => Edit::Style {
+                node,
+                before: after,
+                after: before,
+            },
+            Edit::Context {
+                node,
+                before,
+                after,
+            } => Edit::Context {
+                node,
+                before: after,
+                after: before,
+            },
+            Edit::Children { before, after } => Edit::Children {
+                before: after,
+                after: before,
+            },
+            Edit::Removed(node) => Edit::Added(node),
+            Edit::Added(node) => Edit::Removed(node),
+            Edit::Measure {
+                node,
+                before,
+                after,
+            } => Edit::Measure {
+                node,
+                before: after,
+                after: before,
+            },
+            Edit::Rounding { before, after } => Edit::Rounding {
+                before: after,
+                after: before,
+            },
+        }
+    }
+}
+
+/// Returns `after` when reapplying an edit and `before` when reverting it
+fn pick<'a, T>(forward: bool, before: &'a T, after: &'a T) -> &'a T {
+    if forward { after } else { before }
+}
+
+// =============================================================================
+// Steps
+// =============================================================================
+
+/// Edits sealed together by `checkpoint()`
+#[derive(Clone)]
+pub(crate) struct Step {
+    name: Option<String>,
+    edits: Vec<Edit>,
+}
+
+impl Step {
+    /// Reverts the step's edits, newest first
+    pub(crate) fn undo(&self, target: &mut Target) {
+        for edit in self.edits.iter().rev() {
+            edit.apply(target, false);
+        }
+    }
+
+    /// Reapplies the step's edits, oldest first
+    pub(crate) fn redo(&self, target: &mut Target) {
+        for edit in &self.edits {
+            edit.apply(target, true);
+        }
+    }
+
+    /// Gets the edits that `redo()` (`forward`) or `undo()` applies, in order
+    pub(crate) fn applied(&self, forward: bool) -> Vec<Edit> {
+        if forward {
+            return self.edits.clone();
+        }
+        self.edits
+            .iter()
+            .rev()
+            .cloned()
+            .map(Edit::inverted)
+            .collect()
+    }
+
+    /// Gets the nodes removed by the step
+    ///
+    /// Once an applied step leaves the history, these can no longer be
+    /// brought back and are freed.
+    pub(crate) fn removed_nodes(&self) -> Vec<NodeId> {
+        removed_nodes(&self.edits).collect()
+    }
+}
+
+/// Gets the nodes removed by a list of edits
+fn removed_nodes(edits: &[Edit]) -> impl Iterator<Item = NodeId> + '_ {
+    edits.iter().filter_map(|edit| match edit {
+        Edit::Removed(node) => Some(*node),
+        _ => None,
+    })
+}
+
+// =============================================================================
+// History
+// =============================================================================
+
+/// Undo and redo stacks of a tree
+#[derive(Clone)]
+pub(crate) struct History {
+    /// Maximum number of undo steps kept
+    depth: usize,
+    /// Applied steps, oldest first
+    undo: VecDeque<Step>,
+    /// Reverted steps, most recently reverted last
+    redo: Vec<Step>,
+    /// Edits recorded since the last checkpoint
+    pending: Vec<Edit>,
+}
+
+impl History {
+    pub(crate) fn new(depth: usize) -> Self {
+        History {
+            depth,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Maximum number of undo steps kept
+    pub(crate) fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Changes the maximum number of undo steps
+    ///
+    /// @returns - The steps that no longer fit, to be discarded
+    pub(crate) fn set_depth(&mut self, depth: usize) -> Vec<Step> {
+        self.depth = depth;
+        self.overflow()
+    }
+
+    /// Records an applied edit and drops the redo stack
+    pub(crate) fn record(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.pending.push(edit);
+    }
+
+    /// Seals the pending edits into a step
+    ///
+    /// @returns - `None` if there were no pending edits, otherwise the steps
+    /// that no longer fit, to be discarded
+    pub(crate) fn checkpoint(&mut self, name: Option<String>) -> Option<Vec<Step>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let edits = std::mem::take(&mut self.pending);
+        self.undo.push_back(Step { name, edits });
+        Some(self.overflow())
+    }
+
+    /// Takes the step to revert, sealing pending edits into an unnamed step first
+    pub(crate) fn take_undo(&mut self) -> Option<Step> {
+        if !self.pending.is_empty() {
+            let edits = std::mem::take(&mut self.pending);
+            return Some(Step { name: None, edits });
+        }
+        self.undo.pop_back()
+    }
+
+    /// Stores a step that has just been reverted
+    pub(crate) fn push_redo(&mut self, step: Step) {
+        self.redo.push(step);
+    }
+
+    /// Takes the step to reapply
+    pub(crate) fn take_redo(&mut self) -> Option<Step> {
+        self.redo.pop()
+    }
+
+    /// Stores a step that has just been reapplied
+    pub(crate) fn push_undo(&mut self, step: Step) {
+        self.undo.push_back(step);
+    }
+
+    /// Whether `take_undo()` would return a step
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.pending.is_empty() || !self.undo.is_empty()
+    }
+
+    /// Whether `take_redo()` would return a step
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Name of the step `take_undo()` would return
+    pub(crate) fn undo_name(&self) -> Option<&str> {
+        if !self.pending.is_empty() {
+            return None;
+        }
+        self.undo.back().and_then(|step| step.name.as_deref())
+    }
+
+    /// Name of the step `take_redo()` would return
+    pub(crate) fn redo_name(&self) -> Option<&str> {
+        self.redo.last().and_then(|step| step.name.as_deref())
+    }
+
+    /// Returns the applied steps, including pending edits, to be discarded
+    pub(crate) fn into_applied(self) -> impl Iterator<Item = Step> {
+        let pending = Step {
+            name: None,
+            edits: self.pending,
+        };
+        self.undo.into_iter().chain(std::iter::once(pending))
+    }
+
+    /// Gets the removed nodes that the history can still bring back
+    pub(crate) fn held_nodes(&self) -> HashSet<NodeId> {
+        let steps = self.undo.iter().chain(&self.redo);
+        steps
+            .flat_map(|step| removed_nodes(&step.edits))
+            .chain(removed_nodes(&self.pending))
+            .collect()
+    }
+
+    /// Removes the oldest steps beyond the depth
+    fn overflow(&mut self) -> Vec<Step> {
+        let excess = self.undo.len().saturating_sub(self.depth);
+        self.undo.drain(..excess).collect()
+    }
+}
+
+// =============================================================================
+// Journal
+// =============================================================================
+
+/// The history to restore when a scope is rolled back
+enum SavedHistory {
+    /// Only edits were recorded since the scope began: the number of pending
+    /// edits at that time, or `None` if the history was disabled
+    Pending(Option<usize>),
+    /// The history when the scope began
+    Copy(Option<History>),
+}
+
+/// An open transaction or batch
+struct Scope {
+    /// Index of the scope's first edit in the journal
+    start: usize,
+    /// Nodes created in the scope, freed when it is rolled back
+    created: Vec<NodeId>,
+    /// Removed nodes that left the history in the scope; freed when the
+    /// outermost scope commits, as rolling back brings their steps back
+    released: Vec<NodeId>,
+    history: SavedHistory,
+}
+
+/// Edits made by the open transactions and batches, innermost scope last
+#[derive(Default)]
+pub(crate) struct Journal {
+    edits: Vec<Edit>,
+    scopes: Vec<Scope>,
+}
+
+impl Journal {
+    /// Number of open scopes
+    pub(crate) fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Whether edits are recorded
+    pub(crate) fn is_open(&self) -> bool {
+        !self.scopes.is_empty()
+    }
+
+    /// Opens a scope
+    pub(crate) fn begin(&mut self, history: Option<&History>) {
+        self.scopes.push(Scope {
+            start: self.edits.len(),
+            created: Vec::new(),
+            released: Vec::new(),
+            history: SavedHistory::Pending(history.map(|h| h.pending.len())),
+        });
+    }
+
+    /// Records an applied edit if a scope is open
+    pub(crate) fn record(&mut self, edit: Edit) {
+        if self.is_open() {
+            self.edits.push(edit);
+        }
+    }
+
+    /// Records a node created in the innermost scope
+    pub(crate) fn created(&mut self, node: NodeId) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.created.push(node);
+            self.edits.push(Edit::Added(node));
+        }
+    }
+
+    /// Defers freeing removed nodes until the outermost scope commits
+    pub(crate) fn release(&mut self, nodes: Vec<NodeId>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.released.extend(nodes);
+        }
+    }
+
+    /// Copies the history before a change other than recording an edit
+    ///
+    /// Called before recording an edit drops the redo steps, and before
+    /// checkpoints, undo, redo, `clear()` and changes to the history settings.
+    pub(crate) fn save_history(&mut self, history: Option<&History>) {
+        for scope in &mut self.scopes {
+            if let SavedHistory::Pending(pending) = scope.history {
+                let mut copy = history.cloned();
+                if let (Some(copy), Some(pending)) = (&mut copy, pending) {
+                    copy.pending.truncate(pending);
+                }
+                scope.history = SavedHistory::Copy(copy);
+            }
+        }
+    }
+
+    /// Closes the innermost scope, keeping its edits
+    ///
+    /// @returns - `None` if no scope is open, otherwise the removed nodes to
+    /// free, once the outermost scope has committed
+    pub(crate) fn commit(&mut self) -> Option<Vec<NodeId>> {
+        let scope = self.scopes.pop()?;
+        if let Some(outer) = self.scopes.last_mut() {
+            outer.created.extend(scope.created);
+            outer.released.extend(scope.released);
+            return Some(Vec::new());
+        }
+        let edits = std::mem::take(&mut self.edits);
+        Some(removed_nodes(&edits).chain(scope.released).collect())
+    }
+
+    /// Closes the innermost scope, reverting its edits and history changes
+    ///
+    /// @returns - `None` if no scope is open, otherwise the nodes created in
+    /// the scope, to be freed
+    pub(crate) fn rollback(
+        &mut self,
+        target: &mut Target,
+        history: &mut Option<History>,
+    ) -> Option<Vec<NodeId>> {
+        let scope = self.scopes.pop()?;
+        for edit in self.edits.drain(scope.start..).rev() {
+            edit.apply(target, false);
+        }
+        match scope.history {
+            SavedHistory::Pending(Some(pending)) => {
+                if let Some(history) = history {
+                    history.pending.truncate(pending);
+                }
+            }
+            SavedHistory::Pending(None) => {}
+            SavedHistory::Copy(copy) => *history = copy,
+        }
+        Some(scope.created)
+    }
+}
@@ -35,6 +35,27 @@
 //! - Positive `x` is to the right
 //! - Positive `y` is downward
 //! - For the root node, `x` and `y` are always 0
+//!
+//! ## Packed Layout Buffers
+//!
+//! `TaffyTree.getLayoutsBuffer()` returns a [`JsLayoutBuffer`], which stores the
+//! layouts of a whole subtree in one `Float32Array`. Each node occupies
+//! `LAYOUT_BUFFER_STRIDE` (18) consecutive floats in this fixed order:
+//!
+//! | Offset | Field           | Offset | Field          |
+//! |--------|-----------------|--------|----------------|
+//! | 0      | `x`             | 9      | `borderBottom` |
+//! | 1      | `y`             | 10     | `paddingLeft`  |
+//! | 2      | `width`         | 11     | `paddingRight` |
+//! | 3      | `height`        | 12     | `paddingTop`   |
+//! | 4      | `contentWidth`  | 13     | `paddingBottom`|
+//! | 5      | `contentHeight` | 14     | `marginLeft`   |
+//! | 6      | `borderLeft`    | 15     | `marginRight`  |
+//! | 7      | `borderRight`   | 16     | `marginTop`    |
+//! | 8      | `borderTop`     | 17     | `marginBottom` |
+//!
+//! This layout is part of the public API: new fields will only ever be added
+//! in a new major version.
 
 use crate::types::*;
 use crate::utils::serialize;
@@ -545,7 +566,7 @@ impl JsLayout {
     /// Internal helper to get a property value by its key
     ///
     /// @throws Error if the key is unknown.
-    fn get_property(&self, path: &str) -> JsValue {
+    pub(crate) fn get_property(&self, path: &str) -> JsValue {
         match path {
             // Rendering order
             "order" => JsValue::from(self.inner.order),
@@ -647,15 +668,134 @@ impl JsLayout {
     }
 }
 
+impl JsLayout {
+    /// Appends this layout's values to `out` in packed buffer order
+    ///
+    /// See the module documentation for the field order.
+    pub(crate) fn write_packed(&self, out: &mut Vec<f32>) {
+        let l = &self.inner;
+        out.extend_from_slice(&[
+            l.location.x,
+            l.location.y,
+            l.size.width,
+            l.size.height,
+            l.content_size.width,
+            l.content_size.height,
+            l.border.left,
+            l.border.right,
+            l.border.top,
+            l.border.bottom,
+            l.padding.left,
+            l.padding.right,
+            l.padding.top,
+            l.padding.bottom,
+            l.margin.left,
+            l.margin.right,
+            l.margin.top,
+            l.margin.bottom,
+        ]);
+    }
+}
+
+// =============================================================================
+// Packed Layout Buffer
+// =============================================================================
+
+/// Number of floats stored per node in a [`JsLayoutBuffer`]
+pub const LAYOUT_BUFFER_STRIDE: usize = 18;
+
+/// Layouts of a whole subtree packed into flat typed arrays.
+///
+/// Returned by `TaffyTree.getLayoutsBuffer()`. `nodes[i]` is the node whose
+/// layout starts at `data[i * stride]`. Nodes are listed in depth-first
+/// pre-order, starting with the root.
+///
+/// @example
+/// ```typescript
+/// const tree = new TaffyTree();
+/// const childStyle = new Style();
+/// childStyle.size = { width: 50, height: 20 };
+/// const child = tree.newLeaf(childStyle);
+/// const root = tree.newWithChildren(new Style(), [child]);
+/// tree.computeLayout(root, { width: 200, height: 100 });
+///
+/// const buffer = tree.getLayoutsBuffer(root);
+/// const nodes = buffer.nodes;
+/// const data = buffer.data;
+/// for (let i = 0; i < buffer.length; i++) {
+///   const base = i * buffer.stride;
+///   console.log(nodes[i], data[base], data[base + 1], data[base + 2], data[base + 3]);
+/// }
+/// ```
+#[wasm_bindgen(js_name = LayoutBuffer)]
+#[derive(Clone, Debug, Default)]
+pub struct JsLayoutBuffer {
+    nodes: Vec<u64>,
+    data: Vec<f32>,
+}
+
+#[wasm_bindgen(js_class = "LayoutBuffer")]
+impl JsLayoutBuffer {
+    /// Gets the node IDs, parallel to the layout records in `data`
+    ///
+    /// Each access copies the IDs into a new array.
+    ///
+    /// @returns - The node IDs in depth-first pre-order
+    #[wasm_bindgen(getter)]
+    pub fn nodes(&self) -> Vec<u64> {
+        self.nodes.clone()
+    }
+
+    /// Gets the packed layout values
+    ///
+    /// Each access copies the values into a new array.
+    ///
+    /// @returns - `length * stride` floats, one record per node
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<f32> {
+        self.data.clone()
+    }
+
+    /// Gets the number of floats stored per node
+    ///
+    /// @returns - Always 18 for the current format
+    #[wasm_bindgen(getter)]
+    pub fn stride(&self) -> usize {
+        LAYOUT_BUFFER_STRIDE
+    }
+
+    /// Gets the number of nodes in the buffer
+    ///
+    /// @returns - The node count
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl JsLayoutBuffer {
+    /// Creates an empty buffer with room for `capacity` nodes
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        JsLayoutBuffer {
+            nodes: Vec::with_capacity(capacity),
+            data: Vec::with_capacity(capacity * LAYOUT_BUFFER_STRIDE),
+        }
+    }
+
+    /// Appends one node's layout record
+    pub(crate) fn push(&mut self, node: u64, layout: &JsLayout) {
+        self.nodes.push(node);
+        layout.write_packed(&mut self.data);
+    }
+}
+
 // =============================================================================
 // Conversion Implementations
 // =============================================================================
 
 impl From<&taffy::Layout> for JsLayout {
     fn from(layout: &taffy::Layout) -> Self {
-        JsLayout {
-            inner: layout.clone(),
-        }
+        JsLayout { inner: *layout }
     }
 }
 
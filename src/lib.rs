@@ -16,7 +16,14 @@
 //!
 //! - **[`enums`]**: CSS layout property enums (Display, Position, FlexDirection, etc.)
 //! - **[`error`]**: Error handling types and result mapping utilities
-//! - **[`layout`]**: Computed layout result wrapper (`Layout` class)
+//! - **[`layout`]**: Computed layout result wrapper (`Layout` and `LayoutBuffer` classes)
+//! - **[`measure`]**: Measure function calls and memoization of their results
+//! - **[`text`]**: Native monospace text measurement for terminal UIs (`enableTextMeasure`)
+//! - **[`snapshot`]**: Versioned tree snapshot format (`toSnapshot`/`fromSnapshot`)
+//! - **[`binary`]**: Compact binary snapshot format (`toBytes`/`fromBytes`)
+//! - **[`batch`]**: Batched tree mutations (`applyBatch`)
+//! - **[`history`]**: Undo/redo history of tree edits (`undo`/`redo`)
+//! - **[`css`]**: CSS declaration parsing and formatting (`Style.fromCss`/`applyCss`/`toCss`)
 //! - **[`style`]**: Style configuration object (`Style` class)
 //! - **[`tree`]**: Layout tree management (`TaffyTree` class)
 //! - **[`types`]**: Data transfer objects and TypeScript type definitions
@@ -59,10 +66,17 @@
 //! - Custom rendering engines
 //! - Any scenario requiring fast, accurate CSS layout computation
 
+pub mod batch;
+pub mod binary;
+pub mod css;
 pub mod enums;
 pub mod error;
+pub mod history;
 pub mod layout;
+pub mod measure;
+pub mod snapshot;
 pub mod style;
+pub mod text;
 pub mod tree;
 pub mod types;
 pub mod typescript;
@@ -71,7 +85,7 @@ pub mod utils;
 // Re-export all public types for convenient access
 pub use enums::*;
 pub use error::JsTaffyError;
-pub use layout::JsLayout;
+pub use layout::{JsLayout, JsLayoutBuffer};
 pub use style::JsStyle;
 pub use tree::JsTaffyTree;
 pub use types::*;
@@ -0,0 +1,358 @@
+//! # Measure Module
+//!
+//! This module calls the JavaScript measure functions of `TaffyTree` and
+//! memoizes their results.
+//!
+//! ## Arguments
+//!
+//! By default every call receives freshly created `knownDimensions` and
+//! `availableSpace` objects and a `Style` copy of the node's style. With
+//! `TaffyTree.setMeasureArgs()` the two size objects can instead be reused
+//! across calls, and the style left out, which avoids most allocations on
+//! trees with many measured leaves.
+//!
+//! ## Cache
+//!
+//! `TaffyTree.enableMeasureCache()` memoizes measure results. Taffy's own
+//! layout cache only keeps a few results per node, so a leaf is measured
+//! again whenever its subtree is laid out under constraints Taffy no longer
+//! remembers, for example when a container is resized back and forth. The
+//! cache keeps the [`ENTRIES_PER_NODE`] most recently used results per node,
+//! keyed by constraint pair (`knownDimensions`, `availableSpace`), so such
+//! calls skip the JavaScript function. Entries of a node are dropped when
+//! anything that can change its measurement changes: its style, context,
+//! measure function or an explicit `markDirty()`. All entries are dropped
+//! when `computeLayoutWithMeasure()` is called with a different callback
+//! than the one whose results are cached.
+
+use crate::error::JsTaffyError;
+use crate::style::JsStyle;
+use crate::types::AvailableSizeDto;
+use js_sys::{Function, Object, Reflect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use taffy::prelude::*;
+use wasm_bindgen::JsValue;
+
+// =============================================================================
+// Arguments
+// =============================================================================
+
+/// Options accepted by `TaffyTree.setMeasureArgs()`
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct MeasureArgsOptionsDto {
+    /// Whether the size arguments are reused across calls
+    pub reuse_objects: bool,
+    /// Whether the node's style is passed
+    pub style: bool,
+}
+
+impl Default for MeasureArgsOptionsDto {
+    fn default() -> Self {
+        MeasureArgsOptionsDto {
+            reuse_objects: false,
+            style: true,
+        }
+    }
+}
+
+/// Builds the arguments of measure function calls
+#[derive(Default)]
+pub(crate) struct MeasureArgs {
+    /// Reused size objects, if enabled
+    scratch: Option<Scratch>,
+    /// Whether the node's style is left out
+    skip_style: bool,
+}
+
+/// Size objects reused by every call, with their property keys and values
+struct Scratch {
+    known: Object,
+    available: Object,
+    width: JsValue,
+    height: JsValue,
+    min_content: JsValue,
+    max_content: JsValue,
+}
+
+impl MeasureArgs {
+    pub(crate) fn new(options: MeasureArgsOptionsDto) -> Self {
+        let scratch = options.reuse_objects.then(|| Scratch {
+            known: Object::new(),
+            available: Object::new(),
+            width: "width".into(),
+            height: "height".into(),
+            min_content: "min-content".into(),
+            max_content: "max-content".into(),
+        });
+        MeasureArgs {
+            scratch,
+            skip_style: !options.style,
+        }
+    }
+
+    /// Calls a measure function for a leaf node
+    ///
+    /// Fails with the thrown value, or with an `InvalidArgument` error if
+    /// the result is not a size of finite numbers.
+    pub(crate) fn call(
+        &self,
+        func: &Function,
+        known: Size<Option<f32>>,
+        available: Size<AvailableSpace>,
+        node: NodeId,
+        context: Option<&JsValue>,
+        style: &Style,
+    ) -> Result<Size<f32>, JsValue> {
+        let node = JsValue::from(u64::from(node));
+        let context = context.cloned().unwrap_or(JsValue::UNDEFINED);
+        let fresh;
+        let (known, available) = match &self.scratch {
+            Some(scratch) => {
+                scratch.fill(known, available);
+                (&*scratch.known, &*scratch.available)
+            }
+            None => {
+                let available = AvailableSizeDto {
+                    width: available.width.into(),
+                    height: available.height.into(),
+                };
+                fresh = (
+                    serde_wasm_bindgen::to_value(&known).unwrap_or(JsValue::NULL),
+                    serde_wasm_bindgen::to_value(&available).unwrap_or(JsValue::NULL),
+                );
+                (&fresh.0, &fresh.1)
+            }
+        };
+        let result = if self.skip_style {
+            func.call4(&JsValue::NULL, known, available, &node, &context)?
+        } else {
+            let style = JsValue::from(JsStyle::from(style.clone()));
+            func.call5(&JsValue::NULL, known, available, &node, &context, &style)?
+        };
+        let dimension = |key: &str| {
+            Reflect::get(&result, &key.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+                .map(|value| value as f32)
+                .filter(|value| value.is_finite())
+        };
+        match (result.is_object(), dimension("width"), dimension("height")) {
+            (true, Some(width), Some(height)) => Ok(Size { width, height }),
+            _ => {
+                Err(
+                    JsTaffyError::invalid_argument("measureFunc() result", &result, "Size<number>")
+                        .into(),
+                )
+            }
+        }
+    }
+}
+
+impl Scratch {
+    /// Writes the constraints of the next call into the reused objects
+    fn fill(&self, known: Size<Option<f32>>, available: Size<AvailableSpace>) {
+        let known_value = |value: Option<f32>| value.map_or(JsValue::UNDEFINED, JsValue::from);
+        let available_value = |space: AvailableSpace| match space {
+            AvailableSpace::Definite(value) => JsValue::from(value),
+            AvailableSpace::MinContent => self.min_content.clone(),
+            AvailableSpace::MaxContent => self.max_content.clone(),
+        };
+        // Setting a data property on a plain object cannot fail
+        let _ = Reflect::set(&self.known, &self.width, &known_value(known.width));
+        let _ = Reflect::set(&self.known, &self.height, &known_value(known.height));
+        let _ = Reflect::set(
+            &self.available,
+            &self.width,
+            &available_value(available.width),
+        );
+        let _ = Reflect::set(
+            &self.available,
+            &self.height,
+            &available_value(available.height),
+        );
+    }
+}
+
+// =============================================================================
+// Cache Keys
+// =============================================================================
+
+/// Measure constraints in a comparable form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Constraints {
+    known: [Option<u32>; 2],
+    available: [(u8, u32); 2],
+}
+
+impl Constraints {
+    fn new(known: Size<Option<f32>>, available: Size<AvailableSpace>) -> Self {
+        let space = |space: AvailableSpace| match space {
+            AvailableSpace::Definite(value) => (0, value.to_bits()),
+            AvailableSpace::MinContent => (1, 0),
+            AvailableSpace::MaxContent => (2, 0),
+        };
+        Constraints {
+            known: [
+                known.width.map(f32::to_bits),
+                known.height.map(f32::to_bits),
+            ],
+            available: [space(available.width), space(available.height)],
+        }
+    }
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Number of results kept per node before the least recently used is dropped
+const ENTRIES_PER_NODE: usize = 16;
+
+/// Memoized measure results with hit and miss counters
+#[derive(Debug, Default)]
+pub(crate) struct MeasureCache {
+    /// Results of each node, least recently used first
+    entries: HashMap<NodeId, Vec<(Constraints, Size<f32>)>>,
+    /// The `computeLayoutWithMeasure()` callback of the cached results
+    fallback: Option<Function>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Counters reported by `measureCacheStats()`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MeasureCacheStatsDto {
+    hits: u64,
+    misses: u64,
+    entries: usize,
+}
+
+impl MeasureCache {
+    /// Returns the cached result for these constraints, or calls `measure`
+    /// and caches its result if it succeeds
+    pub(crate) fn measure<E>(
+        &mut self,
+        node: NodeId,
+        known: Size<Option<f32>>,
+        available: Size<AvailableSpace>,
+        measure: impl FnOnce() -> Result<Size<f32>, E>,
+    ) -> Result<Size<f32>, E> {
+        let entries = self.entries.entry(node).or_default();
+        let key = Constraints::new(known, available);
+        if let Some(index) = entries
+            .iter()
+            .position(|(constraints, _)| *constraints == key)
+        {
+            self.hits += 1;
+            entries[index..].rotate_left(1);
+            return Ok(entries[entries.len() - 1].1);
+        }
+        self.misses += 1;
+        let size = measure()?;
+        if entries.len() == ENTRIES_PER_NODE {
+            entries.remove(0);
+        }
+        entries.push((key, size));
+        Ok(size)
+    }
+
+    /// Drops all cached results if `fallback` is not the callback of the last
+    /// `computeLayoutWithMeasure()` pass, as leaves measured by that callback
+    /// would reuse its results
+    pub(crate) fn set_fallback(&mut self, fallback: &Function) {
+        if self.fallback.as_ref() != Some(fallback) {
+            self.entries.clear();
+            self.fallback = Some(fallback.clone());
+        }
+    }
+
+    /// Drops the cached results of a node
+    pub(crate) fn invalidate(&mut self, node: NodeId) {
+        self.entries.remove(&node);
+    }
+
+    /// Drops all cached results, keeping the counters
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Gets the counters for `measureCacheStats()`
+    pub(crate) fn stats(&self) -> MeasureCacheStatsDto {
+        MeasureCacheStatsDto {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.values().map(Vec::len).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_results_for_identical_constraints() {
+        let mut cache = MeasureCache::default();
+        let node = NodeId::from(1u64);
+        let known = Size {
+            width: Some(10.0),
+            height: None,
+        };
+        let available = Size {
+            width: AvailableSpace::Definite(100.0),
+            height: AvailableSpace::MaxContent,
+        };
+        let size = Size {
+            width: 10.0,
+            height: 4.0,
+        };
+
+        let mut measure = |known, available, size: Result<Size<f32>, ()>| {
+            cache.measure(node, known, available, || size)
+        };
+        assert_eq!(measure(known, available, Ok(size)), Ok(size));
+        assert_eq!(measure(known, available, Err(())), Ok(size));
+        let other = Size {
+            width: AvailableSpace::MinContent,
+            ..available
+        };
+        assert_eq!(measure(known, other, Err(())), Err(()));
+        assert_eq!(measure(known, other, Ok(Size::ZERO)), Ok(Size::ZERO));
+        assert_eq!((cache.hits, cache.misses), (1, 3));
+
+        cache.invalidate(node);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn drops_the_least_recently_used_result_of_a_node() {
+        let mut cache = MeasureCache::default();
+        let node = NodeId::from(1u64);
+        let available = |width: usize| Size {
+            width: AvailableSpace::Definite(width as f32),
+            height: AvailableSpace::MaxContent,
+        };
+        let mut measure = |width: usize| {
+            let size = Size {
+                width: width as f32,
+                height: 1.0,
+            };
+            cache.measure(node, Size::NONE, available(width), || Ok::<_, ()>(size))
+        };
+
+        for width in 0..ENTRIES_PER_NODE {
+            measure(width).unwrap();
+        }
+        // Using the oldest result makes the second oldest the one dropped
+        measure(0).unwrap();
+        measure(ENTRIES_PER_NODE).unwrap();
+        measure(0).unwrap();
+        measure(1).unwrap();
+
+        assert_eq!(cache.stats().entries, ENTRIES_PER_NODE);
+        assert_eq!((cache.hits, cache.misses), (2, ENTRIES_PER_NODE as u64 + 2));
+    }
+}
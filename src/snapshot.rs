@@ -0,0 +1,260 @@
+//! # Tree Snapshot Module
+//!
+//! This module implements the versioned document format used by
+//! `TaffyTree.toSnapshot()` and `TaffyTree.fromSnapshot()`.
+//!
+//! ## Format
+//!
+//! A snapshot is a plain JSON-compatible object:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "root": 0,
+//!   "nodes": [
+//!     { "style": { "display": 1, "size": { "width": 100, "height": "auto" } }, "children": [1] },
+//!     { "style": {}, "children": [], "context": { "text": "hello" } }
+//!   ]
+//! }
+//! ```
+//!
+//! - `nodes` are written in depth-first pre-order, so `root` is always `0` in
+//!   snapshots produced by `toSnapshot()`
+//! - `children` holds indices into `nodes`, in child order
+//! - `style` uses the same keys and value formats as `Style.get()`/`Style.set()`;
+//!   properties equal to their default are omitted
+//! - `context` is only present when requested, and is stored as-is
+//! - `layout` is only present when requested. It uses the keys of `Layout.get()`
+//!   and is informational: it is ignored when restoring
+
+use crate::error::{JsTaffyError, describe_value};
+use crate::layout::JsLayout;
+use crate::style::{JsStyle, STYLE_PROPERTY_KEYS};
+use js_sys::{Array, Object, Reflect};
+use serde::Deserialize;
+use taffy::style::Style;
+use wasm_bindgen::prelude::*;
+
+/// Current snapshot format version
+pub(crate) const SNAPSHOT_VERSION: u32 = 1;
+
+/// `Layout.get()` keys written to the `layout` field of a node
+const LAYOUT_KEYS: &[&str] = &[
+    "order",
+    "position",
+    "size",
+    "contentSize",
+    "scrollbarSize",
+    "border",
+    "padding",
+    "margin",
+];
+
+// =============================================================================
+// Options
+// =============================================================================
+
+/// Options accepted by `TaffyTree.toSnapshot()`
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct SnapshotOptionsDto {
+    /// Whether node contexts are written
+    pub include_context: bool,
+    /// Whether computed layouts are written
+    pub include_layout: bool,
+}
+
+// =============================================================================
+// Encoding
+// =============================================================================
+
+/// Encodes a style as an object of its non-default properties
+pub(crate) fn encode_style(style: &JsStyle) -> Object {
+    let defaults = JsStyle::from(Style::default());
+    let obj = Object::new();
+    for &key in STYLE_PROPERTY_KEYS {
+        if style.property_eq(&defaults, key) {
+            continue;
+        }
+        // Keys of `STYLE_PROPERTY_KEYS` are always known
+        if let Ok(value) = style.get_property(key) {
+            let _ = Reflect::set(&obj, &key.into(), &value);
+        }
+    }
+    obj
+}
+
+/// Encodes a single node entry
+pub(crate) fn encode_node(
+    style: &JsStyle,
+    children: &[usize],
+    context: Option<&JsValue>,
+    layout: Option<&JsLayout>,
+) -> Object {
+    let obj = Object::new();
+    let _ = Reflect::set(&obj, &"style".into(), &encode_style(style));
+    let indices: Array = children.iter().map(|&i| JsValue::from(i as u32)).collect();
+    let _ = Reflect::set(&obj, &"children".into(), &indices);
+    if let Some(context) = context {
+        let _ = Reflect::set(&obj, &"context".into(), context);
+    }
+    if let Some(layout) = layout {
+        let layout_obj = Object::new();
+        for &key in LAYOUT_KEYS {
+            let _ = Reflect::set(&layout_obj, &key.into(), &layout.get_property(key));
+        }
+        let _ = Reflect::set(&obj, &"layout".into(), &layout_obj);
+    }
+    obj
+}
+
+/// Wraps encoded nodes into a versioned document
+pub(crate) fn encode_document(root: usize, nodes: Array) -> Object {
+    let obj = Object::new();
+    let _ = Reflect::set(&obj, &"version".into(), &JsValue::from(SNAPSHOT_VERSION));
+    let _ = Reflect::set(&obj, &"root".into(), &JsValue::from(root as u32));
+    let _ = Reflect::set(&obj, &"nodes".into(), &nodes);
+    obj
+}
+
+// =============================================================================
+// Decoding
+// =============================================================================
+
+/// A node read from a snapshot, ready to be inserted into a tree
+pub(crate) struct DecodedNode {
+    /// The node's style
+    pub style: JsStyle,
+    /// Indices of the node's children
+    pub children: Vec<usize>,
+    /// The node's context, if one was stored
+    pub context: Option<JsValue>,
+}
+
+/// Reads a property of a snapshot object
+fn field(obj: &JsValue, key: &str) -> JsValue {
+    Reflect::get(obj, &key.into()).unwrap_or(JsValue::UNDEFINED)
+}
+
+/// Reads a non-negative integer, as used for indices and the version
+fn index(value: &JsValue) -> Option<usize> {
+    value
+        .as_f64()
+        .filter(|n| n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64)
+        .map(|n| n as usize)
+}
+
+/// Decodes the `style` field of a node
+pub(crate) fn decode_style(value: &JsValue, node: usize) -> Result<JsStyle, JsTaffyError> {
+    let mut style = JsStyle::from(Style::default());
+    if value.is_undefined() {
+        return Ok(style);
+    }
+    if !value.is_object() || Array::is_array(value) {
+        return Err(JsTaffyError::invalid_snapshot(format!(
+            "style of node {node} is not an object"
+        )));
+    }
+    let entries = Object::entries(value.unchecked_ref());
+    for entry in entries.iter() {
+        let entry = Array::from(&entry);
+        let key = entry.get(0).as_string().unwrap_or_default();
+        if !STYLE_PROPERTY_KEYS.contains(&key.as_str()) {
+            return Err(JsTaffyError::invalid_snapshot(format!(
+                "unknown style property '{key}' in node {node}"
+            )));
+        }
+        style.set_property(&key, entry.get(1)).map_err(|issue| {
+            JsTaffyError::invalid_snapshot(format!("invalid style of node {node}: {issue}"))
+        })?;
+    }
+    Ok(style)
+}
+
+/// Decodes and validates a snapshot document
+///
+/// Returns the root index and the nodes. The nodes are guaranteed to form a
+/// single tree: every node except the root has exactly one parent, and every
+/// node is reachable from the root.
+pub(crate) fn decode_document(doc: &JsValue) -> Result<(usize, Vec<DecodedNode>), JsTaffyError> {
+    if !doc.is_object() {
+        return Err(JsTaffyError::invalid_snapshot("expected an object"));
+    }
+    let version = field(doc, "version");
+    if index(&version) != Some(SNAPSHOT_VERSION as usize) {
+        return Err(JsTaffyError::invalid_snapshot(format!(
+            "unsupported version {}, expected {SNAPSHOT_VERSION}",
+            describe_value(&version)
+        )));
+    }
+    let nodes_value = field(doc, "nodes");
+    if !Array::is_array(&nodes_value) {
+        return Err(JsTaffyError::invalid_snapshot("'nodes' is not an array"));
+    }
+    let entries = Array::from(&nodes_value);
+    let count = entries.length() as usize;
+    let root = index(&field(doc, "root"))
+        .filter(|&root| root < count)
+        .ok_or_else(|| JsTaffyError::invalid_snapshot("'root' is not a valid node index"))?;
+
+    let mut nodes = Vec::with_capacity(count);
+    let mut parents: Vec<Option<usize>> = vec![None; count];
+    for (i, entry) in entries.iter().enumerate() {
+        if !entry.is_object() {
+            return Err(JsTaffyError::invalid_snapshot(format!(
+                "node {i} is not an object"
+            )));
+        }
+        let style = decode_style(&field(&entry, "style"), i)?;
+
+        let children_value = field(&entry, "children");
+        let mut children = Vec::new();
+        if !children_value.is_undefined() {
+            if !Array::is_array(&children_value) {
+                return Err(JsTaffyError::invalid_snapshot(format!(
+                    "children of node {i} is not an array"
+                )));
+            }
+            for child in Array::from(&children_value).iter() {
+                let child = index(&child).filter(|&c| c < count).ok_or_else(|| {
+                    JsTaffyError::invalid_snapshot(format!(
+                        "node {i} has an invalid child index {}",
+                        describe_value(&child)
+                    ))
+                })?;
+                if child == root || parents[child].is_some() {
+                    return Err(JsTaffyError::invalid_snapshot(format!(
+                        "node {child} has more than one parent"
+                    )));
+                }
+                parents[child] = Some(i);
+                children.push(child);
+            }
+        }
+
+        let context = Reflect::has(&entry, &"context".into())
+            .unwrap_or(false)
+            .then(|| field(&entry, "context"));
+        nodes.push(DecodedNode {
+            style,
+            children,
+            context,
+        });
+    }
+
+    // With at most one parent per node, every node reachable from the root
+    // rules out cycles as well.
+    let mut reachable = vec![false; count];
+    let mut stack = vec![root];
+    while let Some(i) = stack.pop() {
+        reachable[i] = true;
+        stack.extend(&nodes[i].children);
+    }
+    if let Some(orphan) = reachable.iter().position(|&r| !r) {
+        return Err(JsTaffyError::invalid_snapshot(format!(
+            "node {orphan} is not reachable from the root"
+        )));
+    }
+
+    Ok((root, nodes))
+}
@@ -89,6 +89,21 @@
 //! const [display, flexDirection, width] = style.get("display", "flexDirection", "width");
 //! ```
 //!
+//! ## Validation
+//!
+//! Values that do not match a property's type (including numbers that are not a
+//! variant of the property's enum) are skipped and recorded as warnings, available
+//! from `style.takeWarnings()`. With `Style.enableStrictMode()`, or
+//! `style.set(props, true)` for a single call, they throw a `TaffyError` with code
+//! `InvalidStyle` instead.
+//!
+//! ## CSS Declarations
+//!
+//! `Style.fromCss()` and `style.applyCss()` accept CSS declaration lists such as
+//! `"display: flex; gap: 8px 4px; margin: 0 auto"`; see the [`css`](crate::css)
+//! module for the supported syntax. `style.toCss()` writes a style back out in
+//! the same syntax.
+//!
 //! ## Property Categories
 //!
 //! | Category | Properties |
@@ -108,10 +123,14 @@
 //! - **LengthPercentage**: `number` or `"{number}%"`
 //! - **LengthPercentageAuto**: `number`, `"{number}%"`, or `"auto"`
 
+use crate::css;
 use crate::enums::*;
+use crate::error::{ArgumentError, JsTaffyError, decode_argument, describe_value};
 use crate::types::*;
 use crate::utils::log;
 use crate::utils::serialize;
+use serde::de::DeserializeOwned;
+use std::cell::Cell;
 use taffy::style::{self as TaffyStyle};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
@@ -141,6 +160,18 @@ use wasm_bindgen::prelude::*;
 pub struct JsStyle {
     /// Internal Taffy style object (crate-internal access for tree operations)
     pub(crate) inner: TaffyStyle::Style,
+    /// Invalid values skipped by lenient validation, drained by `takeWarnings()`
+    pub(crate) warnings: Vec<String>,
+}
+
+thread_local! {
+    /// Whether style writes are validated strictly by default
+    static STRICT_MODE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns whether strict style validation is enabled globally
+fn strict_mode() -> bool {
+    STRICT_MODE.with(Cell::get)
 }
 
 #[wasm_bindgen(js_class = "Style")]
@@ -169,18 +200,161 @@ impl JsStyle {
     /// });
     /// ```
     #[wasm_bindgen(constructor)]
-    pub fn new(props: Option<JsValue>) -> JsStyle {
-        let mut style = JsStyle {
-            inner: TaffyStyle::Style::default(),
-        };
+    pub fn new(props: Option<JsValue>) -> Result<JsStyle, JsValue> {
+        let mut style = JsStyle::from(TaffyStyle::Style::default());
 
         if let Some(props_value) = props {
             if props_value.is_object() {
-                style.set(props_value);
+                style.set(props_value, None)?;
             }
         }
 
-        style
+        Ok(style)
+    }
+
+    // =========================================================================
+    // Validation
+    // =========================================================================
+
+    /// Enables strict validation for all style writes
+    ///
+    /// In strict mode, `Style.set()`, the `Style` constructor and the property
+    /// setters throw a `TaffyError` with code `InvalidStyle` instead of skipping
+    /// invalid values. `Style.set()` can override the mode per call.
+    ///
+    /// @example
+    /// ```typescript
+    /// Style.enableStrictMode();
+    /// try {
+    ///   new Style({ display: 42 } as never);
+    /// } catch (e) {
+    ///   console.log((e as TaffyError).code); // "InvalidStyle"
+    /// }
+    /// Style.disableStrictMode();
+    /// ```
+    #[wasm_bindgen(js_name = enableStrictMode)]
+    pub fn enable_strict_mode() {
+        STRICT_MODE.with(|strict| strict.set(true));
+    }
+
+    /// Disables strict validation for style writes (the default)
+    ///
+    /// Invalid values are skipped and recorded as warnings, which can be
+    /// retrieved with `takeWarnings()`.
+    #[wasm_bindgen(js_name = disableStrictMode)]
+    pub fn disable_strict_mode() {
+        STRICT_MODE.with(|strict| strict.set(false));
+    }
+
+    /// Returns and clears the warnings recorded by lenient validation
+    ///
+    /// @returns - One message per skipped value, e.g. `"display: 42 is not a valid Display"`
+    ///
+    /// @example
+    /// ```typescript
+    /// const style = new Style();
+    /// style.set({ width: "wide" } as never);
+    /// console.log(style.takeWarnings()); // ['width: "wide" is not a valid Dimension']
+    /// console.log(style.takeWarnings()); // []
+    /// ```
+    #[wasm_bindgen(js_name = takeWarnings)]
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    // =========================================================================
+    // CSS Declarations
+    // =========================================================================
+
+    /// Creates a style from a CSS declaration list
+    ///
+    /// Accepts standard property names and the common shorthands (`margin`,
+    /// `padding`, `inset`, `flex`, `gap`, `grid-area`, `place-items`,
+    /// `overflow`, ...). Lengths must be in `px` or `%`.
+    ///
+    /// @param css - Declarations such as `"display: flex; gap: 8px 4px"`
+    /// @param strict - Overrides the global strict mode for this call
+    ///
+    /// @returns - A new `Style` with the declarations applied to the defaults
+    ///
+    /// @throws `TaffyError` with code `InvalidStyle` in strict mode if any declaration
+    /// cannot be parsed. Each entry of `issues` starts with its `line:column`.
+    ///
+    /// @example
+    /// ```typescript
+    /// const style = Style.fromCss("display: flex; flex-direction: column; margin: 0 auto");
+    /// console.log(style.flexDirection === FlexDirection.Column); // true
+    /// ```
+    #[wasm_bindgen(js_name = fromCss)]
+    pub fn from_css(css: &str, strict: Option<bool>) -> Result<JsStyle, JsValue> {
+        let mut style = JsStyle::from(TaffyStyle::Style::default());
+        style.apply_css(css, strict)?;
+        Ok(style)
+    }
+
+    /// Applies a CSS declaration list to this style
+    ///
+    /// Declarations that cannot be parsed are skipped and recorded as warnings
+    /// prefixed with their `line:column` (see `takeWarnings()`), unless strict
+    /// validation applies.
+    ///
+    /// @param css - Declarations such as `"padding: 4px 8px; flex: 1"`
+    /// @param strict - Overrides the global strict mode for this call
+    ///
+    /// @throws `TaffyError` with code `InvalidStyle` in strict mode if any declaration
+    /// cannot be parsed. No declaration is applied in that case.
+    ///
+    /// @example
+    /// ```typescript
+    /// const style = new Style();
+    /// style.applyCss("flex: 1; padding: 4px 8px; flex-direction: sideways");
+    /// console.log(style.takeWarnings());
+    /// // ["1:28: flex-direction: `sideways` is not one of row, row-reverse, column, column-reverse"]
+    /// ```
+    #[wasm_bindgen(js_name = applyCss)]
+    pub fn apply_css(&mut self, css: &str, strict: Option<bool>) -> Result<(), JsValue> {
+        let strict = strict.unwrap_or_else(strict_mode);
+        let mut inner = self.inner.clone();
+        let issues: Vec<String> = css::apply(&mut inner, css)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        if strict && !issues.is_empty() {
+            return Err(JsTaffyError::invalid_style(issues).into());
+        }
+        self.inner = inner;
+        self.warnings.extend(issues);
+        Ok(())
+    }
+
+    /// Formats this style as a CSS declaration block
+    ///
+    /// Writes one `property: value;` per line, using shorthands such as
+    /// `margin`, `flex` and `place-items` where they fit. The result can be
+    /// read back with `Style.fromCss()`.
+    ///
+    /// @param options - Use `{ onlyNonDefault: true }` to leave out properties
+    /// that still have their default value
+    ///
+    /// @returns - The CSS declarations
+    ///
+    /// @throws `TaffyError` with code `InvalidArgument` if `options` is malformed
+    ///
+    /// @example
+    /// ```typescript
+    /// const style = Style.fromCss("display: grid; margin: 0 auto; flex-grow: 1");
+    /// console.log(style.toCss({ onlyNonDefault: true }));
+    /// // display: grid;
+    /// // flex: 1 1 auto;
+    /// // margin: 0px auto;
+    /// ```
+    #[wasm_bindgen(js_name = toCss)]
+    pub fn to_css(&self, options: Option<JsCssOptions>) -> Result<String, JsValue> {
+        let options: css::CssOptionsDto = match options {
+            Some(o) => decode_argument("options", o.into(), "CssOptions")?,
+            None => css::CssOptionsDto::default(),
+        };
+        Ok(css::serialize(&self.inner, options.only_non_default))
     }
 
     // =========================================================================
@@ -374,15 +548,8 @@ impl JsStyle {
     /// style.alignItems = AlignItems.Center;
     /// ```
     #[wasm_bindgen(setter, js_name = alignItems)]
-    pub fn set_align_items(&mut self, val: JsOptionAlignItems) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.align_items = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            Some(unsafe { std::mem::transmute::<u8, JsAlignItems>(n as u8) }.into())
-        } else {
-            None
-        };
+    pub fn set_align_items(&mut self, val: JsOptionAlignItems) -> Result<(), JsValue> {
+        self.assign("alignItems", val.into())
     }
 
     /// Gets the align-self property
@@ -408,19 +575,8 @@ impl JsStyle {
     /// style.alignSelf = AlignSelf.Stretch;
     /// ```
     #[wasm_bindgen(setter, js_name = alignSelf)]
-    pub fn set_align_self(&mut self, val: JsOptionAlignSelf) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.align_self = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            let js_val = unsafe { std::mem::transmute::<u8, JsAlignSelf>(n as u8) };
-            match js_val {
-                JsAlignSelf::Auto => None,
-                _ => Some(js_val.into()),
-            }
-        } else {
-            None
-        };
+    pub fn set_align_self(&mut self, val: JsOptionAlignSelf) -> Result<(), JsValue> {
+        self.assign("alignSelf", val.into())
     }
 
     /// Gets the align-content property
@@ -443,15 +599,8 @@ impl JsStyle {
     /// style.alignContent = AlignContent.SpaceBetween;
     /// ```
     #[wasm_bindgen(setter, js_name = alignContent)]
-    pub fn set_align_content(&mut self, val: JsOptionAlignContent) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.align_content = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            Some(unsafe { std::mem::transmute::<u8, JsAlignContent>(n as u8) }.into())
-        } else {
-            None
-        };
+    pub fn set_align_content(&mut self, val: JsOptionAlignContent) -> Result<(), JsValue> {
+        self.assign("alignContent", val.into())
     }
 
     /// Gets the justify-content property
@@ -474,15 +623,8 @@ impl JsStyle {
     /// style.justifyContent = JustifyContent.Center;
     /// ```
     #[wasm_bindgen(setter, js_name = justifyContent)]
-    pub fn set_justify_content(&mut self, val: JsOptionJustifyContent) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.justify_content = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            Some(unsafe { std::mem::transmute::<u8, JsJustifyContent>(n as u8) }.into())
-        } else {
-            None
-        };
+    pub fn set_justify_content(&mut self, val: JsOptionJustifyContent) -> Result<(), JsValue> {
+        self.assign("justifyContent", val.into())
     }
 
     // =========================================================================
@@ -509,13 +651,8 @@ impl JsStyle {
     /// style.aspectRatio = 16 / 9;
     /// ```
     #[wasm_bindgen(setter, js_name = aspectRatio)]
-    pub fn set_aspect_ratio(&mut self, val: JsOptionNumber) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.aspect_ratio = if val.is_undefined() || val.is_null() {
-            None
-        } else {
-            val.as_f64().map(|v| v as f32)
-        };
+    pub fn set_aspect_ratio(&mut self, val: JsOptionNumber) -> Result<(), JsValue> {
+        self.assign("aspectRatio", val.into())
     }
 
     /// Gets the overflow behavior
@@ -542,11 +679,8 @@ impl JsStyle {
     /// style.overflow = { x: Overflow.Hidden, y: Overflow.Scroll };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_overflow(&mut self, val: JsPointOverflow) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(s) = serde_wasm_bindgen::from_value::<PointOverflowDto>(val) {
-            self.inner.overflow = s.into();
-        }
+    pub fn set_overflow(&mut self, val: JsPointOverflow) -> Result<(), JsValue> {
+        self.assign("overflow", val.into())
     }
 
     /// Gets the horizontal overflow behavior
@@ -640,11 +774,8 @@ impl JsStyle {
     /// style.flexBasis = 100;
     /// ```
     #[wasm_bindgen(setter, js_name = flexBasis)]
-    pub fn set_flex_basis(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.flex_basis = d.into();
-        }
+    pub fn set_flex_basis(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("flexBasis", val.into())
     }
 
     // =========================================================================
@@ -673,20 +804,8 @@ impl JsStyle {
     /// style.size = { width: 200, height: "100%" };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_size(&mut self, val: JsSizeDimension) {
-        let val: JsValue = val.unchecked_into();
-        match serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(val.clone()) {
-            Ok(s) => {
-                self.inner.size = s.into();
-            }
-            Err(e) => {
-                let json = js_sys::JSON::stringify(&val)
-                    .ok()
-                    .and_then(|s| s.as_string())
-                    .unwrap_or("?".to_string());
-                log(&format!("set_size Error: {} | Input: {}", e, json));
-            }
-        }
+    pub fn set_size(&mut self, val: JsSizeDimension) -> Result<(), JsValue> {
+        self.assign("size", val.into())
     }
 
     /// Gets the minimum size constraints
@@ -711,11 +830,8 @@ impl JsStyle {
     /// style.minSize = { width: 100, height: "auto" };
     /// ```
     #[wasm_bindgen(setter, js_name = minSize)]
-    pub fn set_min_size(&mut self, val: JsSizeDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(val) {
-            self.inner.min_size = s.into();
-        }
+    pub fn set_min_size(&mut self, val: JsSizeDimension) -> Result<(), JsValue> {
+        self.assign("minSize", val.into())
     }
 
     /// Gets the maximum size constraints
@@ -740,11 +856,8 @@ impl JsStyle {
     /// style.maxSize = { width: "auto", height: 500 };
     /// ```
     #[wasm_bindgen(setter, js_name = maxSize)]
-    pub fn set_max_size(&mut self, val: JsSizeDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(val) {
-            self.inner.max_size = s.into();
-        }
+    pub fn set_max_size(&mut self, val: JsSizeDimension) -> Result<(), JsValue> {
+        self.assign("maxSize", val.into())
     }
 
     /// Gets the width
@@ -768,11 +881,8 @@ impl JsStyle {
     /// style.width = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_width(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.size.width = d.into();
-        }
+    pub fn set_width(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("width", val.into())
     }
 
     /// Gets the height
@@ -796,11 +906,8 @@ impl JsStyle {
     /// style.height = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_height(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.size.height = d.into();
-        }
+    pub fn set_height(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("height", val.into())
     }
 
     /// Gets the minimum width
@@ -823,11 +930,8 @@ impl JsStyle {
     /// style.minWidth = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = minWidth)]
-    pub fn set_min_width(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.min_size.width = d.into();
-        }
+    pub fn set_min_width(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("minWidth", val.into())
     }
 
     /// Gets the minimum height
@@ -850,11 +954,8 @@ impl JsStyle {
     /// style.minHeight = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = minHeight)]
-    pub fn set_min_height(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.min_size.height = d.into();
-        }
+    pub fn set_min_height(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("minHeight", val.into())
     }
 
     /// Gets the maximum width
@@ -877,11 +978,8 @@ impl JsStyle {
     /// style.maxWidth = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = maxWidth)]
-    pub fn set_max_width(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.max_size.width = d.into();
-        }
+    pub fn set_max_width(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("maxWidth", val.into())
     }
 
     /// Gets the maximum height
@@ -904,11 +1002,8 @@ impl JsStyle {
     /// style.maxHeight = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = maxHeight)]
-    pub fn set_max_height(&mut self, val: JsDimension) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(val) {
-            self.inner.max_size.height = d.into();
-        }
+    pub fn set_max_height(&mut self, val: JsDimension) -> Result<(), JsValue> {
+        self.assign("maxHeight", val.into())
     }
 
     // =========================================================================
@@ -941,11 +1036,8 @@ impl JsStyle {
     /// style.margin = { left: 10, right: 10, top: 5, bottom: 5 };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_margin(&mut self, val: JsRectLengthPercentageAuto) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(m) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageAutoDto>>(val) {
-            self.inner.margin = m.into();
-        }
+    pub fn set_margin(&mut self, val: JsRectLengthPercentageAuto) -> Result<(), JsValue> {
+        self.assign("margin", val.into())
     }
 
     /// Gets the left margin
@@ -969,11 +1061,8 @@ impl JsStyle {
     /// style.marginLeft = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = marginLeft)]
-    pub fn set_margin_left(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.margin.left = d.into();
-        }
+    pub fn set_margin_left(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("marginLeft", val)
     }
 
     /// Gets the right margin
@@ -997,11 +1086,8 @@ impl JsStyle {
     /// style.marginRight = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = marginRight)]
-    pub fn set_margin_right(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.margin.right = d.into();
-        }
+    pub fn set_margin_right(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("marginRight", val)
     }
 
     /// Gets the top margin
@@ -1025,11 +1111,8 @@ impl JsStyle {
     /// style.marginTop = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = marginTop)]
-    pub fn set_margin_top(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.margin.top = d.into();
-        }
+    pub fn set_margin_top(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("marginTop", val)
     }
 
     /// Gets the bottom margin
@@ -1053,11 +1136,8 @@ impl JsStyle {
     /// style.marginBottom = "auto";
     /// ```
     #[wasm_bindgen(setter, js_name = marginBottom)]
-    pub fn set_margin_bottom(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.margin.bottom = d.into();
-        }
+    pub fn set_margin_bottom(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("marginBottom", val)
     }
 
     /// Gets the padding
@@ -1086,11 +1166,8 @@ impl JsStyle {
     /// style.padding = { left: 20, right: 20, top: 10, bottom: 10 };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_padding(&mut self, val: JsRectLengthPercentage) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(p) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageDto>>(val) {
-            self.inner.padding = p.into();
-        }
+    pub fn set_padding(&mut self, val: JsRectLengthPercentage) -> Result<(), JsValue> {
+        self.assign("padding", val.into())
     }
 
     /// Gets the left padding
@@ -1113,11 +1190,8 @@ impl JsStyle {
     /// style.paddingLeft = "10%";
     /// ```
     #[wasm_bindgen(setter, js_name = paddingLeft)]
-    pub fn set_padding_left(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.padding.left = d.into();
-        }
+    pub fn set_padding_left(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("paddingLeft", val)
     }
 
     /// Gets the right padding
@@ -1140,11 +1214,8 @@ impl JsStyle {
     /// style.paddingRight = "10%";
     /// ```
     #[wasm_bindgen(setter, js_name = paddingRight)]
-    pub fn set_padding_right(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.padding.right = d.into();
-        }
+    pub fn set_padding_right(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("paddingRight", val)
     }
 
     /// Gets the top padding
@@ -1167,11 +1238,8 @@ impl JsStyle {
     /// style.paddingTop = "5%";
     /// ```
     #[wasm_bindgen(setter, js_name = paddingTop)]
-    pub fn set_padding_top(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.padding.top = d.into();
-        }
+    pub fn set_padding_top(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("paddingTop", val)
     }
 
     /// Gets the bottom padding
@@ -1194,11 +1262,8 @@ impl JsStyle {
     /// style.paddingBottom = "5%";
     /// ```
     #[wasm_bindgen(setter, js_name = paddingBottom)]
-    pub fn set_padding_bottom(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.padding.bottom = d.into();
-        }
+    pub fn set_padding_bottom(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("paddingBottom", val)
     }
 
     /// Gets the border width
@@ -1227,11 +1292,8 @@ impl JsStyle {
     /// style.border = { left: 1, right: 1, top: 1, bottom: 1 };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_border(&mut self, val: JsRectLengthPercentage) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(b) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageDto>>(val) {
-            self.inner.border = b.into();
-        }
+    pub fn set_border(&mut self, val: JsRectLengthPercentage) -> Result<(), JsValue> {
+        self.assign("border", val.into())
     }
 
     /// Gets the left border width
@@ -1254,11 +1316,8 @@ impl JsStyle {
     /// style.borderLeft = "2%";
     /// ```
     #[wasm_bindgen(setter, js_name = borderLeft)]
-    pub fn set_border_left(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.border.left = d.into();
-        }
+    pub fn set_border_left(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("borderLeft", val)
     }
 
     /// Gets the right border width
@@ -1281,11 +1340,8 @@ impl JsStyle {
     /// style.borderRight = "2%";
     /// ```
     #[wasm_bindgen(setter, js_name = borderRight)]
-    pub fn set_border_right(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.border.right = d.into();
-        }
+    pub fn set_border_right(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("borderRight", val)
     }
 
     /// Gets the top border width
@@ -1308,11 +1364,8 @@ impl JsStyle {
     /// style.borderTop = "2%";
     /// ```
     #[wasm_bindgen(setter, js_name = borderTop)]
-    pub fn set_border_top(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.border.top = d.into();
-        }
+    pub fn set_border_top(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("borderTop", val)
     }
 
     /// Gets the bottom border width
@@ -1335,11 +1388,8 @@ impl JsStyle {
     /// style.borderBottom = "2%";
     /// ```
     #[wasm_bindgen(setter, js_name = borderBottom)]
-    pub fn set_border_bottom(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.border.bottom = d.into();
-        }
+    pub fn set_border_bottom(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("borderBottom", val)
     }
 
     /// Gets the gap
@@ -1366,11 +1416,8 @@ impl JsStyle {
     /// style.gap = { width: 10, height: 10 };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_gap(&mut self, val: JsSizeLengthPercentage) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(g) = serde_wasm_bindgen::from_value::<SizeDto<LengthPercentageDto>>(val) {
-            self.inner.gap = g.into();
-        }
+    pub fn set_gap(&mut self, val: JsSizeLengthPercentage) -> Result<(), JsValue> {
+        self.assign("gap", val.into())
     }
 
     /// Gets the column gap (horizontal spacing between items)
@@ -1393,11 +1440,8 @@ impl JsStyle {
     /// style.columnGap = "5%";
     /// ```
     #[wasm_bindgen(setter, js_name = columnGap)]
-    pub fn set_column_gap(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.gap.width = d.into();
-        }
+    pub fn set_column_gap(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("columnGap", val)
     }
 
     /// Gets the row gap (vertical spacing between items)
@@ -1420,11 +1464,8 @@ impl JsStyle {
     /// style.rowGap = "5%";
     /// ```
     #[wasm_bindgen(setter, js_name = rowGap)]
-    pub fn set_row_gap(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(val) {
-            self.inner.gap.height = d.into();
-        }
+    pub fn set_row_gap(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("rowGap", val)
     }
 
     /// Gets the inset
@@ -1454,11 +1495,8 @@ impl JsStyle {
     /// style.inset = { left: 0, top: 0, right: "auto", bottom: "auto" };
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_inset(&mut self, val: JsRectLengthPercentageAuto) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(i) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageAutoDto>>(val) {
-            self.inner.inset = i.into();
-        }
+    pub fn set_inset(&mut self, val: JsRectLengthPercentageAuto) -> Result<(), JsValue> {
+        self.assign("inset", val.into())
     }
 
     /// Gets the left inset offset
@@ -1483,11 +1521,8 @@ impl JsStyle {
     /// style.left = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_left(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.inset.left = d.into();
-        }
+    pub fn set_left(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("left", val)
     }
 
     /// Gets the right inset offset
@@ -1512,11 +1547,8 @@ impl JsStyle {
     /// style.right = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_right(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.inset.right = d.into();
-        }
+    pub fn set_right(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("right", val)
     }
 
     /// Gets the top inset offset
@@ -1541,11 +1573,8 @@ impl JsStyle {
     /// style.top = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_top(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.inset.top = d.into();
-        }
+    pub fn set_top(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("top", val)
     }
 
     /// Gets the bottom inset offset
@@ -1570,11 +1599,8 @@ impl JsStyle {
     /// style.bottom = "auto";
     /// ```
     #[wasm_bindgen(setter)]
-    pub fn set_bottom(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(val) {
-            self.inner.inset.bottom = d.into();
-        }
+    pub fn set_bottom(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("bottom", val)
     }
 
     // =========================================================================
@@ -1700,15 +1726,8 @@ impl JsStyle {
     /// style.justifyItems = AlignItems.Center;
     /// ```
     #[wasm_bindgen(setter, js_name = justifyItems)]
-    pub fn set_justify_items(&mut self, val: JsOptionAlignItems) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.justify_items = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            Some(unsafe { std::mem::transmute::<u8, JsAlignItems>(n as u8) }.into())
-        } else {
-            None
-        };
+    pub fn set_justify_items(&mut self, val: JsOptionAlignItems) -> Result<(), JsValue> {
+        self.assign("justifyItems", val.into())
     }
 
     /// Gets the justify-self property
@@ -1734,19 +1753,8 @@ impl JsStyle {
     /// style.justifySelf = AlignSelf.End;
     /// ```
     #[wasm_bindgen(setter, js_name = justifySelf)]
-    pub fn set_justify_self(&mut self, val: JsOptionAlignSelf) {
-        let val: JsValue = val.unchecked_into();
-        self.inner.justify_self = if val.is_undefined() {
-            None
-        } else if let Some(n) = val.as_f64() {
-            let js_val = unsafe { std::mem::transmute::<u8, JsAlignSelf>(n as u8) };
-            match js_val {
-                JsAlignSelf::Auto => None,
-                _ => Some(js_val.into()),
-            }
-        } else {
-            None
-        };
+    pub fn set_justify_self(&mut self, val: JsOptionAlignSelf) -> Result<(), JsValue> {
+        self.assign("justifySelf", val.into())
     }
 
     // =========================================================================
@@ -1806,11 +1814,8 @@ impl JsStyle {
     /// style.gridRow = { start: 2, end: { span: 2 } };
     /// ```
     #[wasm_bindgen(setter, js_name = gridRow)]
-    pub fn set_grid_row(&mut self, val: JsLineGridPlacement) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<LineGridPlacementDto>(val) {
-            self.inner.grid_row = dto.into();
-        }
+    pub fn set_grid_row(&mut self, val: JsLineGridPlacement) -> Result<(), JsValue> {
+        self.assign("gridRow", val.into())
     }
 
     /// Gets the grid-column property
@@ -1839,11 +1844,8 @@ impl JsStyle {
     /// style.gridColumn = { start: "auto", end: { span: 3 } };
     /// ```
     #[wasm_bindgen(setter, js_name = gridColumn)]
-    pub fn set_grid_column(&mut self, val: JsLineGridPlacement) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<LineGridPlacementDto>(val) {
-            self.inner.grid_column = dto.into();
-        }
+    pub fn set_grid_column(&mut self, val: JsLineGridPlacement) -> Result<(), JsValue> {
+        self.assign("gridColumn", val.into())
     }
 
     /// Gets the grid-row-start property
@@ -1868,11 +1870,8 @@ impl JsStyle {
     /// style.gridRowStart = { span: 2 };
     /// ```
     #[wasm_bindgen(setter, js_name = gridRowStart)]
-    pub fn set_grid_row_start(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(val) {
-            self.inner.grid_row.start = dto.into();
-        }
+    pub fn set_grid_row_start(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("gridRowStart", val)
     }
 
     /// Gets the grid-row-end property
@@ -1897,11 +1896,8 @@ impl JsStyle {
     /// style.gridRowEnd = { span: 2 };
     /// ```
     #[wasm_bindgen(setter, js_name = gridRowEnd)]
-    pub fn set_grid_row_end(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(val) {
-            self.inner.grid_row.end = dto.into();
-        }
+    pub fn set_grid_row_end(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("gridRowEnd", val)
     }
 
     /// Gets the grid-column-start property
@@ -1926,11 +1922,8 @@ impl JsStyle {
     /// style.gridColumnStart = { span: 2 };
     /// ```
     #[wasm_bindgen(setter, js_name = gridColumnStart)]
-    pub fn set_grid_column_start(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(val) {
-            self.inner.grid_column.start = dto.into();
-        }
+    pub fn set_grid_column_start(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("gridColumnStart", val)
     }
 
     /// Gets the grid-column-end property
@@ -1955,11 +1948,8 @@ impl JsStyle {
     /// style.gridColumnEnd = { span: 3 };
     /// ```
     #[wasm_bindgen(setter, js_name = gridColumnEnd)]
-    pub fn set_grid_column_end(&mut self, val: JsValue) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(val) {
-            self.inner.grid_column.end = dto.into();
-        }
+    pub fn set_grid_column_end(&mut self, val: JsValue) -> Result<(), JsValue> {
+        self.assign("gridColumnEnd", val)
     }
 
     /// Gets the grid-template-rows property
@@ -1983,11 +1973,8 @@ impl JsStyle {
     ///
     /// @param val - An array of GridTrack objects
     #[wasm_bindgen(setter, js_name = gridTemplateRows)]
-    pub fn set_grid_template_rows(&mut self, val: JsGridTemplateComponents) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(tracks) = serde_wasm_bindgen::from_value::<Vec<GridTemplateComponentDto>>(val) {
-            self.inner.grid_template_rows = tracks.into_iter().map(|t| t.into()).collect();
-        }
+    pub fn set_grid_template_rows(&mut self, val: JsGridTemplateComponents) -> Result<(), JsValue> {
+        self.assign("gridTemplateRows", val.into())
     }
 
     /// Gets the grid-template-columns property
@@ -2022,11 +2009,11 @@ impl JsStyle {
     /// ];
     /// ```
     #[wasm_bindgen(setter, js_name = gridTemplateColumns)]
-    pub fn set_grid_template_columns(&mut self, val: JsGridTemplateComponents) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(tracks) = serde_wasm_bindgen::from_value::<Vec<GridTemplateComponentDto>>(val) {
-            self.inner.grid_template_columns = tracks.into_iter().map(|t| t.into()).collect();
-        }
+    pub fn set_grid_template_columns(
+        &mut self,
+        val: JsGridTemplateComponents,
+    ) -> Result<(), JsValue> {
+        self.assign("gridTemplateColumns", val.into())
     }
 
     /// Gets the grid-auto-rows property
@@ -2057,11 +2044,8 @@ impl JsStyle {
     /// style.gridAutoRows = [{ min: "auto", max: "auto" }];
     /// ```
     #[wasm_bindgen(setter, js_name = gridAutoRows)]
-    pub fn set_grid_auto_rows(&mut self, val: JsTrackSizingFunctions) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(tracks) = serde_wasm_bindgen::from_value::<Vec<TrackSizingFunctionDto>>(val) {
-            self.inner.grid_auto_rows = tracks.into_iter().map(|t| t.into()).collect();
-        }
+    pub fn set_grid_auto_rows(&mut self, val: JsTrackSizingFunctions) -> Result<(), JsValue> {
+        self.assign("gridAutoRows", val.into())
     }
 
     /// Gets the grid-auto-columns property
@@ -2085,11 +2069,8 @@ impl JsStyle {
     ///
     /// @param val - An array of track sizing functions for implicit columns
     #[wasm_bindgen(setter, js_name = gridAutoColumns)]
-    pub fn set_grid_auto_columns(&mut self, val: JsTrackSizingFunctions) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(tracks) = serde_wasm_bindgen::from_value::<Vec<TrackSizingFunctionDto>>(val) {
-            self.inner.grid_auto_columns = tracks.into_iter().map(|t| t.into()).collect();
-        }
+    pub fn set_grid_auto_columns(&mut self, val: JsTrackSizingFunctions) -> Result<(), JsValue> {
+        self.assign("gridAutoColumns", val.into())
     }
 
     /// Gets the grid-template-areas property
@@ -2123,13 +2104,8 @@ impl JsStyle {
     /// ];
     /// ```
     #[wasm_bindgen(setter, js_name = gridTemplateAreas)]
-    pub fn set_grid_template_areas(&mut self, val: JsGridTemplateAreas) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(areas) =
-            serde_wasm_bindgen::from_value::<Vec<crate::types::GridTemplateAreaDto>>(val)
-        {
-            self.inner.grid_template_areas = areas.into_iter().map(|a| a.into()).collect();
-        }
+    pub fn set_grid_template_areas(&mut self, val: JsGridTemplateAreas) -> Result<(), JsValue> {
+        self.assign("gridTemplateAreas", val.into())
     }
 
     /// Gets the grid-template-row-names property
@@ -2162,14 +2138,8 @@ impl JsStyle {
     /// style.gridTemplateRowNames = [["header-start"], ["header-end", "main-start"], ["main-end"]];
     /// ```
     #[wasm_bindgen(setter, js_name = gridTemplateRowNames)]
-    pub fn set_grid_template_row_names(&mut self, val: JsGridLineNames) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(names) = serde_wasm_bindgen::from_value::<Vec<Vec<String>>>(val) {
-            self.inner.grid_template_row_names = names
-                .into_iter()
-                .map(|v| v.into_iter().map(|s| s.into()).collect())
-                .collect();
-        }
+    pub fn set_grid_template_row_names(&mut self, val: JsGridLineNames) -> Result<(), JsValue> {
+        self.assign("gridTemplateRowNames", val.into())
     }
 
     /// Gets the grid-template-column-names property
@@ -2202,14 +2172,55 @@ impl JsStyle {
     /// style.gridTemplateColumnNames = [["sidebar-start"], ["sidebar-end", "main-start"], ["main-end"]];
     /// ```
     #[wasm_bindgen(setter, js_name = gridTemplateColumnNames)]
-    pub fn set_grid_template_column_names(&mut self, val: JsGridLineNames) {
-        let val: JsValue = val.unchecked_into();
-        if let Ok(names) = serde_wasm_bindgen::from_value::<Vec<Vec<String>>>(val) {
-            self.inner.grid_template_column_names = names
-                .into_iter()
-                .map(|v| v.into_iter().map(|s| s.into()).collect())
-                .collect();
-        }
+    pub fn set_grid_template_column_names(&mut self, val: JsGridLineNames) -> Result<(), JsValue> {
+        self.assign("gridTemplateColumnNames", val.into())
+    }
+
+    // =========================================================================
+    // Comparison
+    // =========================================================================
+
+    /// Checks whether two styles have the same value for every property
+    ///
+    /// @param other - The style to compare with
+    ///
+    /// @returns - `true` if no property differs
+    ///
+    /// @example
+    /// ```typescript
+    /// const a = new Style({ display: Display.Grid, width: 100 });
+    /// const b = Style.fromCss("display: grid; width: 100px");
+    /// console.log(a.equals(b)); // true
+    /// ```
+    #[wasm_bindgen(js_name = equals)]
+    pub fn equals(&self, other: &JsStyle) -> bool {
+        self.inner == other.inner
+    }
+
+    /// Lists the properties whose values differ between two styles
+    ///
+    /// Keys are reported at the granularity `toSnapshot()` uses: `size` rather
+    /// than `width`/`height`, `margin` rather than `marginLeft`, and so on.
+    ///
+    /// @param other - The style to compare with
+    ///
+    /// @returns - The differing property keys, in declaration order; empty if
+    /// the styles are equal
+    ///
+    /// @example
+    /// ```typescript
+    /// const a = new Style({ display: Display.Grid, width: 100 });
+    /// const b = new Style({ display: Display.Grid, width: 200, flexGrow: 1 });
+    /// console.log(a.diff(b)); // ["flexGrow", "size"]
+    /// ```
+    #[wasm_bindgen(js_name = diff)]
+    pub fn diff(&self, other: &JsStyle) -> JsStylePropertyArray {
+        STYLE_PROPERTY_KEYS
+            .iter()
+            .filter(|&&key| !self.property_eq(other, key))
+            .map(|&key| JsValue::from_str(key))
+            .collect::<js_sys::Array>()
+            .unchecked_into()
     }
 
     // =========================================================================
@@ -2241,79 +2252,92 @@ impl JsStyle {
     /// const [display, width, margin] = style.get("display", "width", "marginLeft");
     /// ```
     #[wasm_bindgen(variadic, skip_typescript)]
-    pub fn get(&self, keys: Vec<String>) -> JsValue {
+    pub fn get(&self, keys: Vec<String>) -> Result<JsValue, JsValue> {
         if keys.is_empty() {
-            return JsValue::UNDEFINED;
+            return Ok(JsValue::UNDEFINED);
         }
 
-        let results: Vec<JsValue> = keys.iter().map(|key| self.get_property(key)).collect();
+        let results = keys
+            .iter()
+            .map(|key| self.get_property(key))
+            .collect::<Result<Vec<JsValue>, JsValue>>()?;
 
         if results.len() == 1 {
-            results.into_iter().next().unwrap()
+            Ok(results.into_iter().next().unwrap())
         } else {
-            js_sys::Array::from_iter(results).into()
+            Ok(js_sys::Array::from_iter(results).into())
         }
     }
 
     /// Internal helper to get a property value by its key
     ///
     /// @throws Error if the key is unknown.
-    fn get_property(&self, path: &str) -> JsValue {
-        match path {
+    pub(crate) fn get_property(&self, path: &str) -> Result<JsValue, JsValue> {
+        JsStyle::read_property(&self.inner, path)
+    }
+
+    /// Reads a property value by its key from a native style
+    ///
+    /// Lets callers holding a `taffy::Style`, such as the tree's stored node
+    /// styles, read properties without wrapping a copy in a `JsStyle`.
+    ///
+    /// @throws Error if the key is unknown.
+    pub(crate) fn read_property(style: &TaffyStyle::Style, path: &str) -> Result<JsValue, JsValue> {
+        let value = match path {
             // Layout Mode
-            "display" => JsValue::from(self.inner.display as u8),
-            "position" => JsValue::from(self.inner.position as u8),
-            "boxSizing" => JsValue::from(self.inner.box_sizing as u8),
+            "display" => JsValue::from(style.display as u8),
+            "position" => JsValue::from(style.position as u8),
+            "boxSizing" => JsValue::from(style.box_sizing as u8),
 
             // Overflow
             "overflow" => {
                 let s = PointOverflowDto {
-                    x: self.inner.overflow.x as u8,
-                    y: self.inner.overflow.y as u8,
+                    x: style.overflow.x as u8,
+                    y: style.overflow.y as u8,
                 };
                 serialize(&s)
             }
-            "overflowX" => JsValue::from(self.inner.overflow.x as u8),
-            "overflowY" => JsValue::from(self.inner.overflow.y as u8),
+            "overflowX" => JsValue::from(style.overflow.x as u8),
+            "overflowY" => JsValue::from(style.overflow.y as u8),
 
             // Flexbox
-            "flexDirection" => JsValue::from(self.inner.flex_direction as u8),
-            "flexWrap" => JsValue::from(self.inner.flex_wrap as u8),
-            "flexGrow" => JsValue::from(self.inner.flex_grow),
-            "flexShrink" => JsValue::from(self.inner.flex_shrink),
+            "flexDirection" => JsValue::from(style.flex_direction as u8),
+            "flexWrap" => JsValue::from(style.flex_wrap as u8),
+            "flexGrow" => JsValue::from(style.flex_grow),
+            "flexShrink" => JsValue::from(style.flex_shrink),
             "flexBasis" => {
-                let d: DimensionDto = self.inner.flex_basis.into();
+                let d: DimensionDto = style.flex_basis.into();
                 serialize(&d)
             }
 
             // Alignment
-            "alignItems" => match self.inner.align_items {
+            "alignItems" => match style.align_items {
                 Some(v) => JsValue::from(v as u8),
                 None => JsValue::UNDEFINED,
             },
-            "alignSelf" => match self.inner.align_self {
-                Some(v) => JsValue::from(v as u8),
+            "alignSelf" => match style.align_self {
+                Some(v) => JsValue::from(JsAlignSelf::from(v) as u8),
                 None => JsValue::UNDEFINED,
             },
-            "alignContent" => match self.inner.align_content {
+            "alignContent" => match style.align_content {
                 Some(v) => JsValue::from(v as u8),
                 None => JsValue::UNDEFINED,
             },
-            "justifyContent" => match self.inner.justify_content {
+            "justifyContent" => match style.justify_content {
                 Some(v) => JsValue::from(v as u8),
                 None => JsValue::UNDEFINED,
             },
-            "justifyItems" => match self.inner.justify_items {
+            "justifyItems" => match style.justify_items {
                 Some(v) => JsValue::from(v as u8),
                 None => JsValue::UNDEFINED,
             },
-            "justifySelf" => match self.inner.justify_self {
-                Some(v) => JsValue::from(v as u8),
+            "justifySelf" => match style.justify_self {
+                Some(v) => JsValue::from(JsAlignSelf::from(v) as u8),
                 None => JsValue::UNDEFINED,
             },
 
             // Sizing - aspectRatio
-            "aspectRatio" => match self.inner.aspect_ratio {
+            "aspectRatio" => match style.aspect_ratio {
                 Some(v) => JsValue::from(v),
                 None => JsValue::UNDEFINED,
             },
@@ -2321,217 +2345,216 @@ impl JsStyle {
             // Sizing - size
             "size" => {
                 let s: SizeDto<DimensionDto> = SizeDto {
-                    width: self.inner.size.width.into(),
-                    height: self.inner.size.height.into(),
+                    width: style.size.width.into(),
+                    height: style.size.height.into(),
                 };
                 serialize(&s)
             }
             "width" => {
-                let d: DimensionDto = self.inner.size.width.into();
+                let d: DimensionDto = style.size.width.into();
                 serialize(&d)
             }
             "height" => {
-                let d: DimensionDto = self.inner.size.height.into();
+                let d: DimensionDto = style.size.height.into();
                 serialize(&d)
             }
 
             // Sizing - minSize
             "minSize" => {
                 let s: SizeDto<DimensionDto> = SizeDto {
-                    width: self.inner.min_size.width.into(),
-                    height: self.inner.min_size.height.into(),
+                    width: style.min_size.width.into(),
+                    height: style.min_size.height.into(),
                 };
                 serialize(&s)
             }
             "minWidth" => {
-                let d: DimensionDto = self.inner.min_size.width.into();
+                let d: DimensionDto = style.min_size.width.into();
                 serialize(&d)
             }
             "minHeight" => {
-                let d: DimensionDto = self.inner.min_size.height.into();
+                let d: DimensionDto = style.min_size.height.into();
                 serialize(&d)
             }
 
             // Sizing - maxSize
             "maxSize" => {
                 let s: SizeDto<DimensionDto> = SizeDto {
-                    width: self.inner.max_size.width.into(),
-                    height: self.inner.max_size.height.into(),
+                    width: style.max_size.width.into(),
+                    height: style.max_size.height.into(),
                 };
                 serialize(&s)
             }
             "maxWidth" => {
-                let d: DimensionDto = self.inner.max_size.width.into();
+                let d: DimensionDto = style.max_size.width.into();
                 serialize(&d)
             }
             "maxHeight" => {
-                let d: DimensionDto = self.inner.max_size.height.into();
+                let d: DimensionDto = style.max_size.height.into();
                 serialize(&d)
             }
 
             // Spacing - margin
             "margin" => {
                 let r: RectDto<LengthPercentageAutoDto> = RectDto {
-                    left: self.inner.margin.left.into(),
-                    right: self.inner.margin.right.into(),
-                    top: self.inner.margin.top.into(),
-                    bottom: self.inner.margin.bottom.into(),
+                    left: style.margin.left.into(),
+                    right: style.margin.right.into(),
+                    top: style.margin.top.into(),
+                    bottom: style.margin.bottom.into(),
                 };
                 serialize(&r)
             }
             "marginLeft" => {
-                let d: LengthPercentageAutoDto = self.inner.margin.left.into();
+                let d: LengthPercentageAutoDto = style.margin.left.into();
                 serialize(&d)
             }
             "marginRight" => {
-                let d: LengthPercentageAutoDto = self.inner.margin.right.into();
+                let d: LengthPercentageAutoDto = style.margin.right.into();
                 serialize(&d)
             }
             "marginTop" => {
-                let d: LengthPercentageAutoDto = self.inner.margin.top.into();
+                let d: LengthPercentageAutoDto = style.margin.top.into();
                 serialize(&d)
             }
             "marginBottom" => {
-                let d: LengthPercentageAutoDto = self.inner.margin.bottom.into();
+                let d: LengthPercentageAutoDto = style.margin.bottom.into();
                 serialize(&d)
             }
 
             // Spacing - padding
             "padding" => {
                 let r: RectDto<LengthPercentageDto> = RectDto {
-                    left: self.inner.padding.left.into(),
-                    right: self.inner.padding.right.into(),
-                    top: self.inner.padding.top.into(),
-                    bottom: self.inner.padding.bottom.into(),
+                    left: style.padding.left.into(),
+                    right: style.padding.right.into(),
+                    top: style.padding.top.into(),
+                    bottom: style.padding.bottom.into(),
                 };
                 serialize(&r)
             }
             "paddingLeft" => {
-                let d: LengthPercentageDto = self.inner.padding.left.into();
+                let d: LengthPercentageDto = style.padding.left.into();
                 serialize(&d)
             }
             "paddingRight" => {
-                let d: LengthPercentageDto = self.inner.padding.right.into();
+                let d: LengthPercentageDto = style.padding.right.into();
                 serialize(&d)
             }
             "paddingTop" => {
-                let d: LengthPercentageDto = self.inner.padding.top.into();
+                let d: LengthPercentageDto = style.padding.top.into();
                 serialize(&d)
             }
             "paddingBottom" => {
-                let d: LengthPercentageDto = self.inner.padding.bottom.into();
+                let d: LengthPercentageDto = style.padding.bottom.into();
                 serialize(&d)
             }
 
             // Spacing - border
             "border" => {
                 let r: RectDto<LengthPercentageDto> = RectDto {
-                    left: self.inner.border.left.into(),
-                    right: self.inner.border.right.into(),
-                    top: self.inner.border.top.into(),
-                    bottom: self.inner.border.bottom.into(),
+                    left: style.border.left.into(),
+                    right: style.border.right.into(),
+                    top: style.border.top.into(),
+                    bottom: style.border.bottom.into(),
                 };
                 serialize(&r)
             }
             "borderLeft" => {
-                let d: LengthPercentageDto = self.inner.border.left.into();
+                let d: LengthPercentageDto = style.border.left.into();
                 serialize(&d)
             }
             "borderRight" => {
-                let d: LengthPercentageDto = self.inner.border.right.into();
+                let d: LengthPercentageDto = style.border.right.into();
                 serialize(&d)
             }
             "borderTop" => {
-                let d: LengthPercentageDto = self.inner.border.top.into();
+                let d: LengthPercentageDto = style.border.top.into();
                 serialize(&d)
             }
             "borderBottom" => {
-                let d: LengthPercentageDto = self.inner.border.bottom.into();
+                let d: LengthPercentageDto = style.border.bottom.into();
                 serialize(&d)
             }
 
             // Spacing - inset
             "inset" => {
                 let r: RectDto<LengthPercentageAutoDto> = RectDto {
-                    left: self.inner.inset.left.into(),
-                    right: self.inner.inset.right.into(),
-                    top: self.inner.inset.top.into(),
-                    bottom: self.inner.inset.bottom.into(),
+                    left: style.inset.left.into(),
+                    right: style.inset.right.into(),
+                    top: style.inset.top.into(),
+                    bottom: style.inset.bottom.into(),
                 };
                 serialize(&r)
             }
             "left" => {
-                let d: LengthPercentageAutoDto = self.inner.inset.left.into();
+                let d: LengthPercentageAutoDto = style.inset.left.into();
                 serialize(&d)
             }
             "right" => {
-                let d: LengthPercentageAutoDto = self.inner.inset.right.into();
+                let d: LengthPercentageAutoDto = style.inset.right.into();
                 serialize(&d)
             }
             "top" => {
-                let d: LengthPercentageAutoDto = self.inner.inset.top.into();
+                let d: LengthPercentageAutoDto = style.inset.top.into();
                 serialize(&d)
             }
             "bottom" => {
-                let d: LengthPercentageAutoDto = self.inner.inset.bottom.into();
+                let d: LengthPercentageAutoDto = style.inset.bottom.into();
                 serialize(&d)
             }
 
             // Spacing - gap
             "gap" => {
                 let s: SizeDto<LengthPercentageDto> = SizeDto {
-                    width: self.inner.gap.width.into(),
-                    height: self.inner.gap.height.into(),
+                    width: style.gap.width.into(),
+                    height: style.gap.height.into(),
                 };
                 serialize(&s)
             }
             "columnGap" => {
-                let d: LengthPercentageDto = self.inner.gap.width.into();
+                let d: LengthPercentageDto = style.gap.width.into();
                 serialize(&d)
             }
             "rowGap" => {
-                let d: LengthPercentageDto = self.inner.gap.height.into();
+                let d: LengthPercentageDto = style.gap.height.into();
                 serialize(&d)
             }
 
             // Block layout
-            "itemIsTable" => JsValue::from(self.inner.item_is_table),
-            "itemIsReplaced" => JsValue::from(self.inner.item_is_replaced),
-            "scrollbarWidth" => JsValue::from(self.inner.scrollbar_width),
-            "textAlign" => JsValue::from(self.inner.text_align as u8),
+            "itemIsTable" => JsValue::from(style.item_is_table),
+            "itemIsReplaced" => JsValue::from(style.item_is_replaced),
+            "scrollbarWidth" => JsValue::from(style.scrollbar_width),
+            "textAlign" => JsValue::from(style.text_align as u8),
 
             // Grid layout
-            "gridAutoFlow" => JsValue::from(self.inner.grid_auto_flow as u8),
+            "gridAutoFlow" => JsValue::from(style.grid_auto_flow as u8),
 
             "gridRow" => {
-                let dto: LineGridPlacementDto = self.inner.grid_row.clone().into();
+                let dto: LineGridPlacementDto = style.grid_row.clone().into();
                 serialize(&dto)
             }
             "gridRowStart" => {
-                let dto: GridPlacementDto = self.inner.grid_row.start.clone().into();
+                let dto: GridPlacementDto = style.grid_row.start.clone().into();
                 serialize(&dto)
             }
             "gridRowEnd" => {
-                let dto: GridPlacementDto = self.inner.grid_row.end.clone().into();
+                let dto: GridPlacementDto = style.grid_row.end.clone().into();
                 serialize(&dto)
             }
 
             "gridColumn" => {
-                let dto: LineGridPlacementDto = self.inner.grid_column.clone().into();
+                let dto: LineGridPlacementDto = style.grid_column.clone().into();
                 serialize(&dto)
             }
             "gridColumnStart" => {
-                let dto: GridPlacementDto = self.inner.grid_column.start.clone().into();
+                let dto: GridPlacementDto = style.grid_column.start.clone().into();
                 serialize(&dto)
             }
             "gridColumnEnd" => {
-                let dto: GridPlacementDto = self.inner.grid_column.end.clone().into();
+                let dto: GridPlacementDto = style.grid_column.end.clone().into();
                 serialize(&dto)
             }
 
             "gridTemplateRows" => {
-                let tracks: Vec<GridTemplateComponentDto> = self
-                    .inner
+                let tracks: Vec<GridTemplateComponentDto> = style
                     .grid_template_rows
                     .iter()
                     .cloned()
@@ -2541,8 +2564,7 @@ impl JsStyle {
             }
 
             "gridTemplateColumns" => {
-                let tracks: Vec<GridTemplateComponentDto> = self
-                    .inner
+                let tracks: Vec<GridTemplateComponentDto> = style
                     .grid_template_columns
                     .iter()
                     .cloned()
@@ -2552,8 +2574,7 @@ impl JsStyle {
             }
 
             "gridAutoRows" => {
-                let tracks: Vec<TrackSizingFunctionDto> = self
-                    .inner
+                let tracks: Vec<TrackSizingFunctionDto> = style
                     .grid_auto_rows
                     .iter()
                     .cloned()
@@ -2563,8 +2584,7 @@ impl JsStyle {
             }
 
             "gridAutoColumns" => {
-                let tracks: Vec<TrackSizingFunctionDto> = self
-                    .inner
+                let tracks: Vec<TrackSizingFunctionDto> = style
                     .grid_auto_columns
                     .iter()
                     .cloned()
@@ -2574,8 +2594,7 @@ impl JsStyle {
             }
 
             "gridTemplateAreas" => {
-                let areas: Vec<crate::types::GridTemplateAreaDto> = self
-                    .inner
+                let areas: Vec<crate::types::GridTemplateAreaDto> = style
                     .grid_template_areas
                     .iter()
                     .cloned()
@@ -2585,8 +2604,7 @@ impl JsStyle {
             }
 
             "gridTemplateRowNames" => {
-                let names: Vec<Vec<String>> = self
-                    .inner
+                let names: Vec<Vec<String>> = style
                     .grid_template_row_names
                     .iter()
                     .map(|v| {
@@ -2599,8 +2617,7 @@ impl JsStyle {
             }
 
             "gridTemplateColumnNames" => {
-                let names: Vec<Vec<String>> = self
-                    .inner
+                let names: Vec<Vec<String>> = style
                     .grid_template_column_names
                     .iter()
                     .map(|v| {
@@ -2614,9 +2631,11 @@ impl JsStyle {
 
             // Unknown property path
             _ => {
-                wasm_bindgen::throw_str(&format!("Unknown property path: {}", path));
+                let message = format!("Unknown property path: {}", path);
+                return Err(js_sys::Error::new(&message).into());
             }
-        }
+        };
+        Ok(value)
     }
 
     // =========================================================================
@@ -2625,11 +2644,17 @@ impl JsStyle {
 
     /// Sets multiple style properties in a single WASM call.
     ///
-    /// Accepts an object where keys are property keys.
+    /// Accepts an object where keys are property keys. Values that do not match
+    /// their property's type are skipped and recorded as warnings (see
+    /// `takeWarnings()`), unless strict validation applies.
     ///
     /// @throws Error if any property key is unknown.
+    /// @throws `TaffyError` with code `InvalidStyle` in strict mode if `props` is
+    /// not an object, or if any key or value is invalid. The error lists every
+    /// problem and no property is changed.
     ///
     /// @param props - Object with property keys as keys and values to set
+    /// @param strict - Overrides the global strict mode for this call
     ///
     /// @example
     /// ```typescript
@@ -2646,12 +2671,23 @@ impl JsStyle {
     /// });
     /// ```
     #[wasm_bindgen(skip_typescript)]
-    pub fn set(&mut self, props: JsValue) {
+    pub fn set(&mut self, props: JsValue, strict: Option<bool>) -> Result<(), JsValue> {
+        let strict = strict.unwrap_or_else(strict_mode);
         if !props.is_object() {
+            if strict {
+                let issue = format!(
+                    "{} is not an object of style properties",
+                    describe_value(&props)
+                );
+                return Err(JsTaffyError::invalid_style(vec![issue]).into());
+            }
             log("set() requires an object argument");
-            return;
+            return Ok(());
         }
 
+        let previous = strict.then(|| self.inner.clone());
+        let mut issues = Vec::new();
+
         let obj = js_sys::Object::from(props);
         let entries = js_sys::Object::entries(&obj);
 
@@ -2662,460 +2698,682 @@ impl JsStyle {
                 let key = arr.get(0);
                 let value = arr.get(1);
                 if let Some(key_str) = key.as_string() {
-                    self.set_property(&key_str, value);
+                    match self.set_property(&key_str, value) {
+                        Ok(()) => {}
+                        Err(issue) if strict => issues.push(issue.to_string()),
+                        Err(StyleIssue::UnknownProperty(path)) => {
+                            let message = format!("Unknown property path for set: {}", path);
+                            return Err(js_sys::Error::new(&message).into());
+                        }
+                        Err(issue) => self.warnings.push(issue.to_string()),
+                    }
                 }
             }
         }
+
+        match previous {
+            Some(previous) if !issues.is_empty() => {
+                self.inner = previous;
+                Err(JsTaffyError::invalid_style(issues).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies `props` to a copy of a native style, with the same key and
+    /// value handling as `set()`
+    ///
+    /// Each property is first decoded into a default style and compared with
+    /// `style` on its own, so `style` is only copied if a property changes.
+    ///
+    /// @returns - The updated style, or `None` if no property changed, and
+    /// the warnings for skipped values
+    ///
+    /// @throws Error if any property key is unknown.
+    /// @throws `TaffyError` with code `InvalidStyle` in strict mode if `props` is
+    /// not an object, or if any key or value is invalid.
+    pub(crate) fn update(
+        style: &TaffyStyle::Style,
+        props: JsValue,
+        strict: Option<bool>,
+    ) -> Result<(Option<TaffyStyle::Style>, Vec<String>), JsValue> {
+        let strict = strict.unwrap_or_else(strict_mode);
+        let mut warnings = Vec::new();
+        if !props.is_object() {
+            let issue = format!(
+                "{} is not an object of style properties",
+                describe_value(&props)
+            );
+            if strict {
+                return Err(JsTaffyError::invalid_style(vec![issue]).into());
+            }
+            warnings.push(issue);
+            return Ok((None, warnings));
+        }
+
+        let mut scratch = JsStyle::from(TaffyStyle::Style::default());
+        let mut valid = Vec::new();
+        let mut changed = Vec::new();
+        let mut issues = Vec::new();
+
+        let entries = js_sys::Object::entries(&js_sys::Object::from(props));
+        for entry in entries.iter() {
+            let entry = js_sys::Array::from(&entry);
+            let Some(key) = entry.get(0).as_string() else {
+                continue;
+            };
+            let value = entry.get(1);
+            match scratch.set_property(&key, value.clone()) {
+                Ok(()) => {
+                    if !JsStyle::fields_eq(&scratch.inner, style, &key) {
+                        changed.push(key.clone());
+                    }
+                    valid.push((key, value));
+                }
+                Err(issue) if strict => issues.push(issue.to_string()),
+                Err(StyleIssue::UnknownProperty(path)) => {
+                    let message = format!("Unknown property path for set: {}", path);
+                    return Err(js_sys::Error::new(&message).into());
+                }
+                Err(issue) => warnings.push(issue.to_string()),
+            }
+        }
+        if !issues.is_empty() {
+            return Err(JsTaffyError::invalid_style(issues).into());
+        }
+        if changed.is_empty() {
+            return Ok((None, warnings));
+        }
+
+        // Properties are applied in order, as a later key can overwrite part
+        // of an earlier one (e.g. `width` and `size`)
+        let mut updated = JsStyle::from(style.clone());
+        for (key, value) in valid {
+            let _ = updated.set_property(&key, value);
+        }
+        let changed = changed
+            .iter()
+            .any(|key| !JsStyle::fields_eq(&updated.inner, style, key));
+        Ok((changed.then_some(updated.inner), warnings))
     }
 
     /// Internal helper to set a property value by its key
     ///
-    /// @throws Error if the key is unknown.
-    fn set_property(&mut self, path: &str, value: JsValue) {
+    /// Leaves the property unchanged and reports why if the key is unknown or
+    /// the value does not match the property's type.
+    pub(crate) fn set_property(&mut self, path: &str, value: JsValue) -> Result<(), StyleIssue> {
         match path {
             // Layout Mode
             "display" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.display =
-                        unsafe { std::mem::transmute::<u8, JsDisplay>(n as u8) }.into();
-                }
+                self.inner.display = enum_value::<JsDisplay>(path, &value)?.into();
             }
             "position" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.position =
-                        unsafe { std::mem::transmute::<u8, JsPosition>(n as u8) }.into();
-                }
+                self.inner.position = enum_value::<JsPosition>(path, &value)?.into();
             }
             "boxSizing" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.box_sizing =
-                        unsafe { std::mem::transmute::<u8, JsBoxSizing>(n as u8) }.into();
-                }
+                self.inner.box_sizing = enum_value::<JsBoxSizing>(path, &value)?.into();
             }
 
             // Overflow
             "overflow" => {
-                if let Ok(s) = serde_wasm_bindgen::from_value::<PointOverflowDto>(value) {
-                    self.inner.overflow = s.into();
-                }
+                let p: PointOverflowDto = decode(path, value, "Point<Overflow>")?;
+                self.inner.overflow = taffy::geometry::Point {
+                    x: enum_value::<JsOverflow>("overflow.x", &p.x.into())?.into(),
+                    y: enum_value::<JsOverflow>("overflow.y", &p.y.into())?.into(),
+                };
             }
             "overflowX" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.overflow.x =
-                        unsafe { std::mem::transmute::<u8, JsOverflow>(n as u8) }.into();
-                }
+                self.inner.overflow.x = enum_value::<JsOverflow>(path, &value)?.into();
             }
             "overflowY" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.overflow.y =
-                        unsafe { std::mem::transmute::<u8, JsOverflow>(n as u8) }.into();
-                }
+                self.inner.overflow.y = enum_value::<JsOverflow>(path, &value)?.into();
             }
 
             // Flexbox
             "flexDirection" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.flex_direction =
-                        unsafe { std::mem::transmute::<u8, JsFlexDirection>(n as u8) }.into();
-                }
+                self.inner.flex_direction = enum_value::<JsFlexDirection>(path, &value)?.into();
             }
             "flexWrap" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.flex_wrap =
-                        unsafe { std::mem::transmute::<u8, JsFlexWrap>(n as u8) }.into();
-                }
+                self.inner.flex_wrap = enum_value::<JsFlexWrap>(path, &value)?.into();
             }
             "flexGrow" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.flex_grow = n as f32;
-                }
+                self.inner.flex_grow = number(path, &value)?;
             }
             "flexShrink" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.flex_shrink = n as f32;
-                }
+                self.inner.flex_shrink = number(path, &value)?;
             }
             "flexBasis" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.flex_basis = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.flex_basis = d.into();
             }
 
             // Alignment
             "alignItems" => {
-                if value.is_undefined() {
-                    self.inner.align_items = None;
-                } else if let Some(n) = value.as_f64() {
-                    self.inner.align_items =
-                        Some(unsafe { std::mem::transmute::<u8, JsAlignItems>(n as u8) }.into());
-                }
+                self.inner.align_items = if value.is_undefined() {
+                    None
+                } else {
+                    Some(enum_value::<JsAlignItems>(path, &value)?.into())
+                };
             }
             "alignSelf" => {
-                if value.is_undefined() {
-                    self.inner.align_self = None;
-                } else if let Some(n) = value.as_f64() {
-                    let js_val = unsafe { std::mem::transmute::<u8, JsAlignSelf>(n as u8) };
-                    self.inner.align_self = match js_val {
-                        JsAlignSelf::Auto => None,
-                        _ => Some(js_val.into()),
-                    };
-                }
+                self.inner.align_self = align_self_value(path, &value)?;
             }
             "alignContent" => {
-                if value.is_undefined() {
-                    self.inner.align_content = None;
-                } else if let Some(n) = value.as_f64() {
-                    self.inner.align_content =
-                        Some(unsafe { std::mem::transmute::<u8, JsAlignContent>(n as u8) }.into());
-                }
+                self.inner.align_content = if value.is_undefined() {
+                    None
+                } else {
+                    Some(enum_value::<JsAlignContent>(path, &value)?.into())
+                };
             }
             "justifyContent" => {
-                if value.is_undefined() {
-                    self.inner.justify_content = None;
-                } else if let Some(n) = value.as_f64() {
-                    self.inner.justify_content = Some(
-                        unsafe { std::mem::transmute::<u8, JsJustifyContent>(n as u8) }.into(),
-                    );
-                }
+                self.inner.justify_content = if value.is_undefined() {
+                    None
+                } else {
+                    Some(enum_value::<JsJustifyContent>(path, &value)?.into())
+                };
             }
             "justifyItems" => {
-                if value.is_undefined() {
-                    self.inner.justify_items = None;
-                } else if let Some(n) = value.as_f64() {
-                    self.inner.justify_items =
-                        Some(unsafe { std::mem::transmute::<u8, JsAlignItems>(n as u8) }.into());
-                }
+                self.inner.justify_items = if value.is_undefined() {
+                    None
+                } else {
+                    Some(enum_value::<JsAlignItems>(path, &value)?.into())
+                };
             }
             "justifySelf" => {
-                if value.is_undefined() {
-                    self.inner.justify_self = None;
-                } else if let Some(n) = value.as_f64() {
-                    let js_val = unsafe { std::mem::transmute::<u8, JsAlignSelf>(n as u8) };
-                    self.inner.justify_self = match js_val {
-                        JsAlignSelf::Auto => None,
-                        _ => Some(js_val.into()),
-                    };
-                }
+                self.inner.justify_self = align_self_value(path, &value)?;
             }
 
             // Sizing - aspectRatio
             "aspectRatio" => {
-                if value.is_undefined() || value.is_null() {
-                    self.inner.aspect_ratio = None;
-                } else if let Some(n) = value.as_f64() {
-                    self.inner.aspect_ratio = Some(n as f32);
-                }
+                self.inner.aspect_ratio = if value.is_undefined() || value.is_null() {
+                    None
+                } else {
+                    Some(number(path, &value)?)
+                };
             }
 
             // Sizing - size
             "size" => {
-                if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(value) {
-                    self.inner.size = s.into();
-                }
+                let s: SizeDto<DimensionDto> = decode(path, value, "Size<Dimension>")?;
+                self.inner.size = s.into();
             }
             "width" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.size.width = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.size.width = d.into();
             }
             "height" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.size.height = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.size.height = d.into();
             }
 
             // Sizing - minSize
             "minSize" => {
-                if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(value) {
-                    self.inner.min_size = s.into();
-                }
+                let s: SizeDto<DimensionDto> = decode(path, value, "Size<Dimension>")?;
+                self.inner.min_size = s.into();
             }
             "minWidth" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.min_size.width = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.min_size.width = d.into();
             }
             "minHeight" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.min_size.height = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.min_size.height = d.into();
             }
 
             // Sizing - maxSize
             "maxSize" => {
-                if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<DimensionDto>>(value) {
-                    self.inner.max_size = s.into();
-                }
+                let s: SizeDto<DimensionDto> = decode(path, value, "Size<Dimension>")?;
+                self.inner.max_size = s.into();
             }
             "maxWidth" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.max_size.width = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.max_size.width = d.into();
             }
             "maxHeight" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<DimensionDto>(value) {
-                    self.inner.max_size.height = d.into();
-                }
+                let d: DimensionDto = decode(path, value, "Dimension")?;
+                self.inner.max_size.height = d.into();
             }
 
             // Spacing - margin
             "margin" => {
-                if let Ok(r) =
-                    serde_wasm_bindgen::from_value::<RectDto<LengthPercentageAutoDto>>(value)
-                {
-                    self.inner.margin = r.into();
-                }
+                let r: RectDto<LengthPercentageAutoDto> =
+                    decode(path, value, "Rect<LengthPercentageAuto>")?;
+                self.inner.margin = r.into();
             }
             "marginLeft" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.margin.left = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.margin.left = d.into();
             }
             "marginRight" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.margin.right = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.margin.right = d.into();
             }
             "marginTop" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.margin.top = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.margin.top = d.into();
             }
             "marginBottom" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.margin.bottom = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.margin.bottom = d.into();
             }
 
             // Spacing - padding
             "padding" => {
-                if let Ok(r) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageDto>>(value)
-                {
-                    self.inner.padding = r.into();
-                }
+                let r: RectDto<LengthPercentageDto> =
+                    decode(path, value, "Rect<LengthPercentage>")?;
+                self.inner.padding = r.into();
             }
             "paddingLeft" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.padding.left = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.padding.left = d.into();
             }
             "paddingRight" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.padding.right = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.padding.right = d.into();
             }
             "paddingTop" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.padding.top = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.padding.top = d.into();
             }
             "paddingBottom" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.padding.bottom = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.padding.bottom = d.into();
             }
 
             // Spacing - border
             "border" => {
-                if let Ok(r) = serde_wasm_bindgen::from_value::<RectDto<LengthPercentageDto>>(value)
-                {
-                    self.inner.border = r.into();
-                }
+                let r: RectDto<LengthPercentageDto> =
+                    decode(path, value, "Rect<LengthPercentage>")?;
+                self.inner.border = r.into();
             }
             "borderLeft" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.border.left = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.border.left = d.into();
             }
             "borderRight" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.border.right = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.border.right = d.into();
             }
             "borderTop" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.border.top = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.border.top = d.into();
             }
             "borderBottom" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.border.bottom = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.border.bottom = d.into();
             }
 
             // Spacing - inset
             "inset" => {
-                if let Ok(r) =
-                    serde_wasm_bindgen::from_value::<RectDto<LengthPercentageAutoDto>>(value)
-                {
-                    self.inner.inset = r.into();
-                }
+                let r: RectDto<LengthPercentageAutoDto> =
+                    decode(path, value, "Rect<LengthPercentageAuto>")?;
+                self.inner.inset = r.into();
             }
             "left" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.inset.left = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.inset.left = d.into();
             }
             "right" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.inset.right = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.inset.right = d.into();
             }
             "top" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.inset.top = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.inset.top = d.into();
             }
             "bottom" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageAutoDto>(value) {
-                    self.inner.inset.bottom = d.into();
-                }
+                let d: LengthPercentageAutoDto = decode(path, value, "LengthPercentageAuto")?;
+                self.inner.inset.bottom = d.into();
             }
 
             // Spacing - gap
             "gap" => {
-                if let Ok(s) = serde_wasm_bindgen::from_value::<SizeDto<LengthPercentageDto>>(value)
-                {
-                    self.inner.gap = s.into();
-                }
+                let s: SizeDto<LengthPercentageDto> =
+                    decode(path, value, "Size<LengthPercentage>")?;
+                self.inner.gap = s.into();
             }
             "columnGap" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.gap.width = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.gap.width = d.into();
             }
             "rowGap" => {
-                if let Ok(d) = serde_wasm_bindgen::from_value::<LengthPercentageDto>(value) {
-                    self.inner.gap.height = d.into();
-                }
+                let d: LengthPercentageDto = decode(path, value, "LengthPercentage")?;
+                self.inner.gap.height = d.into();
             }
 
             // Block layout
             "itemIsTable" => {
-                if let Some(b) = value.as_bool() {
-                    self.inner.item_is_table = b;
-                }
+                self.inner.item_is_table = boolean(path, &value)?;
             }
             "itemIsReplaced" => {
-                if let Some(b) = value.as_bool() {
-                    self.inner.item_is_replaced = b;
-                }
+                self.inner.item_is_replaced = boolean(path, &value)?;
             }
             "scrollbarWidth" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.scrollbar_width = n as f32;
-                }
+                self.inner.scrollbar_width = number(path, &value)?;
             }
             "textAlign" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.text_align =
-                        unsafe { std::mem::transmute::<u8, JsTextAlign>(n as u8) }.into();
-                }
+                self.inner.text_align = enum_value::<JsTextAlign>(path, &value)?.into();
             }
 
             // Grid layout
             "gridAutoFlow" => {
-                if let Some(n) = value.as_f64() {
-                    self.inner.grid_auto_flow =
-                        unsafe { std::mem::transmute::<u8, JsGridAutoFlow>(n as u8) }.into();
-                }
+                self.inner.grid_auto_flow = enum_value::<JsGridAutoFlow>(path, &value)?.into();
             }
 
             "gridRow" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<LineGridPlacementDto>(value) {
-                    self.inner.grid_row = dto.into();
-                }
+                let dto: LineGridPlacementDto = decode(path, value, "Line<GridPlacement>")?;
+                self.inner.grid_row = dto.into();
             }
             "gridRowStart" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(value) {
-                    self.inner.grid_row.start = dto.into();
-                }
+                let dto: GridPlacementDto = decode(path, value, "GridPlacement")?;
+                self.inner.grid_row.start = dto.into();
             }
             "gridRowEnd" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(value) {
-                    self.inner.grid_row.end = dto.into();
-                }
+                let dto: GridPlacementDto = decode(path, value, "GridPlacement")?;
+                self.inner.grid_row.end = dto.into();
             }
 
             "gridColumn" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<LineGridPlacementDto>(value) {
-                    self.inner.grid_column = dto.into();
-                }
+                let dto: LineGridPlacementDto = decode(path, value, "Line<GridPlacement>")?;
+                self.inner.grid_column = dto.into();
             }
             "gridColumnStart" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(value) {
-                    self.inner.grid_column.start = dto.into();
-                }
+                let dto: GridPlacementDto = decode(path, value, "GridPlacement")?;
+                self.inner.grid_column.start = dto.into();
             }
             "gridColumnEnd" => {
-                if let Ok(dto) = serde_wasm_bindgen::from_value::<GridPlacementDto>(value) {
-                    self.inner.grid_column.end = dto.into();
-                }
+                let dto: GridPlacementDto = decode(path, value, "GridPlacement")?;
+                self.inner.grid_column.end = dto.into();
             }
 
             "gridTemplateRows" => {
-                if let Ok(tracks) =
-                    serde_wasm_bindgen::from_value::<Vec<GridTemplateComponentDto>>(value)
-                {
-                    self.inner.grid_template_rows = tracks.into_iter().map(|t| t.into()).collect();
-                }
+                let tracks: Vec<GridTemplateComponentDto> =
+                    decode(path, value, "GridTemplateComponent[]")?;
+                self.inner.grid_template_rows = tracks.into_iter().map(|t| t.into()).collect();
             }
 
             "gridTemplateColumns" => {
-                if let Ok(tracks) =
-                    serde_wasm_bindgen::from_value::<Vec<GridTemplateComponentDto>>(value)
-                {
-                    self.inner.grid_template_columns =
-                        tracks.into_iter().map(|t| t.into()).collect();
-                }
+                let tracks: Vec<GridTemplateComponentDto> =
+                    decode(path, value, "GridTemplateComponent[]")?;
+                self.inner.grid_template_columns = tracks.into_iter().map(|t| t.into()).collect();
             }
 
             "gridAutoRows" => {
-                if let Ok(tracks) =
-                    serde_wasm_bindgen::from_value::<Vec<TrackSizingFunctionDto>>(value)
-                {
-                    self.inner.grid_auto_rows = tracks.into_iter().map(|t| t.into()).collect();
-                }
+                let tracks: Vec<TrackSizingFunctionDto> =
+                    decode(path, value, "TrackSizingFunction[]")?;
+                self.inner.grid_auto_rows = tracks.into_iter().map(|t| t.into()).collect();
             }
 
             "gridAutoColumns" => {
-                if let Ok(tracks) =
-                    serde_wasm_bindgen::from_value::<Vec<TrackSizingFunctionDto>>(value)
-                {
-                    self.inner.grid_auto_columns = tracks.into_iter().map(|t| t.into()).collect();
-                }
+                let tracks: Vec<TrackSizingFunctionDto> =
+                    decode(path, value, "TrackSizingFunction[]")?;
+                self.inner.grid_auto_columns = tracks.into_iter().map(|t| t.into()).collect();
             }
 
             "gridTemplateAreas" => {
-                if let Ok(areas) =
-                    serde_wasm_bindgen::from_value::<Vec<crate::types::GridTemplateAreaDto>>(value)
-                {
-                    self.inner.grid_template_areas = areas.into_iter().map(|a| a.into()).collect();
-                }
+                let areas: Vec<crate::types::GridTemplateAreaDto> =
+                    decode(path, value, "GridTemplateArea[]")?;
+                self.inner.grid_template_areas = areas.into_iter().map(|a| a.into()).collect();
             }
 
             "gridTemplateRowNames" => {
-                if let Ok(names) = serde_wasm_bindgen::from_value::<Vec<Vec<String>>>(value) {
-                    self.inner.grid_template_row_names = names
-                        .into_iter()
-                        .map(|v| v.into_iter().map(|s| s.into()).collect())
-                        .collect();
-                }
+                let names: Vec<Vec<String>> = decode(path, value, "string[][]")?;
+                self.inner.grid_template_row_names = names;
             }
 
             "gridTemplateColumnNames" => {
-                if let Ok(names) = serde_wasm_bindgen::from_value::<Vec<Vec<String>>>(value) {
-                    self.inner.grid_template_column_names = names
-                        .into_iter()
-                        .map(|v| v.into_iter().map(|s| s.into()).collect())
-                        .collect();
-                }
+                let names: Vec<Vec<String>> = decode(path, value, "string[][]")?;
+                self.inner.grid_template_column_names = names;
+            }
+
+            // Unknown property path
+            _ => return Err(StyleIssue::UnknownProperty(path.to_string())),
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Property Keys and Comparison
+// =============================================================================
+
+/// Property keys that together cover every field of a style exactly once
+///
+/// These are the object-valued keys of `StyleProperty` (e.g. `size` rather
+/// than `width`/`height`), in declaration order. Reading each key with
+/// `get_property` and writing it back with `set_property` reproduces the style,
+/// and `diff()` reports changes in terms of these keys.
+pub(crate) const STYLE_PROPERTY_KEYS: &[&str] = &[
+    "display",
+    "position",
+    "boxSizing",
+    "overflow",
+    "flexDirection",
+    "flexWrap",
+    "flexGrow",
+    "flexShrink",
+    "flexBasis",
+    "alignItems",
+    "alignSelf",
+    "alignContent",
+    "justifyContent",
+    "justifyItems",
+    "justifySelf",
+    "aspectRatio",
+    "size",
+    "minSize",
+    "maxSize",
+    "margin",
+    "padding",
+    "border",
+    "inset",
+    "gap",
+    "itemIsTable",
+    "itemIsReplaced",
+    "scrollbarWidth",
+    "textAlign",
+    "gridAutoFlow",
+    "gridRow",
+    "gridColumn",
+    "gridTemplateRows",
+    "gridTemplateColumns",
+    "gridAutoRows",
+    "gridAutoColumns",
+    "gridTemplateAreas",
+    "gridTemplateRowNames",
+    "gridTemplateColumnNames",
+];
+
+impl JsStyle {
+    /// Compares a single property of two styles
+    ///
+    /// Accepts any `StyleProperty` key, including flat keys such as `width`.
+    ///
+    /// @throws Error if the key is unknown.
+    pub(crate) fn property_eq(&self, other: &JsStyle, path: &str) -> bool {
+        JsStyle::fields_eq(&self.inner, &other.inner, path)
+    }
+
+    /// Compares a single property of two native styles
+    ///
+    /// @throws Error if the key is unknown.
+    pub(crate) fn fields_eq(a: &TaffyStyle::Style, b: &TaffyStyle::Style, path: &str) -> bool {
+        match path {
+            "display" => a.display == b.display,
+            "position" => a.position == b.position,
+            "boxSizing" => a.box_sizing == b.box_sizing,
+            "overflow" => a.overflow == b.overflow,
+            "overflowX" => a.overflow.x == b.overflow.x,
+            "overflowY" => a.overflow.y == b.overflow.y,
+            "flexDirection" => a.flex_direction == b.flex_direction,
+            "flexWrap" => a.flex_wrap == b.flex_wrap,
+            "flexGrow" => a.flex_grow == b.flex_grow,
+            "flexShrink" => a.flex_shrink == b.flex_shrink,
+            "flexBasis" => a.flex_basis == b.flex_basis,
+            "alignItems" => a.align_items == b.align_items,
+            "alignSelf" => a.align_self == b.align_self,
+            "alignContent" => a.align_content == b.align_content,
+            "justifyContent" => a.justify_content == b.justify_content,
+            "justifyItems" => a.justify_items == b.justify_items,
+            "justifySelf" => a.justify_self == b.justify_self,
+            "aspectRatio" => a.aspect_ratio == b.aspect_ratio,
+            "size" => a.size == b.size,
+            "width" => a.size.width == b.size.width,
+            "height" => a.size.height == b.size.height,
+            "minSize" => a.min_size == b.min_size,
+            "minWidth" => a.min_size.width == b.min_size.width,
+            "minHeight" => a.min_size.height == b.min_size.height,
+            "maxSize" => a.max_size == b.max_size,
+            "maxWidth" => a.max_size.width == b.max_size.width,
+            "maxHeight" => a.max_size.height == b.max_size.height,
+            "margin" => a.margin == b.margin,
+            "marginLeft" => a.margin.left == b.margin.left,
+            "marginRight" => a.margin.right == b.margin.right,
+            "marginTop" => a.margin.top == b.margin.top,
+            "marginBottom" => a.margin.bottom == b.margin.bottom,
+            "padding" => a.padding == b.padding,
+            "paddingLeft" => a.padding.left == b.padding.left,
+            "paddingRight" => a.padding.right == b.padding.right,
+            "paddingTop" => a.padding.top == b.padding.top,
+            "paddingBottom" => a.padding.bottom == b.padding.bottom,
+            "border" => a.border == b.border,
+            "borderLeft" => a.border.left == b.border.left,
+            "borderRight" => a.border.right == b.border.right,
+            "borderTop" => a.border.top == b.border.top,
+            "borderBottom" => a.border.bottom == b.border.bottom,
+            "inset" => a.inset == b.inset,
+            "left" => a.inset.left == b.inset.left,
+            "right" => a.inset.right == b.inset.right,
+            "top" => a.inset.top == b.inset.top,
+            "bottom" => a.inset.bottom == b.inset.bottom,
+            "gap" => a.gap == b.gap,
+            "columnGap" => a.gap.width == b.gap.width,
+            "rowGap" => a.gap.height == b.gap.height,
+            "itemIsTable" => a.item_is_table == b.item_is_table,
+            "itemIsReplaced" => a.item_is_replaced == b.item_is_replaced,
+            "scrollbarWidth" => a.scrollbar_width == b.scrollbar_width,
+            "textAlign" => a.text_align == b.text_align,
+            "gridAutoFlow" => a.grid_auto_flow == b.grid_auto_flow,
+            "gridRow" => a.grid_row == b.grid_row,
+            "gridRowStart" => a.grid_row.start == b.grid_row.start,
+            "gridRowEnd" => a.grid_row.end == b.grid_row.end,
+            "gridColumn" => a.grid_column == b.grid_column,
+            "gridColumnStart" => a.grid_column.start == b.grid_column.start,
+            "gridColumnEnd" => a.grid_column.end == b.grid_column.end,
+            "gridTemplateRows" => a.grid_template_rows == b.grid_template_rows,
+            "gridTemplateColumns" => a.grid_template_columns == b.grid_template_columns,
+            "gridAutoRows" => a.grid_auto_rows == b.grid_auto_rows,
+            "gridAutoColumns" => a.grid_auto_columns == b.grid_auto_columns,
+            "gridTemplateAreas" => a.grid_template_areas == b.grid_template_areas,
+            "gridTemplateRowNames" => a.grid_template_row_names == b.grid_template_row_names,
+            "gridTemplateColumnNames" => {
+                a.grid_template_column_names == b.grid_template_column_names
             }
 
             // Unknown property path
             _ => {
-                wasm_bindgen::throw_str(&format!("Unknown property path for set: {}", path));
+                wasm_bindgen::throw_str(&format!("Unknown property path: {}", path));
+            }
+        }
+    }
+}
+
+impl From<TaffyStyle::Style> for JsStyle {
+    fn from(inner: TaffyStyle::Style) -> Self {
+        JsStyle {
+            inner,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl JsStyle {
+    /// Sets a single property from a property setter
+    ///
+    /// Throws in strict mode; otherwise records a warning for an invalid value.
+    fn assign(&mut self, path: &str, value: JsValue) -> Result<(), JsValue> {
+        match self.set_property(path, value) {
+            Ok(()) => Ok(()),
+            Err(issue) if strict_mode() => {
+                Err(JsTaffyError::invalid_style(vec![issue.to_string()]).into())
+            }
+            Err(issue) => {
+                self.warnings.push(issue.to_string());
+                Ok(())
             }
         }
     }
 }
+
+// =============================================================================
+// Value Validation
+// =============================================================================
+
+/// Why a style property could not be set
+pub(crate) enum StyleIssue {
+    /// The key is not a style property
+    UnknownProperty(String),
+    /// The value does not match the property's type
+    InvalidValue(ArgumentError),
+}
+
+impl std::fmt::Display for StyleIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleIssue::UnknownProperty(path) => write!(f, "{path} is not a style property"),
+            StyleIssue::InvalidValue(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Creates the issue for a value that is not a valid `expected`
+fn invalid(path: &str, value: &JsValue, expected: &'static str) -> StyleIssue {
+    StyleIssue::InvalidValue(ArgumentError::new(path, value, expected))
+}
+
+/// Decodes a property value with `serde-wasm-bindgen`
+fn decode<T: DeserializeOwned>(
+    path: &str,
+    value: JsValue,
+    expected: &'static str,
+) -> Result<T, StyleIssue> {
+    serde_wasm_bindgen::from_value(value.clone()).map_err(|_| invalid(path, &value, expected))
+}
+
+/// Converts a property value to an enum, rejecting numbers that are not one of its variants
+fn enum_value<E: JsEnum>(path: &str, value: &JsValue) -> Result<E, StyleIssue> {
+    value
+        .as_f64()
+        .filter(|n| n.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(n))
+        .and_then(|n| E::try_from(n as u32).ok())
+        .ok_or_else(|| invalid(path, value, E::NAME))
+}
+
+/// Converts an `alignSelf`/`justifySelf` value, where `undefined` and `Auto` both mean unset
+fn align_self_value(
+    path: &str,
+    value: &JsValue,
+) -> Result<Option<TaffyStyle::AlignSelf>, StyleIssue> {
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    Ok(match enum_value::<JsAlignSelf>(path, value)? {
+        JsAlignSelf::Auto => None,
+        v => Some(v.into()),
+    })
+}
+
+/// Converts a numeric property value
+fn number(path: &str, value: &JsValue) -> Result<f32, StyleIssue> {
+    value
+        .as_f64()
+        .map(|n| n as f32)
+        .ok_or_else(|| invalid(path, value, "number"))
+}
+
+/// Converts a boolean property value
+fn boolean(path: &str, value: &JsValue) -> Result<bool, StyleIssue> {
+    value
+        .as_bool()
+        .ok_or_else(|| invalid(path, value, "boolean"))
+}
@@ -0,0 +1,273 @@
+//! # Monospace Text Module
+//!
+//! This module measures text in terminal cells for `TaffyTree.enableTextMeasure()`,
+//! so that terminal UIs can lay out text leaves without a JavaScript measure
+//! function.
+//!
+//! A leaf is measured as text when its context is an object with a string
+//! `text` property and an optional `wrap` mode:
+//!
+//! | `wrap` | Definite width | `min-content` | `max-content` |
+//! |--------|----------------|---------------|---------------|
+//! | `"wrap"` (default) | Wraps words at the width | Longest word | Longest line |
+//! | `"truncate"` | Cuts lines at the width | Longest line | Longest line |
+//! | `"none"` | Longest line | Longest line | Longest line |
+//!
+//! Widths are counted per grapheme cluster using East Asian display widths,
+//! so wide characters such as CJK ideographs and most emoji take two cells.
+//! Every `\n` starts a new line and each line is one cell high. When wrapping,
+//! words are separated by whitespace runs that count as one cell, and words
+//! longer than the width are broken between graphemes.
+
+use crate::error::JsTaffyError;
+use js_sys::Reflect;
+use taffy::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use wasm_bindgen::JsValue;
+
+/// TypeScript type of the `wrap` property, for error messages
+const TEXT_WRAP: &str = "\"wrap\" | \"truncate\" | \"none\"";
+
+// =============================================================================
+// Text Content
+// =============================================================================
+
+/// How text that does not fit the available width is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextWrap {
+    /// Words wrap onto further lines
+    Wrap,
+    /// Lines are cut at the available width
+    Truncate,
+    /// Lines keep their full width
+    None,
+}
+
+/// The text of a leaf measured in terminal cells
+#[derive(Debug, Clone)]
+pub(crate) struct TextContent {
+    text: String,
+    wrap: TextWrap,
+}
+
+impl TextContent {
+    pub(crate) fn new(text: impl Into<String>, wrap: TextWrap) -> Self {
+        TextContent {
+            text: text.into(),
+            wrap,
+        }
+    }
+
+    /// Reads a `TextContext` node context
+    ///
+    /// @returns - `None` if the context has no string `text` property
+    ///
+    /// @throws `InvalidArgument` if `wrap` is not a known mode
+    pub(crate) fn from_context(context: &JsValue) -> Result<Option<Self>, JsTaffyError> {
+        if !context.is_object() {
+            return Ok(None);
+        }
+        let field = |name: &str| Reflect::get(context, &name.into()).unwrap_or(JsValue::UNDEFINED);
+        let Some(text) = field("text").as_string() else {
+            return Ok(None);
+        };
+        let wrap = field("wrap");
+        let wrap = match wrap.as_string().as_deref() {
+            _ if wrap.is_undefined() => TextWrap::Wrap,
+            Some("wrap") => TextWrap::Wrap,
+            Some("truncate") => TextWrap::Truncate,
+            Some("none") => TextWrap::None,
+            _ => {
+                return Err(JsTaffyError::invalid_argument(
+                    "context.wrap",
+                    &wrap,
+                    TEXT_WRAP,
+                ));
+            }
+        };
+        Ok(Some(TextContent::new(text, wrap)))
+    }
+
+    /// Measures the text in cells
+    ///
+    /// A known width is used as the wrapping width; known dimensions are
+    /// returned unchanged.
+    pub(crate) fn measure(
+        &self,
+        known: Size<Option<f32>>,
+        available: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        if let Size {
+            width: Some(width),
+            height: Some(height),
+        } = known
+        {
+            return Size { width, height };
+        }
+        let (width, height) = if self.text.is_empty() {
+            (0, 0)
+        } else {
+            let space = known
+                .width
+                .map_or(available.width, AvailableSpace::Definite);
+            self.measure_cells(space)
+        };
+        Size {
+            width: known.width.unwrap_or(width as f32),
+            height: known.height.unwrap_or(height as f32),
+        }
+    }
+
+    /// Measures the text at an available width
+    ///
+    /// @returns - The width of the widest line and the number of lines
+    fn measure_cells(&self, space: AvailableSpace) -> (usize, usize) {
+        let limit = match space {
+            AvailableSpace::Definite(width) => Some(width.max(0.0).floor() as usize),
+            AvailableSpace::MinContent if self.wrap == TextWrap::Wrap => {
+                Some(self.lines().map(longest_word).max().unwrap_or(0))
+            }
+            AvailableSpace::MinContent | AvailableSpace::MaxContent => None,
+        };
+        match self.wrap {
+            TextWrap::Wrap => {
+                let max = limit.unwrap_or(usize::MAX);
+                self.lines().map(|line| wrap_line(line, max)).fold(
+                    (0, 0),
+                    |(width, count), (line_width, line_count)| {
+                        (width.max(line_width), count + line_count)
+                    },
+                )
+            }
+            TextWrap::Truncate | TextWrap::None => {
+                let widest = self.lines().map(|line| line.width()).max().unwrap_or(0);
+                let width = match (self.wrap, limit) {
+                    (TextWrap::Truncate, Some(limit)) => widest.min(limit),
+                    _ => widest,
+                };
+                (width, self.lines().count())
+            }
+        }
+    }
+
+    /// Iterates over the lines of the text, without line breaks
+    fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+    }
+}
+
+// =============================================================================
+// Wrapping
+// =============================================================================
+
+/// Gets the width of the widest word of a line
+fn longest_word(line: &str) -> usize {
+    line.split_whitespace().map(str::width).max().unwrap_or(0)
+}
+
+/// Wraps a line at `max` cells
+///
+/// @returns - The width of the widest wrapped line and the number of lines
+fn wrap_line(line: &str, max: usize) -> (usize, usize) {
+    let mut widest = 0;
+    let mut count = 1;
+    let mut current = 0;
+    for word in line.split_whitespace() {
+        let width = word.width();
+        if current > 0 && current + 1 + width <= max {
+            current += 1 + width;
+            continue;
+        }
+        if current > 0 {
+            widest = widest.max(current);
+            count += 1;
+            current = 0;
+        }
+        if width <= max {
+            current = width;
+            continue;
+        }
+        // Words wider than a line are broken between graphemes
+        for grapheme in word.graphemes(true) {
+            let width = grapheme.width();
+            if current > 0 && current + width > max {
+                widest = widest.max(current);
+                count += 1;
+                current = 0;
+            }
+            current += width;
+        }
+    }
+    (widest.max(current), count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(text: &str, wrap: TextWrap, width: AvailableSpace) -> (f32, f32) {
+        let size = TextContent::new(text, wrap).measure(
+            Size::NONE,
+            Size {
+                width,
+                height: AvailableSpace::MaxContent,
+            },
+        );
+        (size.width, size.height)
+    }
+
+    #[test]
+    fn wraps_words_at_the_available_width() {
+        let text = "the quick brown fox";
+        let at = AvailableSpace::Definite;
+        assert_eq!(measure(text, TextWrap::Wrap, at(10.0)), (9.0, 2.0));
+        assert_eq!(measure(text, TextWrap::Wrap, at(4.5)), (4.0, 6.0));
+        assert_eq!(
+            measure(text, TextWrap::Wrap, AvailableSpace::MinContent),
+            (5.0, 4.0)
+        );
+        assert_eq!(
+            measure(text, TextWrap::Wrap, AvailableSpace::MaxContent),
+            (19.0, 1.0)
+        );
+        assert_eq!(measure("abcdefgh", TextWrap::Wrap, at(3.0)), (3.0, 3.0));
+        assert_eq!(measure("a\n\nbc", TextWrap::Wrap, at(3.0)), (2.0, 3.0));
+    }
+
+    #[test]
+    fn counts_wide_graphemes_as_two_cells() {
+        let at = AvailableSpace::Definite;
+        assert_eq!(measure("日本語", TextWrap::None, at(1.0)), (6.0, 1.0));
+        assert_eq!(measure("日本語", TextWrap::Wrap, at(5.0)), (4.0, 2.0));
+        assert_eq!(measure("e\u{301}👍🏽", TextWrap::None, at(1.0)), (3.0, 1.0));
+    }
+
+    #[test]
+    fn truncates_lines_only_at_a_definite_width() {
+        let text = "hello world\nhi";
+        let at = AvailableSpace::Definite;
+        assert_eq!(measure(text, TextWrap::Truncate, at(5.0)), (5.0, 2.0));
+        assert_eq!(
+            measure(text, TextWrap::Truncate, AvailableSpace::MinContent),
+            (11.0, 2.0)
+        );
+        assert_eq!(measure(text, TextWrap::None, at(5.0)), (11.0, 2.0));
+        assert_eq!(measure("", TextWrap::None, at(5.0)), (0.0, 0.0));
+    }
+
+    #[test]
+    fn keeps_known_dimensions() {
+        let text = TextContent::new("the quick brown fox", TextWrap::Wrap);
+        let size = text.measure(
+            Size {
+                width: Some(10.0),
+                height: None,
+            },
+            Size::MAX_CONTENT,
+        );
+        assert_eq!((size.width, size.height), (10.0, 2.0));
+    }
+}
@@ -96,15 +96,32 @@
 //! }
 //! ```
 
-use crate::error::{JsTaffyError, map_bool_result, map_node_result, map_void_result, to_js_error};
-use crate::layout::JsLayout;
+use crate::batch::{self, BatchOp, NodeRef};
+use crate::binary;
+use crate::error::{
+    JsTaffyError, decode_argument, map_bool_result, map_node_result, map_void_result, to_js_error,
+};
+use crate::history::{self, Edit, History, Journal, Target};
+use crate::layout::{JsLayout, JsLayoutBuffer};
+use crate::measure::{MeasureArgs, MeasureArgsOptionsDto, MeasureCache};
+use crate::snapshot::{self, SnapshotOptionsDto};
 use crate::style::JsStyle;
-use crate::types::{AvailableSizeDto, JsAvailableSizeArg, JsBigIntArray, JsMeasureFunctionArg};
+use crate::text::TextContent;
+use crate::types::{
+    AvailableSpaceDto, BoundingBoxDto, JsAvailableSizeArg, JsBatchOpArray, JsBatchResults,
+    JsBigIntArray, JsBoundingBoxArg, JsLayoutChangeCallbackArg, JsMeasureArgsOptions,
+    JsMeasureCacheStats, JsMeasureFunctionArg, JsScrollOffsets, JsSnapshotOptions, JsTaffyTreeArg,
+    JsTreeIssueArray, JsTreeSnapshot, PointDto,
+};
+use crate::utils::queue_microtask;
 use crate::{DetailedGridInfoDto, DetailedGridItemsInfoDto, DetailedGridTracksInfoDto};
 
-use js_sys::{Array, BigInt};
+use js_sys::{Array, BigInt, Map, Object, Reflect};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use taffy::TaffyError as NativeTaffyError;
 use taffy::TaffyTree;
+use taffy::geometry::Point;
 use taffy::prelude::*;
 use taffy::style::{self as TaffyStyle};
 #[cfg(feature = "detailed_layout_info")]
@@ -125,6 +142,29 @@ use wasm_bindgen::prelude::*;
 pub struct JsTaffyTree {
     /// The underlying Taffy tree with JsValue context type
     tree: TaffyTree<JsValue>,
+    /// IDs of all nodes currently in `tree`, used to detect dangling IDs
+    nodes: HashSet<NodeId>,
+    /// Whether structural edits are validated before they are applied
+    strict: bool,
+    /// Invalid values skipped by lenient style updates, drained by `takeWarnings()`
+    warnings: Vec<String>,
+    /// Edits of the open transactions and the running `applyBatch()` call,
+    /// for rolling them back
+    journal: Journal,
+    /// Whether layout values are rounded, see `enableRounding()`
+    rounding: bool,
+    /// Undo/redo history, if enabled
+    history: Option<History>,
+    /// Layout change tracking, if enabled
+    layout_changes: Option<LayoutChanges>,
+    /// Measure functions set with `setMeasureFunc()`
+    measure_funcs: HashMap<NodeId, js_sys::Function>,
+    /// Memoized measure results, if enabled
+    measure_cache: Option<MeasureCache>,
+    /// How measure functions are called, see `setMeasureArgs()`
+    measure_args: MeasureArgs,
+    /// Whether leaves with a text context are measured natively
+    text_measure: bool,
 }
 
 #[wasm_bindgen(js_class = "TaffyTree")]
@@ -148,6 +188,17 @@ impl JsTaffyTree {
         console_error_panic_hook::set_once();
         JsTaffyTree {
             tree: TaffyTree::new(),
+            nodes: HashSet::new(),
+            strict: false,
+            warnings: Vec::new(),
+            journal: Journal::default(),
+            rounding: true,
+            history: None,
+            layout_changes: None,
+            measure_funcs: HashMap::new(),
+            measure_cache: None,
+            measure_args: MeasureArgs::default(),
+            text_measure: false,
         }
     }
 
@@ -168,6 +219,17 @@ impl JsTaffyTree {
         console_error_panic_hook::set_once();
         JsTaffyTree {
             tree: TaffyTree::with_capacity(capacity),
+            nodes: HashSet::with_capacity(capacity),
+            strict: false,
+            warnings: Vec::new(),
+            journal: Journal::default(),
+            rounding: true,
+            history: None,
+            layout_changes: None,
+            measure_funcs: HashMap::new(),
+            measure_cache: None,
+            measure_args: MeasureArgs::default(),
+            text_measure: false,
         }
     }
 
@@ -188,7 +250,7 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = enableRounding)]
     pub fn enable_rounding(&mut self) {
-        self.tree.enable_rounding();
+        self.set_rounding(true);
     }
 
     /// Disables rounding of layout values
@@ -207,7 +269,49 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = disableRounding)]
     pub fn disable_rounding(&mut self) {
-        self.tree.disable_rounding();
+        self.set_rounding(false);
+    }
+
+    /// Enables strict mode for structural edits
+    ///
+    /// In strict mode, the methods that create nodes or change children check
+    /// each edit first, and throw instead of applying an edit that would
+    /// make a node its own ancestor, give a node a second parent, or list a
+    /// child twice. Edits referencing a node that does not exist are
+    /// rejected in either mode. Strict mode is disabled by default.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableStrictMode();
+    /// const child = tree.newLeaf(new Style());
+    /// const a = tree.newWithChildren(new Style(), [child]);
+    /// const b = tree.newLeaf(new Style());
+    /// try {
+    ///   tree.addChild(b, child); // child already belongs to a
+    /// } catch (e) {
+    ///   console.error((e as TaffyError).message);
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = enableStrictMode)]
+    pub fn enable_strict_mode(&mut self) {
+        self.strict = true;
+    }
+
+    /// Disables strict mode for structural edits
+    ///
+    /// Edits are applied without the checks described in
+    /// `enableStrictMode()`. Use `validate()` to find integrity problems.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableStrictMode();
+    /// tree.disableStrictMode();
+    /// ```
+    #[wasm_bindgen(js_name = disableStrictMode)]
+    pub fn disable_strict_mode(&mut self) {
+        self.strict = false;
     }
 
     // =========================================================================
@@ -232,7 +336,11 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = newLeaf)]
     pub fn new_leaf(&mut self, style: &JsStyle) -> Result<u64, JsValue> {
-        map_node_result(self.tree.new_leaf(style.inner.clone()))
+        map_node_result(
+            self.tree
+                .new_leaf(style.inner.clone())
+                .map(|id| self.created(id)),
+        )
     }
 
     /// Creates a new leaf node with an attached context value
@@ -263,7 +371,8 @@ impl JsTaffyTree {
     ) -> Result<u64, JsValue> {
         map_node_result(
             self.tree
-                .new_leaf_with_context(style.inner.clone(), context),
+                .new_leaf_with_context(style.inner.clone(), context)
+                .map(|id| self.created(id)),
         )
     }
 
@@ -299,12 +408,9 @@ impl JsTaffyTree {
         style: &JsStyle,
         children: JsBigIntArray,
     ) -> Result<u64, JsValue> {
-        let children: Vec<u64> = serde_wasm_bindgen::from_value(children.into())?;
+        let children: Vec<u64> = decode_argument("children", children.into(), "bigint[]")?;
         let children_ids: Vec<NodeId> = children.iter().map(|&id| NodeId::from(id)).collect();
-        map_node_result(
-            self.tree
-                .new_with_children(style.inner.clone(), &children_ids),
-        )
+        self.create_with_children(style, &children_ids)
     }
 
     // =========================================================================
@@ -316,6 +422,9 @@ impl JsTaffyTree {
     /// This clears the entire tree, removing all nodes and their relationships.
     /// Use this to reset the tree for reuse.
     ///
+    /// Inside a transaction, the nodes stay allocated until the outermost
+    /// transaction commits, so that rolling back restores them.
+    ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
@@ -324,7 +433,19 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = clear)]
     pub fn clear(&mut self) {
+        if self.journal.is_open() {
+            self.clear_undoably();
+            return;
+        }
         self.tree.clear();
+        self.nodes.clear();
+        self.measure_funcs.clear();
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+        if let Some(history) = &mut self.history {
+            *history = History::new(history.depth());
+        }
     }
 
     /// Removes a node from the tree
@@ -350,7 +471,106 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = remove)]
     pub fn remove(&mut self, node: u64) -> Result<u64, JsValue> {
-        map_node_result(self.tree.remove(NodeId::from(node)))
+        let node = NodeId::from(node);
+        self.check_edit(Some(node), &[], false)?;
+        self.invalidate_measure(node);
+        if self.recording() {
+            self.remove_undoably(node)?;
+            return Ok(u64::from(node));
+        }
+        self.nodes.remove(&node);
+        self.measure_funcs.remove(&node);
+        map_node_result(self.tree.remove(node))
+    }
+
+    /// Creates a deep copy of a subtree in this tree
+    ///
+    /// Every node in the subtree is copied with its style, child order,
+    /// context and measure function. Contexts are shared by reference, not
+    /// deep-copied. The copy has no parent.
+    ///
+    /// @param node - The root node ID of the subtree to copy
+    ///
+    /// @returns - The ID of the copied root node
+    ///
+    /// @throws `TaffyError` if the node does not exist, or `IntegrityViolation`
+    /// if a node is reachable through more than one parent
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const label = tree.newLeafWithContext(new Style(), { text: "Row" });
+    /// const row = tree.newWithChildren(new Style({ display: Display.Flex }), [label]);
+    /// const list = tree.newWithChildren(new Style(), [row]);
+    ///
+    /// const copy: bigint = tree.cloneSubtree(row);
+    /// tree.addChild(list, copy);
+    /// console.log(tree.childCount(list)); // 2
+    /// ```
+    #[wasm_bindgen(js_name = cloneSubtree)]
+    pub fn clone_subtree(&mut self, node: u64) -> Result<u64, JsValue> {
+        let copies = self.copy_subtree(self.existing(node)?)?;
+        self.paste_subtree(copies).map(u64::from)
+    }
+
+    /// Creates a deep copy of a subtree of this tree in another tree
+    ///
+    /// Works like `cloneSubtree()`, but the copy is created in `target`.
+    /// This tree is not modified. Use `cloneSubtree()` to copy within a tree.
+    ///
+    /// @param target - The tree to create the copy in; must not be this tree
+    /// @param node - The root node ID of the subtree to copy, in this tree
+    ///
+    /// @returns - The ID of the copied root node in `target`
+    ///
+    /// @throws `TaffyError` if the node does not exist, `InvalidArgument` if
+    /// `target` is not a different `TaffyTree`, or `IntegrityViolation` if a
+    /// node is reachable through more than one parent
+    ///
+    /// @example
+    /// ```typescript
+    /// const templates = new TaffyTree();
+    /// const card = templates.newWithChildren(new Style({ padding: { left: 8, right: 8, top: 8, bottom: 8 } }), [
+    ///   templates.newLeaf(new Style({ height: 20 })),
+    /// ]);
+    ///
+    /// const tree = new TaffyTree();
+    /// const copy: bigint = templates.cloneSubtreeInto(tree, card);
+    /// console.log(tree.childCount(copy)); // 1
+    /// ```
+    #[wasm_bindgen(js_name = cloneSubtreeInto)]
+    pub fn clone_subtree_into(&self, target: &JsTaffyTreeArg, node: u64) -> Result<u64, JsValue> {
+        let copies = self.copy_subtree(self.existing(node)?)?;
+        CLONE_TRANSFER.set(Some(CloneTransfer {
+            source: std::ptr::from_ref(self),
+            copies,
+        }));
+        let result = Self::transfer_clone(target);
+        CLONE_TRANSFER.take();
+        result
+    }
+
+    /// Returns whether this tree is the source of the `cloneSubtreeInto()`
+    /// call in progress
+    ///
+    /// Only borrows the tree, so it can be called on the source tree itself.
+    #[wasm_bindgen(js_name = __isCloneSource, skip_typescript)]
+    pub fn is_clone_source(&self) -> bool {
+        CLONE_TRANSFER.with_borrow(|transfer| {
+            transfer
+                .as_ref()
+                .is_some_and(|transfer| std::ptr::eq(transfer.source, self))
+        })
+    }
+
+    /// Recreates the subtree copied by the `cloneSubtreeInto()` call in
+    /// progress in this tree
+    #[wasm_bindgen(js_name = __pasteClone, skip_typescript)]
+    pub fn paste_clone(&mut self) -> Result<u64, JsValue> {
+        let Some(transfer) = CLONE_TRANSFER.take() else {
+            return Err(JsTaffyError::integrity_violation("no subtree is being cloned").into());
+        };
+        self.paste_subtree(transfer.copies).map(u64::from)
     }
 
     // =========================================================================
@@ -376,10 +596,8 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = setNodeContext)]
     pub fn set_node_context(&mut self, node: u64, context: JsValue) -> Result<(), JsValue> {
-        map_void_result(
-            self.tree
-                .set_node_context(NodeId::from(node), Some(context)),
-        )
+        let node = self.existing(node)?;
+        self.write_context(node, Some(context))
     }
 
     /// Gets the context value for a node
@@ -400,7 +618,7 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = getNodeContext)]
     pub fn get_node_context(&self, node: u64) -> Result<JsValue, JsValue> {
-        match self.tree.get_node_context(NodeId::from(node)) {
+        match self.tree.get_node_context(self.existing(node)?) {
             Some(ctx) => Ok(ctx.clone()),
             None => Ok(JsValue::UNDEFINED),
         }
@@ -416,7 +634,8 @@ impl JsTaffyTree {
     /// @returns - The attached context value, or `undefined` if none is set
     #[wasm_bindgen(js_name = getNodeContextMut)]
     pub fn get_node_context_mut(&mut self, node: u64) -> Result<JsValue, JsValue> {
-        match self.tree.get_node_context_mut(NodeId::from(node)) {
+        let node = self.existing(node)?;
+        match self.tree.get_node_context_mut(node) {
             Some(ctx) => Ok(ctx.clone()),
             None => Ok(JsValue::UNDEFINED),
         }
@@ -444,10 +663,11 @@ impl JsTaffyTree {
         &mut self,
         children: JsBigIntArray,
     ) -> Result<Box<[JsValue]>, JsValue> {
-        let children: Vec<u64> = serde_wasm_bindgen::from_value(children.into())?;
+        let children: Vec<u64> = decode_argument("children", children.into(), "bigint[]")?;
         let mut results = Vec::with_capacity(children.len());
-        for id in children.iter() {
-            match self.tree.get_node_context_mut(NodeId::from(*id)) {
+        for &id in children.iter() {
+            let node = self.existing(id)?;
+            match self.tree.get_node_context_mut(node) {
                 Some(ctx) => results.push(ctx.clone()),
                 None => results.push(JsValue::UNDEFINED),
             }
@@ -477,10 +697,11 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = addChild)]
     pub fn add_child(&mut self, parent: u64, child: u64) -> Result<(), JsValue> {
-        map_void_result(
-            self.tree
-                .add_child(NodeId::from(parent), NodeId::from(child)),
-        )
+        let (parent, child) = (NodeId::from(parent), NodeId::from(child));
+        self.check_edit(Some(parent), &[child], false)?;
+        self.record_links(parent, &[child], |this| {
+            map_void_result(this.tree.add_child(parent, child))
+        })
     }
 
     /// Inserts a child at a specific index
@@ -505,11 +726,81 @@ impl JsTaffyTree {
         index: usize,
         child: u64,
     ) -> Result<(), JsValue> {
-        map_void_result(self.tree.insert_child_at_index(
-            NodeId::from(parent),
-            index,
-            NodeId::from(child),
-        ))
+        let (parent, child) = (NodeId::from(parent), NodeId::from(child));
+        self.check_edit(Some(parent), &[child], false)?;
+        self.record_links(parent, &[child], |this| {
+            map_void_result(this.tree.insert_child_at_index(parent, index, child))
+        })
+    }
+
+    /// Moves a node to a new parent, or to a new position under its parent
+    ///
+    /// The node is detached from its current parent, if any, and inserted
+    /// into `newParent`'s children. The index refers to the children of
+    /// `newParent` after the node has been detached, so moving a node within
+    /// the same parent places it exactly at `index`. The move is validated
+    /// before anything changes.
+    ///
+    /// @param node - The node ID to move
+    /// @param newParent - The node ID of the new parent
+    /// @param index - The position to insert at (0-based); appends when omitted
+    ///
+    /// @throws `TaffyError` if either node does not exist, the index is out
+    /// of bounds, or `newParent` is `node` itself or one of its descendants
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const item = tree.newLeaf(new Style());
+    /// const from = tree.newWithChildren(new Style(), [item]);
+    /// const to = tree.newWithChildren(new Style(), [tree.newLeaf(new Style())]);
+    ///
+    /// tree.moveNode(item, to, 0);
+    /// console.log(tree.childCount(from)); // 0
+    /// console.log(tree.getChildAtIndex(to, 0) === item); // true
+    /// ```
+    #[wasm_bindgen(js_name = moveNode)]
+    pub fn move_node(
+        &mut self,
+        node: u64,
+        new_parent: u64,
+        index: Option<usize>,
+    ) -> Result<(), JsValue> {
+        let node = NodeId::from(node);
+        let new_parent = NodeId::from(new_parent);
+        self.check_edit(Some(new_parent), &[], false)?;
+        self.check_edit(Some(node), &[], false)?;
+        let mut child_count = self.tree.child_count(new_parent);
+
+        let mut ancestor = Some(new_parent);
+        while let Some(current) = ancestor {
+            if current == node {
+                return Err(JsTaffyError::would_create_cycle(node, new_parent).into());
+            }
+            ancestor = self.tree.parent(current);
+        }
+
+        let old_parent = self.tree.parent(node);
+        if old_parent == Some(new_parent) {
+            child_count -= 1;
+        }
+        let index = index.unwrap_or(child_count);
+        if index > child_count {
+            return Err(to_js_error(NativeTaffyError::ChildIndexOutOfBounds {
+                parent: new_parent,
+                child_index: index,
+                child_count,
+            }));
+        }
+
+        self.record_links(new_parent, &[node], |this| {
+            if let Some(old_parent) = old_parent {
+                this.tree
+                    .remove_child(old_parent, node)
+                    .map_err(to_js_error)?;
+            }
+            map_void_result(this.tree.insert_child_at_index(new_parent, index, node))
+        })
     }
 
     /// Replaces all children of a node
@@ -533,9 +824,9 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = setChildren)]
     pub fn set_children(&mut self, parent: u64, children: JsBigIntArray) -> Result<(), JsValue> {
-        let children: Vec<u64> = serde_wasm_bindgen::from_value(children.into())?;
+        let children: Vec<u64> = decode_argument("children", children.into(), "bigint[]")?;
         let children_ids: Vec<NodeId> = children.iter().map(|&id| NodeId::from(id)).collect();
-        map_void_result(self.tree.set_children(NodeId::from(parent), &children_ids))
+        self.replace_children(NodeId::from(parent), &children_ids)
     }
 
     /// Removes a specific child from a parent
@@ -557,10 +848,12 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = removeChild)]
     pub fn remove_child(&mut self, parent: u64, child: u64) -> Result<u64, JsValue> {
-        map_node_result(
-            self.tree
-                .remove_child(NodeId::from(parent), NodeId::from(child)),
-        )
+        let (parent, child) = (NodeId::from(parent), NodeId::from(child));
+        self.check_edit(Some(parent), &[], false)?;
+        self.check_exists(child)?;
+        self.record_links(parent, &[], |this| {
+            map_node_result(this.tree.remove_child(parent, child))
+        })
     }
 
     /// Removes a child at a specific index
@@ -582,7 +875,11 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = removeChildAtIndex)]
     pub fn remove_child_at_index(&mut self, parent: u64, index: usize) -> Result<u64, JsValue> {
-        map_node_result(self.tree.remove_child_at_index(NodeId::from(parent), index))
+        let parent = NodeId::from(parent);
+        self.check_edit(Some(parent), &[], false)?;
+        self.record_links(parent, &[], |this| {
+            map_node_result(this.tree.remove_child_at_index(parent, index))
+        })
     }
 
     /// Replaces a child at a specific index
@@ -614,11 +911,11 @@ impl JsTaffyTree {
         index: usize,
         #[wasm_bindgen(js_name = "newChild")] new_child: u64,
     ) -> Result<u64, JsValue> {
-        map_node_result(self.tree.replace_child_at_index(
-            NodeId::from(parent),
-            index,
-            NodeId::from(new_child),
-        ))
+        let (parent, new_child) = (NodeId::from(parent), NodeId::from(new_child));
+        self.check_edit(Some(parent), &[new_child], false)?;
+        self.record_links(parent, &[new_child], |this| {
+            map_node_result(this.tree.replace_child_at_index(parent, index, new_child))
+        })
     }
 
     /// Gets the child at a specific index
@@ -640,7 +937,7 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = getChildAtIndex)]
     pub fn get_child_at_index(&self, parent: u64, index: usize) -> Result<u64, JsValue> {
-        map_node_result(self.tree.child_at_index(NodeId::from(parent), index))
+        map_node_result(self.tree.child_at_index(self.existing(parent)?, index))
     }
 
     /// Removes a range of children
@@ -672,10 +969,14 @@ impl JsTaffyTree {
         #[wasm_bindgen(js_name = "startIndex")] start_index: usize,
         #[wasm_bindgen(js_name = "endIndex")] end_index: usize,
     ) -> Result<(), JsValue> {
-        map_void_result(
-            self.tree
-                .remove_children_range(NodeId::from(parent), start_index..end_index),
-        )
+        let parent = NodeId::from(parent);
+        self.check_edit(Some(parent), &[], false)?;
+        self.record_links(parent, &[], |this| {
+            map_void_result(
+                this.tree
+                    .remove_children_range(parent, start_index..end_index),
+            )
+        })
     }
 
     /// Gets the total number of nodes in the tree
@@ -689,7 +990,7 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = totalNodeCount)]
     pub fn total_node_count(&self) -> usize {
-        self.tree.total_node_count()
+        self.nodes.len()
     }
 
     /// Gets the number of children of a node
@@ -707,8 +1008,8 @@ impl JsTaffyTree {
     /// const count: number = tree.childCount(parentId);
     /// ```
     #[wasm_bindgen(js_name = childCount)]
-    pub fn child_count(&self, parent: u64) -> usize {
-        self.tree.child_count(NodeId::from(parent))
+    pub fn child_count(&self, parent: u64) -> Result<usize, JsValue> {
+        Ok(self.tree.child_count(self.existing(parent)?))
     }
 
     /// Gets the parent of a node
@@ -717,6 +1018,8 @@ impl JsTaffyTree {
     ///
     /// @returns - The parent node ID, or `undefined` if the node has no parent
     ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
@@ -726,8 +1029,8 @@ impl JsTaffyTree {
     /// const parent: bigint | undefined = tree.parent(childId);
     /// ```
     #[wasm_bindgen(js_name = parent)]
-    pub fn parent(&self, child: u64) -> Option<u64> {
-        self.tree.parent(NodeId::from(child)).map(u64::from)
+    pub fn parent(&self, child: u64) -> Result<Option<u64>, JsValue> {
+        Ok(self.tree.parent(self.existing(child)?).map(u64::from))
     }
 
     /// Gets all children of a node
@@ -747,7 +1050,7 @@ impl JsTaffyTree {
     #[wasm_bindgen(js_name = children)]
     pub fn children(&self, parent: u64) -> Result<JsBigIntArray, JsValue> {
         self.tree
-            .children(NodeId::from(parent))
+            .children(self.existing(parent)?)
             .map(|c| {
                 let array = Array::new();
                 for &id in c.iter() {
@@ -782,213 +1085,1451 @@ impl JsTaffyTree {
     /// ```
     #[wasm_bindgen(js_name = setStyle)]
     pub fn set_style(&mut self, node: u64, style: &JsStyle) -> Result<(), JsValue> {
-        map_void_result(self.tree.set_style(NodeId::from(node), style.inner.clone()))
+        let node = self.existing(node)?;
+        self.write_style(node, style.inner.clone())
     }
 
-    /// Gets the style for a node
+    /// Sets the style for an existing node unless it already has that style
+    ///
+    /// Unlike `setStyle()`, an equal style (see `Style.equals()`) leaves the
+    /// node and its ancestors clean, so cached layouts stay valid.
     ///
     /// @param node - The node ID
+    /// @param style - The new style configuration
     ///
-    /// @returns - The node's `Style`
+    /// @returns - `true` if the style changed and the node was marked dirty
     ///
     /// @throws `TaffyError` if the node does not exist
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const nodeId = tree.newLeaf(new Style());
-    /// const style: Style = tree.getStyle(nodeId);
-    /// console.log('Flex grow:', style.flexGrow);
+    /// const nodeId = tree.newLeaf(new Style({ width: 100 }));
+    /// tree.computeLayout(nodeId, { width: 500, height: 500 });
+    /// console.log(tree.setStyleIfChanged(nodeId, new Style({ width: 100 }))); // false
+    /// console.log(tree.dirty(nodeId)); // false
     /// ```
-    #[wasm_bindgen(js_name = getStyle)]
-    pub fn style(&self, node: u64) -> Result<JsStyle, JsValue> {
-        match self.tree.style(NodeId::from(node)) {
-            Ok(s) => Ok(JsStyle { inner: s.clone() }),
-            Err(e) => Err(JsValue::from(JsTaffyError::from(e))),
+    #[wasm_bindgen(js_name = setStyleIfChanged)]
+    pub fn set_style_if_changed(&mut self, node: u64, style: &JsStyle) -> Result<bool, JsValue> {
+        let node = self.existing(node)?;
+        if *self.tree.style(node).map_err(to_js_error)? == style.inner {
+            return Ok(false);
         }
+        self.write_style(node, style.inner.clone())?;
+        Ok(true)
     }
 
-    // =========================================================================
-    // Layout Results
-    // =========================================================================
-
-    /// Gets the computed layout for a node
+    /// Updates some style properties of a node in place
     ///
-    /// Call this after `computeLayout()` to retrieve the computed position
-    /// and size for a node.
+    /// Applies `props` to the node's stored style with the same key and value
+    /// handling as `Style.set()`, without creating `Style` objects. The node is
+    /// marked dirty only if a property actually changed.
+    ///
+    /// Invalid values are skipped and recorded as warnings (see
+    /// `takeWarnings()`), unless strict validation applies.
     ///
     /// @param node - The node ID
+    /// @param props - Object with property keys as keys and values to set
+    /// @param strict - Overrides the global strict mode for this call
     ///
-    /// @returns - The computed `Layout`
+    /// @returns - `true` if the style changed and the node was marked dirty
     ///
     /// @throws `TaffyError` if the node does not exist
+    /// @throws Error if any property key is unknown.
+    /// @throws `TaffyError` with code `InvalidStyle` in strict mode if `props` is
+    /// not an object, or if any key or value is invalid. The node's style is
+    /// left unchanged in that case.
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const style = new Style();
-    /// style.size = { width: 100, height: 100 };
-    /// const rootId = tree.newLeaf(style);
-    /// const nodeId = rootId;
-    ///
-    /// tree.computeLayout(rootId, { width: 800, height: 600 });
-    /// const layout: Layout = tree.getLayout(nodeId);
-    /// console.log(`Position: (${layout.x}, ${layout.y}), Size: ${layout.width}x${layout.height}`);
+    /// const nodeId = tree.newLeaf(new Style());
+    /// tree.updateStyle(nodeId, { width: 100, flexGrow: 1 });
+    /// console.log(tree.getStyleProperty(nodeId, "width")); // 100
     /// ```
-    #[wasm_bindgen(js_name = getLayout)]
-    pub fn layout(&self, node: u64) -> Result<JsLayout, JsValue> {
-        match self.tree.layout(NodeId::from(node)) {
-            Ok(l) => Ok(JsLayout::from(l)),
-            Err(e) => Err(JsValue::from(JsTaffyError::from(e))),
-        }
+    #[wasm_bindgen(js_name = updateStyle, skip_typescript)]
+    pub fn update_style(
+        &mut self,
+        node: u64,
+        props: JsValue,
+        strict: Option<bool>,
+    ) -> Result<bool, JsValue> {
+        let node = self.existing(node)?;
+        let current = self.tree.style(node).map_err(to_js_error)?;
+        let (updated, warnings) = JsStyle::update(current, props, strict)?;
+        let warnings = warnings.iter().map(|w| format!("updateStyle(): {w}"));
+        self.warnings.extend(warnings);
+        let Some(style) = updated else {
+            return Ok(false);
+        };
+        self.write_style(node, style)?;
+        Ok(true)
     }
 
-    /// Gets the unrounded (fractional) layout for a node
-    ///
-    /// Returns the raw computed values before any rounding is applied.
-    /// Useful when you need sub-pixel precision.
+    /// Returns and clears the warnings recorded by lenient style updates
     ///
-    /// @param node - The node ID
+    /// Each message names the method that skipped the value, followed by the
+    /// same text as `Style.takeWarnings()`.
     ///
-    /// @returns - The unrounded `Layout`
+    /// @returns - One message per skipped value
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const nodeId = tree.newLeaf(new Style());
-    /// const layout: Layout = tree.unroundedLayout(nodeId);
-    /// console.log(`Exact width: ${layout.width}`);
+    /// const node = tree.newLeaf(new Style());
+    /// tree.updateStyle(node, { width: "wide" } as never);
+    /// console.log(tree.takeWarnings()); // ['updateStyle(): width: "wide" is not a valid Dimension']
+    /// console.log(tree.takeWarnings()); // []
     /// ```
-    #[wasm_bindgen(js_name = unroundedLayout)]
-    pub fn unrounded_layout(&self, node: u64) -> JsLayout {
-        JsLayout::from(self.tree.unrounded_layout(NodeId::from(node)))
+    #[wasm_bindgen(js_name = takeWarnings)]
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
     }
 
-    /// Gets detailed layout information for grid layouts
+    /// Reads style properties of a node in a single WASM call
     ///
-    /// @note
-    /// This method is only available when the `detailed_layout_info`
-    /// feature is enabled.
+    /// Mirrors `Style.get()` on the node's stored style, without copying the
+    /// whole style the way `getStyle()` does.
     ///
     /// @param node - The node ID
+    /// @param keys - Property keys to read
     ///
-    /// @returns - Detailed grid info or "None" for non-grid nodes
+    /// @returns - Single value if one key, array of values if multiple keys
     ///
     /// @throws `TaffyError` if the node does not exist
-    #[cfg(feature = "detailed_layout_info")]
-    #[wasm_bindgen(js_name = detailedLayoutInfo)]
-    pub fn detailed_layout_info(&self, node: u64) -> Result<JsValue, JsValue> {
-        match self.tree.detailed_layout_info(NodeId::from(node)) {
-            DetailedLayoutInfo::Grid(info) => {
-                let dto = DetailedGridInfoDto {
-                    rows: DetailedGridTracksInfoDto {
-                        negative_implicit_tracks: info.rows.negative_implicit_tracks,
-                        explicit_tracks: info.rows.explicit_tracks,
-                        positive_implicit_tracks: info.rows.positive_implicit_tracks,
-                        gutters: info.rows.gutters.clone(),
-                        sizes: info.rows.sizes.clone(),
-                    },
-                    columns: DetailedGridTracksInfoDto {
-                        negative_implicit_tracks: info.columns.negative_implicit_tracks,
-                        explicit_tracks: info.columns.explicit_tracks,
-                        positive_implicit_tracks: info.columns.positive_implicit_tracks,
-                        gutters: info.columns.gutters.clone(),
-                        sizes: info.columns.sizes.clone(),
-                    },
-                    items: info
-                        .items
-                        .iter()
-                        .map(|item| DetailedGridItemsInfoDto {
-                            row_start: item.row_start,
-                            row_end: item.row_end,
-                            column_start: item.column_start,
-                            column_end: item.column_end,
-                        })
-                        .collect(),
-                };
-                Ok(serde_wasm_bindgen::to_value(&dto).unwrap_or(JsValue::NULL))
-            }
-            DetailedLayoutInfo::None => Ok(JsValue::NULL),
+    /// @throws Error if any property key is unknown.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const nodeId = tree.newLeaf(new Style({ width: 100, marginLeft: 8 }));
+    /// const [width, marginLeft] = tree.getStyleProperty(nodeId, "width", "marginLeft");
+    /// ```
+    #[wasm_bindgen(js_name = getStyleProperty, variadic, skip_typescript)]
+    pub fn get_style_property(&self, node: u64, keys: Vec<String>) -> Result<JsValue, JsValue> {
+        let style = self.tree.style(self.existing(node)?).map_err(to_js_error)?;
+        if keys.is_empty() {
+            return Ok(JsValue::UNDEFINED);
         }
-    }
 
-    // =========================================================================
-    // Dirty Tracking
-    // =========================================================================
+        let results = keys
+            .iter()
+            .map(|key| JsStyle::read_property(style, key))
+            .collect::<Result<Vec<JsValue>, JsValue>>()?;
 
-    /// Marks a node as dirty (requiring re-layout)
+        if results.len() == 1 {
+            Ok(results.into_iter().next().unwrap())
+        } else {
+            Ok(Array::from_iter(results).into())
+        }
+    }
+
+    /// Gets the style for a node
     ///
-    /// Use this when a node's content has changed but its style hasn't.
-    /// For example, when text content changes and needs remeasuring.
+    /// @param node - The node ID
     ///
-    /// @param node - The node ID to mark dirty
+    /// @returns - The node's `Style`
     ///
     /// @throws `TaffyError` if the node does not exist
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const rootId = tree.newLeaf(new Style());
-    /// const nodeId = rootId;
-    /// const availableSpace = { width: 100, height: 100 };
-    ///
-    /// // After updating text content
-    /// tree.setNodeContext(nodeId, { text: "Updated text" });
-    /// tree.markDirty(nodeId);
-    /// tree.computeLayout(rootId, availableSpace);
+    /// const nodeId = tree.newLeaf(new Style());
+    /// const style: Style = tree.getStyle(nodeId);
+    /// console.log('Flex grow:', style.flexGrow);
     /// ```
-    #[wasm_bindgen(js_name = markDirty)]
-    pub fn mark_dirty(&mut self, node: u64) -> Result<(), JsValue> {
-        map_void_result(self.tree.mark_dirty(NodeId::from(node)))
+    #[wasm_bindgen(js_name = getStyle)]
+    pub fn style(&self, node: u64) -> Result<JsStyle, JsValue> {
+        match self.tree.style(self.existing(node)?) {
+            Ok(s) => Ok(JsStyle::from(s.clone())),
+            Err(e) => Err(JsValue::from(JsTaffyError::from(e))),
+        }
     }
 
-    /// Checks if a node is dirty (needs re-layout)
+    // =========================================================================
+    // Batched Mutations
+    // =========================================================================
+
+    /// Applies a list of tree mutations in a single call
     ///
-    /// A node is dirty if its style or content has changed since the last
-    /// layout computation.
+    /// Operations run in order with the same semantics as the matching
+    /// methods (see `BatchOp`). A node created by the batch can be referenced
+    /// by later operations through the index of its `create` operation. All
+    /// operations and their styles are decoded before the first one runs. The
+    /// batch is all-or-nothing: if any operation fails, the edits made by the
+    /// earlier ones are reverted and the nodes they created are freed.
     ///
-    /// @param node - The node ID to check
+    /// Invalid style values are skipped as in `Style.set()`. Once the batch
+    /// has succeeded, they are recorded as warnings prefixed with the
+    /// operation's index (see `takeWarnings()`).
     ///
-    /// @returns - true if dirty, false otherwise
+    /// @param ops - The operations to apply
     ///
-    /// @throws `TaffyError` if the node does not exist
+    /// @returns - For each operation, the ID of the created node for `create`
+    /// operations and `undefined` otherwise
+    ///
+    /// @throws `TaffyError` with code `InvalidArgument` if `ops` is not an
+    /// array, or with code `BatchFailed` if an operation is malformed or
+    /// fails; `error.opIndex` identifies the operation and `error.cause`
+    /// holds the underlying error
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const rootId = tree.newLeaf(new Style());
-    /// const nodeId = rootId;
-    /// const availableSpace = { width: 100, height: 100 };
-    ///
-    /// if (tree.dirty(nodeId)) {
-    ///   tree.computeLayout(rootId, availableSpace);
-    /// }
+    /// const [root, label] = tree.applyBatch([
+    ///   { op: "create", style: { display: Display.Flex, width: 200 } },
+    ///   { op: "create", context: { text: "Hello" } },
+    ///   { op: "insert", parent: 0, child: 1 },
+    /// ]);
+    /// console.log(tree.children(root!)[0] === label); // true
     /// ```
-    #[wasm_bindgen(js_name = dirty)]
-    pub fn dirty(&self, node: u64) -> Result<bool, JsValue> {
-        map_bool_result(self.tree.dirty(NodeId::from(node)))
+    #[wasm_bindgen(js_name = applyBatch)]
+    pub fn apply_batch(&mut self, ops: JsBatchOpArray) -> Result<JsBatchResults, JsValue> {
+        let ops: JsValue = ops.into();
+        let ops = ops
+            .dyn_into::<Array>()
+            .map_err(|ops| JsTaffyError::invalid_argument("ops", &ops, "BatchOp[]"))?;
+
+        let mut creates = Vec::with_capacity(ops.length() as usize);
+        let mut decoded = Vec::with_capacity(ops.length() as usize);
+        for (i, value) in ops.iter().enumerate() {
+            let (name, op) = batch::decode_op(&value, i, &creates)
+                .map_err(|e| JsTaffyError::batch_failed(i, None, e.into()))?;
+            let style = Self::batch_style(&op)
+                .map_err(|e| JsTaffyError::batch_failed(i, Some(name.clone()), e))?;
+            creates.push(matches!(op, BatchOp::Create { .. }));
+            decoded.push((name, op, style));
+        }
+
+        self.begin_scope();
+        let mut created = Vec::with_capacity(decoded.len());
+        let mut warnings = Vec::new();
+        for (i, (name, op, mut style)) in decoded.into_iter().enumerate() {
+            let mut skipped = style
+                .as_mut()
+                .map(JsStyle::take_warnings)
+                .unwrap_or_default();
+            match self.run_batch_op(op, style, &created, &mut skipped) {
+                Ok(id) => created.push(id),
+                Err(cause) => {
+                    self.rollback_scope();
+                    return Err(JsTaffyError::batch_failed(i, Some(name), cause).into());
+                }
+            }
+            warnings.extend(
+                skipped
+                    .iter()
+                    .map(|w| format!("applyBatch(): ops[{i}]: {w}")),
+            );
+        }
+        self.commit_scope();
+        self.warnings.extend(warnings);
+
+        let results: Array = created
+            .into_iter()
+            .map(|id| id.map_or(JsValue::UNDEFINED, |id| BigInt::from(u64::from(id)).into()))
+            .collect();
+        Ok(JsValue::from(results).unchecked_into())
     }
 
     // =========================================================================
-    // Layout Computation
+    // Transactions
     // =========================================================================
 
-    /// Updates the stored layout of the provided node and its children
-    ///
-    /// The measure function is called for leaf nodes (nodes without children) that
-    /// require measurement according to the layout algorithm (Flexbox/Grid).
-    /// For example, this is used for text nodes or other content that has intrinsic size.
+    /// Starts a transaction
     ///
-    /// @param node - The root node ID to compute layout for
-    /// @param availableSpace - The available space constraints
-    /// @param measureFunc - A function that measures leaf node content
+    /// Every change made until the matching `commit()` or `rollback()` can be
+    /// undone as a whole: structure, styles, contexts, measure functions, the
+    /// rounding setting and the undo history. Changes are recorded as they are
+    /// made, so a transaction costs time and memory proportional to the
+    /// changes made inside it. Contexts are restored by reference; changes
+    /// made inside context objects are not undone.
     ///
-    /// @throws `TaffyError` if the node does not exist or available space is invalid
+    /// Transactions can be nested. Rolling back an inner transaction only
+    /// undoes the changes made since it started.
     ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
-    /// const rootId = tree.newLeaf(new Style());
+    /// const root = tree.newLeaf(new Style());
+    /// tree.beginTransaction();
+    /// try {
+    ///   tree.addChild(root, tree.newLeaf(new Style()));
+    ///   tree.commit();
+    /// } catch (e) {
+    ///   tree.rollback();
+    ///   throw e;
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = beginTransaction)]
+    pub fn begin_transaction(&mut self) {
+        self.begin_scope();
+    }
+
+    /// Keeps the changes made by the innermost transaction and ends it
+    ///
+    /// The changes can still be undone by rolling back an enclosing
+    /// transaction. Nodes removed inside transactions are freed once the
+    /// outermost transaction commits.
+    ///
+    /// @throws `TaffyError` with code `NoTransaction` if no transaction is open
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.beginTransaction();
+    /// tree.newLeaf(new Style());
+    /// tree.commit();
+    /// console.log(tree.totalNodeCount()); // 1
+    /// ```
+    #[wasm_bindgen(js_name = commit)]
+    pub fn commit(&mut self) -> Result<(), JsValue> {
+        if !self.journal.is_open() {
+            return Err(JsTaffyError::no_transaction("commit").into());
+        }
+        self.commit_scope();
+        Ok(())
+    }
+
+    /// Undoes the changes made by the innermost transaction and ends it
+    ///
+    /// The tree returns to its exact state when the transaction started. Nodes
+    /// created during the transaction are freed and their IDs become invalid,
+    /// and removed nodes come back with their original IDs.
+    ///
+    /// @throws `TaffyError` with code `NoTransaction` if no transaction is open
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const root = tree.newLeaf(new Style({ width: 100 }));
+    /// tree.beginTransaction();
+    /// tree.setStyle(root, new Style({ width: 200 }));
+    /// tree.remove(root);
+    /// tree.rollback();
+    /// console.log(tree.getStyle(root).get("width")); // 100
+    /// ```
+    #[wasm_bindgen(js_name = rollback)]
+    pub fn rollback(&mut self) -> Result<(), JsValue> {
+        if !self.journal.is_open() {
+            return Err(JsTaffyError::no_transaction("rollback").into());
+        }
+        self.rollback_scope();
+        Ok(())
+    }
+
+    /// Gets the number of open transactions
+    ///
+    /// @returns - `0` outside of any transaction, higher for nested transactions
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.beginTransaction();
+    /// console.log(tree.transactionDepth()); // 1
+    /// ```
+    #[wasm_bindgen(js_name = transactionDepth)]
+    pub fn transaction_depth(&self) -> usize {
+        self.journal.depth()
+    }
+
+    // =========================================================================
+    // History
+    // =========================================================================
+
+    /// Enables the undo/redo history, or changes its depth
+    ///
+    /// While enabled, the tree records how to revert style changes
+    /// (`setStyle()`, `setStyleIfChanged()`, `updateStyle()`), context
+    /// changes (`setNodeContext()`) and child edits (`addChild()`,
+    /// `insertChildAtIndex()`, `replaceChildAtIndex()`, `removeChild()`,
+    /// `removeChildAtIndex()`, `removeChildrenRange()`, `setChildren()`,
+    /// `moveNode()` and `remove()`), including edits made by `applyBatch()`.
+    /// Creating nodes is not recorded.
+    ///
+    /// Recorded edits are grouped into steps by `checkpoint()`. Nodes removed
+    /// by a step stay allocated until the step leaves the history, so undoing
+    /// brings them back under their original IDs.
+    ///
+    /// @param depth - Maximum number of steps `undo()` can revert (default: 100)
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory(50);
+    /// const root = tree.newLeaf(new Style());
+    /// tree.addChild(root, tree.newLeaf(new Style()));
+    /// tree.checkpoint("Add item");
+    /// tree.undo();
+    /// console.log(tree.childCount(root)); // 0
+    /// ```
+    #[wasm_bindgen(js_name = enableHistory)]
+    pub fn enable_history(&mut self, depth: Option<usize>) {
+        let depth = depth.unwrap_or(history::DEFAULT_DEPTH);
+        self.journal.save_history(self.history.as_ref());
+        match &mut self.history {
+            Some(history) => {
+                let dropped = history.set_depth(depth);
+                self.discard_steps(dropped);
+            }
+            None => self.history = Some(History::new(depth)),
+        }
+    }
+
+    /// Disables the undo/redo history and forgets all recorded steps
+    ///
+    /// Nodes removed by recorded steps are freed.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// tree.disableHistory();
+    /// console.log(tree.canUndo()); // false
+    /// ```
+    #[wasm_bindgen(js_name = disableHistory)]
+    pub fn disable_history(&mut self) {
+        self.journal.save_history(self.history.as_ref());
+        if let Some(history) = self.history.take() {
+            self.discard_steps(history.into_applied());
+        }
+    }
+
+    /// Seals the edits recorded since the last checkpoint into one undo step
+    ///
+    /// @param name - Optional label for the step, e.g. for an "Undo …" menu entry
+    ///
+    /// @returns - `false` if the history is disabled or nothing was recorded
+    /// since the last checkpoint
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// const node = tree.newLeaf(new Style());
+    /// tree.setStyle(node, new Style({ width: 100 }));
+    /// tree.checkpoint("Resize");
+    /// console.log(tree.undoName()); // "Resize"
+    /// ```
+    #[wasm_bindgen(js_name = checkpoint)]
+    pub fn checkpoint(&mut self, name: Option<String>) -> bool {
+        self.journal.save_history(self.history.as_ref());
+        let Some(dropped) = self.history.as_mut().and_then(|h| h.checkpoint(name)) else {
+            return false;
+        };
+        self.discard_steps(dropped);
+        true
+    }
+
+    /// Reverts the most recent step
+    ///
+    /// Edits recorded since the last checkpoint count as one unnamed step.
+    ///
+    /// @returns - `false` if there is nothing to undo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// const node = tree.newLeaf(new Style({ width: 10 }));
+    /// tree.setStyle(node, new Style({ width: 20 }));
+    /// tree.undo();
+    /// console.log(tree.getStyle(node).get("width")); // 10
+    /// ```
+    #[wasm_bindgen(js_name = undo)]
+    pub fn undo(&mut self) -> bool {
+        self.journal.save_history(self.history.as_ref());
+        let Some(step) = self.history.as_mut().and_then(History::take_undo) else {
+            return false;
+        };
+        step.undo(&mut Target {
+            tree: &mut self.tree,
+            nodes: &mut self.nodes,
+            measure_funcs: &mut self.measure_funcs,
+            rounding: &mut self.rounding,
+        });
+        if self.journal.is_open() {
+            for edit in step.applied(false) {
+                self.journal.record(edit);
+            }
+        }
+        if let Some(history) = &mut self.history {
+            history.push_redo(step);
+        }
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+        true
+    }
+
+    /// Reapplies the most recently undone step
+    ///
+    /// Recording a new edit clears the steps that can be redone.
+    ///
+    /// @returns - `false` if there is nothing to redo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// const node = tree.newLeaf(new Style({ width: 10 }));
+    /// tree.setStyle(node, new Style({ width: 20 }));
+    /// tree.undo();
+    /// tree.redo();
+    /// console.log(tree.getStyle(node).get("width")); // 20
+    /// ```
+    #[wasm_bindgen(js_name = redo)]
+    pub fn redo(&mut self) -> bool {
+        self.journal.save_history(self.history.as_ref());
+        let Some(step) = self.history.as_mut().and_then(History::take_redo) else {
+            return false;
+        };
+        step.redo(&mut Target {
+            tree: &mut self.tree,
+            nodes: &mut self.nodes,
+            measure_funcs: &mut self.measure_funcs,
+            rounding: &mut self.rounding,
+        });
+        if self.journal.is_open() {
+            for edit in step.applied(true) {
+                self.journal.record(edit);
+            }
+        }
+        if let Some(history) = &mut self.history {
+            history.push_undo(step);
+        }
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+        true
+    }
+
+    /// Checks whether `undo()` would revert a step
+    ///
+    /// @returns - `true` if there is a step to undo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// console.log(tree.canUndo()); // false
+    /// ```
+    #[wasm_bindgen(js_name = canUndo)]
+    pub fn can_undo(&self) -> bool {
+        self.history.as_ref().is_some_and(History::can_undo)
+    }
+
+    /// Checks whether `redo()` would reapply a step
+    ///
+    /// @returns - `true` if there is a step to redo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// console.log(tree.canRedo()); // false
+    /// ```
+    #[wasm_bindgen(js_name = canRedo)]
+    pub fn can_redo(&self) -> bool {
+        self.history.as_ref().is_some_and(History::can_redo)
+    }
+
+    /// Gets the name of the step `undo()` would revert
+    ///
+    /// @returns - The checkpoint name, or `undefined` for unnamed steps and
+    /// when there is nothing to undo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// const node = tree.newLeaf(new Style());
+    /// tree.setStyle(node, new Style({ flexGrow: 1 }));
+    /// console.log(tree.undoName()); // undefined, not checkpointed yet
+    /// tree.checkpoint("Grow");
+    /// console.log(tree.undoName()); // "Grow"
+    /// ```
+    #[wasm_bindgen(js_name = undoName)]
+    pub fn undo_name(&self) -> Option<String> {
+        self.history.as_ref()?.undo_name().map(str::to_string)
+    }
+
+    /// Gets the name of the step `redo()` would reapply
+    ///
+    /// @returns - The checkpoint name, or `undefined` for unnamed steps and
+    /// when there is nothing to redo
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableHistory();
+    /// const node = tree.newLeaf(new Style());
+    /// tree.setNodeContext(node, "label");
+    /// tree.checkpoint("Edit label");
+    /// tree.undo();
+    /// console.log(tree.redoName()); // "Edit label"
+    /// ```
+    #[wasm_bindgen(js_name = redoName)]
+    pub fn redo_name(&self) -> Option<String> {
+        self.history.as_ref()?.redo_name().map(str::to_string)
+    }
+
+    // =========================================================================
+    // Layout Results
+    // =========================================================================
+
+    /// Gets the computed layout for a node
+    ///
+    /// Call this after `computeLayout()` to retrieve the computed position
+    /// and size for a node.
+    ///
+    /// @param node - The node ID
+    ///
+    /// @returns - The computed `Layout`
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const style = new Style();
+    /// style.size = { width: 100, height: 100 };
+    /// const rootId = tree.newLeaf(style);
+    /// const nodeId = rootId;
+    ///
+    /// tree.computeLayout(rootId, { width: 800, height: 600 });
+    /// const layout: Layout = tree.getLayout(nodeId);
+    /// console.log(`Position: (${layout.x}, ${layout.y}), Size: ${layout.width}x${layout.height}`);
+    /// ```
+    #[wasm_bindgen(js_name = getLayout)]
+    pub fn layout(&self, node: u64) -> Result<JsLayout, JsValue> {
+        match self.tree.layout(self.existing(node)?) {
+            Ok(l) => Ok(JsLayout::from(l)),
+            Err(e) => Err(JsValue::from(JsTaffyError::from(e))),
+        }
+    }
+
+    /// Gets the unrounded (fractional) layout for a node
+    ///
+    /// Returns the raw computed values before any rounding is applied.
+    /// Useful when you need sub-pixel precision.
+    ///
+    /// @param node - The node ID
+    ///
+    /// @returns - The unrounded `Layout`
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const nodeId = tree.newLeaf(new Style());
+    /// const layout: Layout = tree.unroundedLayout(nodeId);
+    /// console.log(`Exact width: ${layout.width}`);
+    /// ```
+    #[wasm_bindgen(js_name = unroundedLayout)]
+    pub fn unrounded_layout(&self, node: u64) -> Result<JsLayout, JsValue> {
+        let node = self.existing(node)?;
+        Ok(JsLayout::from(self.tree.unrounded_layout(node)))
+    }
+
+    /// Gets the computed layouts of a whole subtree in one packed buffer
+    ///
+    /// Walks the subtree rooted at `root` in depth-first pre-order and writes
+    /// every node's layout into a single `Float32Array`, avoiding one `Layout`
+    /// object per node. See `LayoutBuffer` for the record format.
+    ///
+    /// @param root - The root node ID of the subtree
+    ///
+    /// @returns - A `LayoutBuffer` holding the node IDs and packed layouts
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const child = tree.newLeaf(new Style());
+    /// const root = tree.newWithChildren(new Style(), [child]);
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    ///
+    /// const buffer = tree.getLayoutsBuffer(root);
+    /// const data: Float32Array = buffer.data;
+    /// console.log(`Root width: ${data[2]}, nodes: ${buffer.length}`);
+    /// ```
+    #[wasm_bindgen(js_name = getLayoutsBuffer)]
+    pub fn layouts_buffer(&self, root: u64) -> Result<JsLayoutBuffer, JsValue> {
+        let root = self.existing(root)?;
+        let mut buffer = JsLayoutBuffer::with_capacity(self.tree.child_count(root) + 1);
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            let layout = self.tree.layout(node).map_err(to_js_error)?;
+            buffer.push(u64::from(node), &JsLayout::from(layout));
+            let first_child = stack.len();
+            stack.extend(self.tree.child_ids(node));
+            stack[first_child..].reverse();
+        }
+        Ok(buffer)
+    }
+
+    /// Gets the computed layout for a node with coordinates relative to the layout root
+    ///
+    /// The returned `Layout` is the same as `getLayout()`, except that `x` and
+    /// `y` are accumulated over all ancestors up to the topmost one. Scroll
+    /// offsets can be supplied per node: an ancestor's offset is subtracted
+    /// from the positions of everything inside it. The node's own scroll
+    /// offset does not move the node itself.
+    ///
+    /// @param node - The node ID
+    /// @param scrollOffsets - Optional map from node ID to that node's scroll offset
+    ///
+    /// @returns - The computed `Layout` with absolute `x` and `y`
+    ///
+    /// @throws `TaffyError` if the node does not exist or a scroll offset is not a `Point<number>`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const style = new Style();
+    /// style.padding = { left: 10, right: 10, top: 10, bottom: 10 };
+    /// const child = tree.newLeaf(new Style());
+    /// const root = tree.newWithChildren(style, [child]);
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    ///
+    /// const layout: Layout = tree.getAbsoluteLayout(child);
+    /// console.log(`Absolute position: (${layout.x}, ${layout.y})`);
+    ///
+    /// const scrolled = tree.getAbsoluteLayout(child, new Map([[root, { x: 0, y: 50 }]]));
+    /// console.log(`Scrolled position: (${scrolled.x}, ${scrolled.y})`);
+    /// ```
+    #[wasm_bindgen(js_name = getAbsoluteLayout)]
+    pub fn absolute_layout(
+        &self,
+        node: u64,
+        #[wasm_bindgen(js_name = "scrollOffsets")] scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<JsLayout, JsValue> {
+        let offsets = Self::scroll_offsets_map(scroll_offsets)?;
+        let node = self.existing(node)?;
+        let mut layout = JsLayout::from(self.tree.layout(node).map_err(to_js_error)?);
+        layout.inner.location = self.absolute_location(node, offsets.as_ref())?;
+        Ok(layout)
+    }
+
+    /// Gets the layouts of a whole subtree with coordinates relative to the layout root
+    ///
+    /// Combines `getLayoutsBuffer()` and `getAbsoluteLayout()`: the records are
+    /// in the same order and format, but `x` and `y` are absolute.
+    ///
+    /// @param root - The root node ID of the subtree
+    /// @param scrollOffsets - Optional map from node ID to that node's scroll offset
+    ///
+    /// @returns - A `LayoutBuffer` with absolute positions
+    ///
+    /// @throws `TaffyError` if the node does not exist or a scroll offset is not a `Point<number>`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const child = tree.newLeaf(new Style());
+    /// const root = tree.newWithChildren(new Style(), [child]);
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    ///
+    /// const buffer = tree.getAbsoluteLayoutsBuffer(root, new Map([[root, { x: 0, y: 100 }]]));
+    /// console.log(`Child y: ${buffer.data[buffer.stride + 1]}`);
+    /// ```
+    #[wasm_bindgen(js_name = getAbsoluteLayoutsBuffer)]
+    pub fn absolute_layouts_buffer(
+        &self,
+        root: u64,
+        #[wasm_bindgen(js_name = "scrollOffsets")] scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<JsLayoutBuffer, JsValue> {
+        let offsets = Self::scroll_offsets_map(scroll_offsets)?;
+        let root = self.existing(root)?;
+        let mut buffer = JsLayoutBuffer::with_capacity(self.tree.child_count(root) + 1);
+        let root_origin = self.subtree_origin(root, offsets.as_ref())?;
+        let mut stack = vec![(root, root_origin)];
+        while let Some((node, origin)) = stack.pop() {
+            let mut layout = JsLayout::from(self.tree.layout(node).map_err(to_js_error)?);
+            layout.inner.location.x += origin.x;
+            layout.inner.location.y += origin.y;
+            buffer.push(u64::from(node), &layout);
+
+            let scroll = Self::scroll_offset(offsets.as_ref(), node)?;
+            let child_origin = Point {
+                x: layout.inner.location.x - scroll.x,
+                y: layout.inner.location.y - scroll.y,
+            };
+            let first_child = stack.len();
+            stack.extend(self.tree.child_ids(node).map(|child| (child, child_origin)));
+            stack[first_child..].reverse();
+        }
+        Ok(buffer)
+    }
+
+    /// Finds the deepest node whose border box contains a point
+    ///
+    /// Coordinates are in the same space as `getAbsoluteLayout()`. Boxes
+    /// include their top/left edge but not their bottom/right edge.
+    ///
+    /// - Nodes with `display: none` and their subtrees are never hit
+    /// - When a node's overflow is not `Visible` on an axis (`Hidden`, `Clip`
+    ///   or `Scroll`), its descendants can only be hit inside its padding box
+    ///   on that axis
+    /// - Overlapping siblings are tested topmost first, by `Layout.order`
+    ///   and then by child index
+    ///
+    /// @param root - The node to start searching from
+    /// @param x - Horizontal position of the point
+    /// @param y - Vertical position of the point
+    /// @param scrollOffsets - Optional map from node ID to that node's scroll offset
+    ///
+    /// @returns - The ID of the deepest node at the point, or `undefined`
+    ///
+    /// @throws `TaffyError` if the node does not exist or a scroll offset is not a `Point<number>`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const buttonStyle = new Style();
+    /// buttonStyle.size = { width: 100, height: 40 };
+    /// const button = tree.newLeaf(buttonStyle);
+    /// const root = tree.newWithChildren(new Style(), [button]);
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    ///
+    /// const hit: bigint | undefined = tree.hitTest(root, 20, 10);
+    /// console.log(hit === button);
+    /// ```
+    #[wasm_bindgen(js_name = hitTest)]
+    pub fn hit_test(
+        &self,
+        root: u64,
+        x: f32,
+        y: f32,
+        #[wasm_bindgen(js_name = "scrollOffsets")] scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<Option<u64>, JsValue> {
+        let path = self.hit_test_path_ids(root, x, y, scroll_offsets)?;
+        Ok(path.last().map(|&id| u64::from(id)))
+    }
+
+    /// Finds the node at a point along with all of its ancestors
+    ///
+    /// Works like `hitTest()`, but returns the whole path from `root` down to
+    /// the deepest hit node.
+    ///
+    /// @param root - The node to start searching from
+    /// @param x - Horizontal position of the point
+    /// @param y - Vertical position of the point
+    /// @param scrollOffsets - Optional map from node ID to that node's scroll offset
+    ///
+    /// @returns - Node IDs from `root` to the deepest hit node, or an empty array
+    ///
+    /// @throws `TaffyError` if the node does not exist or a scroll offset is not a `Point<number>`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const itemStyle = new Style();
+    /// itemStyle.size = { width: 50, height: 50 };
+    /// const item = tree.newLeaf(itemStyle);
+    /// const root = tree.newWithChildren(new Style(), [item]);
+    /// tree.computeLayout(root, { width: 200, height: 200 });
+    ///
+    /// const path: bigint[] = tree.hitTestPath(root, 25, 25);
+    /// console.log(path); // [root, item]
+    /// ```
+    #[wasm_bindgen(js_name = hitTestPath)]
+    pub fn hit_test_path(
+        &self,
+        root: u64,
+        x: f32,
+        y: f32,
+        #[wasm_bindgen(js_name = "scrollOffsets")] scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<JsBigIntArray, JsValue> {
+        let path = self.hit_test_path_ids(root, x, y, scroll_offsets)?;
+        let array = Array::new();
+        for id in path {
+            array.push(&BigInt::from(u64::from(id)));
+        }
+        Ok(array.unchecked_into())
+    }
+
+    /// Finds all nodes whose boxes intersect a rectangle
+    ///
+    /// Intended for virtualised scrolling and culling. Coordinates are in the
+    /// same space as `getAbsoluteLayout()`. A node matches when its border box
+    /// overlaps the rectangle with a non-zero area.
+    ///
+    /// - Nodes with `display: none` and their subtrees are skipped
+    /// - Descendants of a node whose overflow is not `Visible` on an axis are
+    ///   only matched against the part of the rectangle inside its padding box,
+    ///   and are skipped entirely when nothing remains
+    ///
+    /// @param root - The node to start searching from
+    /// @param rect - The query rectangle
+    /// @param leavesOnly - If `true`, only nodes without children are returned
+    /// @param scrollOffsets - Optional map from node ID to that node's scroll offset
+    ///
+    /// @returns - Matching node IDs in depth-first pre-order
+    ///
+    /// @throws `TaffyError` if the node does not exist, `rect` is malformed, or a
+    /// scroll offset is not a `Point<number>`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const rowStyle = new Style();
+    /// rowStyle.size = { width: 300, height: 50 };
+    /// rowStyle.flexShrink = 0;
+    /// const rows = Array.from({ length: 100 }, () => tree.newLeaf(rowStyle));
+    /// const listStyle = new Style();
+    /// listStyle.flexDirection = FlexDirection.Column;
+    /// const list = tree.newWithChildren(listStyle, rows);
+    /// tree.computeLayout(list, { width: 300, height: "max-content" });
+    ///
+    /// const scrollOffsets = new Map([[list, { x: 0, y: 1000 }]]);
+    /// const visible: bigint[] = tree.queryRect(
+    ///   list,
+    ///   { x: 0, y: 0, width: 300, height: 400 },
+    ///   true,
+    ///   scrollOffsets,
+    /// );
+    /// console.log(`Visible rows: ${visible.length}`);
+    /// ```
+    #[wasm_bindgen(js_name = queryRect)]
+    pub fn query_rect(
+        &self,
+        root: u64,
+        rect: JsBoundingBoxArg,
+        #[wasm_bindgen(js_name = "leavesOnly")] leaves_only: Option<bool>,
+        #[wasm_bindgen(js_name = "scrollOffsets")] scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<JsBigIntArray, JsValue> {
+        let root = self.existing(root)?;
+        let r: BoundingBoxDto = decode_argument("rect", rect.into(), "BoundingBox")?;
+        let rect = Rect {
+            left: r.x,
+            right: r.x + r.width,
+            top: r.y,
+            bottom: r.y + r.height,
+        };
+        let leaves_only = leaves_only.unwrap_or(false);
+        let offsets = Self::scroll_offsets_map(scroll_offsets)?;
+
+        let array = Array::new();
+        let mut stack = vec![(root, self.subtree_origin(root, offsets.as_ref())?, rect)];
+        while let Some((node, origin, rect)) = stack.pop() {
+            let style = self.tree.style(node).map_err(to_js_error)?;
+            if style.display == Display::None {
+                continue;
+            }
+            let layout = self.tree.layout(node).map_err(to_js_error)?;
+            let left = origin.x + layout.location.x;
+            let top = origin.y + layout.location.y;
+            let right = left + layout.size.width;
+            let bottom = top + layout.size.height;
+            let has_children = self.tree.child_count(node) > 0;
+
+            let intersects =
+                left < rect.right && right > rect.left && top < rect.bottom && bottom > rect.top;
+            if intersects && !(leaves_only && has_children) {
+                array.push(&BigInt::from(u64::from(node)));
+            }
+            if !has_children {
+                continue;
+            }
+
+            let mut child_rect = rect;
+            if style.overflow.x != TaffyStyle::Overflow::Visible {
+                child_rect.left = child_rect.left.max(left + layout.border.left);
+                child_rect.right = child_rect.right.min(right - layout.border.right);
+            }
+            if style.overflow.y != TaffyStyle::Overflow::Visible {
+                child_rect.top = child_rect.top.max(top + layout.border.top);
+                child_rect.bottom = child_rect.bottom.min(bottom - layout.border.bottom);
+            }
+            if child_rect.left >= child_rect.right || child_rect.top >= child_rect.bottom {
+                continue;
+            }
+
+            let scroll = Self::scroll_offset(offsets.as_ref(), node)?;
+            let child_origin = Point {
+                x: left - scroll.x,
+                y: top - scroll.y,
+            };
+            let first_child = stack.len();
+            stack.extend(
+                self.tree
+                    .child_ids(node)
+                    .map(|child| (child, child_origin, child_rect)),
+            );
+            stack[first_child..].reverse();
+        }
+        Ok(array.unchecked_into())
+    }
+
+    /// Gets detailed layout information for grid layouts
+    ///
+    /// @note
+    /// This method is only available when the `detailed_layout_info`
+    /// feature is enabled.
+    ///
+    /// @param node - The node ID
+    ///
+    /// @returns - Detailed grid info or "None" for non-grid nodes
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    #[cfg(feature = "detailed_layout_info")]
+    #[wasm_bindgen(js_name = detailedLayoutInfo)]
+    pub fn detailed_layout_info(&self, node: u64) -> Result<JsValue, JsValue> {
+        match self.tree.detailed_layout_info(self.existing(node)?) {
+            DetailedLayoutInfo::Grid(info) => {
+                let dto = DetailedGridInfoDto {
+                    rows: DetailedGridTracksInfoDto {
+                        negative_implicit_tracks: info.rows.negative_implicit_tracks,
+                        explicit_tracks: info.rows.explicit_tracks,
+                        positive_implicit_tracks: info.rows.positive_implicit_tracks,
+                        gutters: info.rows.gutters.clone(),
+                        sizes: info.rows.sizes.clone(),
+                    },
+                    columns: DetailedGridTracksInfoDto {
+                        negative_implicit_tracks: info.columns.negative_implicit_tracks,
+                        explicit_tracks: info.columns.explicit_tracks,
+                        positive_implicit_tracks: info.columns.positive_implicit_tracks,
+                        gutters: info.columns.gutters.clone(),
+                        sizes: info.columns.sizes.clone(),
+                    },
+                    items: info
+                        .items
+                        .iter()
+                        .map(|item| DetailedGridItemsInfoDto {
+                            row_start: item.row_start,
+                            row_end: item.row_end,
+                            column_start: item.column_start,
+                            column_end: item.column_end,
+                        })
+                        .collect(),
+                };
+                Ok(serde_wasm_bindgen::to_value(&dto).unwrap_or(JsValue::NULL))
+            }
+            DetailedLayoutInfo::None => Ok(JsValue::NULL),
+        }
+    }
+
+    // =========================================================================
+    // Dirty Tracking
+    // =========================================================================
+
+    /// Marks a node as dirty (requiring re-layout)
+    ///
+    /// Use this when a node's content has changed but its style hasn't.
+    /// For example, when text content changes and needs remeasuring.
+    ///
+    /// @param node - The node ID to mark dirty
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const rootId = tree.newLeaf(new Style());
+    /// const nodeId = rootId;
+    /// const availableSpace = { width: 100, height: 100 };
+    ///
+    /// // After updating text content
+    /// tree.setNodeContext(nodeId, { text: "Updated text" });
+    /// tree.markDirty(nodeId);
+    /// tree.computeLayout(rootId, availableSpace);
+    /// ```
+    #[wasm_bindgen(js_name = markDirty)]
+    pub fn mark_dirty(&mut self, node: u64) -> Result<(), JsValue> {
+        let node = self.existing(node)?;
+        self.invalidate_measure(node);
+        map_void_result(self.tree.mark_dirty(node))
+    }
+
+    /// Checks if a node is dirty (needs re-layout)
+    ///
+    /// A node is dirty if its style or content has changed since the last
+    /// layout computation.
+    ///
+    /// @param node - The node ID to check
+    ///
+    /// @returns - true if dirty, false otherwise
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const rootId = tree.newLeaf(new Style());
+    /// const nodeId = rootId;
+    /// const availableSpace = { width: 100, height: 100 };
+    ///
+    /// if (tree.dirty(nodeId)) {
+    ///   tree.computeLayout(rootId, availableSpace);
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = dirty)]
+    pub fn dirty(&self, node: u64) -> Result<bool, JsValue> {
+        map_bool_result(self.tree.dirty(self.existing(node)?))
+    }
+
+    // =========================================================================
+    // Measure Functions
+    // =========================================================================
+
+    /// Sets the function that measures a leaf node's content
+    ///
+    /// Used by `computeLayout()` and, in place of its callback, by
+    /// `computeLayoutWithMeasure()`. Cloning the node with `cloneSubtree()`
+    /// copies the function. Marks the node dirty.
+    ///
+    /// @param node - The node ID
+    /// @param measureFunc - The function that measures the node
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const image = tree.newLeaf(new Style());
+    /// tree.setMeasureFunc(image, () => ({ width: 64, height: 48 }));
+    /// tree.computeLayout(image, { width: 800, height: 600 });
+    /// console.log(tree.getLayout(image).width); // 64
+    /// ```
+    #[wasm_bindgen(js_name = setMeasureFunc)]
+    pub fn set_measure_func(
+        &mut self,
+        node: u64,
+        #[wasm_bindgen(js_name = "measureFunc")] measure_func: JsMeasureFunctionArg,
+    ) -> Result<(), JsValue> {
+        let node = self.existing(node)?;
+        let func: js_sys::Function = measure_func.unchecked_into();
+        let before = self.measure_funcs.insert(node, func.clone());
+        self.journal.record(Edit::Measure {
+            node,
+            before,
+            after: Some(func),
+        });
+        self.invalidate_measure(node);
+        map_void_result(self.tree.mark_dirty(node))
+    }
+
+    /// Removes the measure function set with `setMeasureFunc()`
+    ///
+    /// Marks the node dirty if it had a measure function.
+    ///
+    /// @param node - The node ID
+    ///
+    /// @returns - `true` if the node had a measure function
+    ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const node = tree.newLeaf(new Style());
+    /// tree.setMeasureFunc(node, () => ({ width: 10, height: 10 }));
+    /// console.log(tree.clearMeasureFunc(node)); // true
+    /// ```
+    #[wasm_bindgen(js_name = clearMeasureFunc)]
+    pub fn clear_measure_func(&mut self, node: u64) -> Result<bool, JsValue> {
+        let node = self.existing(node)?;
+        let Some(before) = self.measure_funcs.remove(&node) else {
+            return Ok(false);
+        };
+        self.journal.record(Edit::Measure {
+            node,
+            before: Some(before),
+            after: None,
+        });
+        self.invalidate_measure(node);
+        map_void_result(self.tree.mark_dirty(node))?;
+        Ok(true)
+    }
+
+    /// Sets how measure functions are called
+    ///
+    /// Applies to functions set with `setMeasureFunc()` and to the
+    /// `computeLayoutWithMeasure()` callback. By default every call receives
+    /// new `knownDimensions` and `availableSpace` objects and a copy of the
+    /// node's style. On trees with many measured leaves, reusing the size
+    /// objects and leaving out the style avoids most of the cost of a call.
+    ///
+    /// Reused objects are overwritten by the next call, so measure functions
+    /// must copy any values they keep.
+    ///
+    /// @param options - Which arguments to reuse or leave out; omitted
+    /// options are reset to their defaults
+    ///
+    /// @throws `TaffyError` if `options` is not a valid `MeasureArgsOptions`
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.setMeasureArgs({ reuseObjects: true, style: false });
+    /// const text = tree.newLeafWithContext(new Style(), { text: "Hello" });
+    /// tree.setMeasureFunc(text, (known, available, node, context) => ({
+    ///   width: known.width ?? context.text.length * 8,
+    ///   height: known.height ?? 16,
+    /// }));
+    /// tree.computeLayout(text, { width: 800, height: 600 });
+    /// ```
+    #[wasm_bindgen(js_name = setMeasureArgs)]
+    pub fn set_measure_args(&mut self, options: JsMeasureArgsOptions) -> Result<(), JsValue> {
+        let options: MeasureArgsOptionsDto =
+            decode_argument("options", options.into(), "MeasureArgsOptions")?;
+        self.measure_args = MeasureArgs::new(options);
+        Ok(())
+    }
+
+    /// Enables native measurement of monospace text
+    ///
+    /// While enabled, leaves without their own measure function (see
+    /// `setMeasureFunc()`) whose context is a `TextContext` are measured in
+    /// terminal cells, without calling JavaScript. Text is split into
+    /// grapheme clusters, wide characters take two cells, and it is wrapped,
+    /// truncated or left as is according to `wrap`. At `min-content` widths
+    /// wrapping text is as wide as its longest word; at `max-content` widths
+    /// text is as wide as its longest line. Each line is one cell high.
+    ///
+    /// Other leaves are measured as before, including by the
+    /// `computeLayoutWithMeasure()` callback.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableTextMeasure();
+    /// const text = tree.newLeafWithContext(new Style(), {
+    ///   text: "Hello, 世界",
+    ///   wrap: "wrap",
+    /// });
+    /// const root = tree.newWithChildren(new Style({ width: 8 }), [text]);
+    /// tree.computeLayout(root, { width: 80, height: 24 });
+    /// console.log(tree.getLayout(text).height); // 2
+    /// ```
+    #[wasm_bindgen(js_name = enableTextMeasure)]
+    pub fn enable_text_measure(&mut self) {
+        self.text_measure = true;
+        self.mark_text_leaves_dirty();
+    }
+
+    /// Disables native measurement of monospace text
+    ///
+    /// Text leaves are then measured like any other leaf.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableTextMeasure();
+    /// tree.disableTextMeasure();
+    /// ```
+    #[wasm_bindgen(js_name = disableTextMeasure)]
+    pub fn disable_text_measure(&mut self) {
+        self.text_measure = false;
+        self.mark_text_leaves_dirty();
+    }
+
+    /// Enables memoization of measure function results
+    ///
+    /// While enabled, a leaf measured again with the same `knownDimensions`
+    /// and `availableSpace` reuses the previous result instead of calling the
+    /// measure function, also across layout passes. This helps when layouts
+    /// alternate between constraints, such as a container being resized back
+    /// and forth. A node's results are dropped when its style, context or
+    /// measure function changes, or when it is marked dirty with `markDirty()`.
+    /// All results are dropped when `computeLayoutWithMeasure()` is called
+    /// with a different callback than before. Up to 16 results are kept per
+    /// node; beyond that, the least recently used one is dropped.
+    ///
+    /// Measure functions must therefore depend only on their arguments and
+    /// the node's context and style.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableMeasureCache();
+    /// const text = tree.newLeafWithContext(new Style({ flexGrow: 1 }), { text: "Hello" });
+    /// tree.setMeasureFunc(text, () => ({ width: 40, height: 10 }));
+    /// const root = tree.newWithChildren(new Style({ width: "100%" }), [text]);
+    /// for (const width of [800, 400, 800]) {
+    ///   tree.computeLayout(root, { width, height: 600 });
+    /// }
+    /// console.log(tree.measureCacheStats()!.hits > 0); // true
+    /// ```
+    #[wasm_bindgen(js_name = enableMeasureCache)]
+    pub fn enable_measure_cache(&mut self) {
+        self.measure_cache.get_or_insert_with(MeasureCache::default);
+    }
+
+    /// Disables measure memoization and drops the cache and its counters
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableMeasureCache();
+    /// tree.disableMeasureCache();
+    /// console.log(tree.measureCacheStats()); // undefined
+    /// ```
+    #[wasm_bindgen(js_name = disableMeasureCache)]
+    pub fn disable_measure_cache(&mut self) {
+        self.measure_cache = None;
+    }
+
+    /// Drops all memoized measure results, keeping the counters
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableMeasureCache();
+    /// tree.clearMeasureCache();
+    /// ```
+    #[wasm_bindgen(js_name = clearMeasureCache)]
+    pub fn clear_measure_cache(&mut self) {
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+    }
+
+    /// Gets the measure cache counters
+    ///
+    /// @returns - Hits and misses since the cache was enabled, and the number
+    /// of cached results, or `undefined` if the cache is disabled
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableMeasureCache();
+    /// const { hits, misses } = tree.measureCacheStats()!;
+    /// console.log(hits / Math.max(1, hits + misses));
+    /// ```
+    #[wasm_bindgen(js_name = measureCacheStats)]
+    pub fn measure_cache_stats(&self) -> JsMeasureCacheStats {
+        match &self.measure_cache {
+            Some(cache) => serde_wasm_bindgen::to_value(&cache.stats())
+                .unwrap_or(JsValue::UNDEFINED)
+                .unchecked_into(),
+            None => JsValue::UNDEFINED.unchecked_into(),
+        }
+    }
+
+    // =========================================================================
+    // Layout Change Tracking
+    // =========================================================================
+
+    /// Enables reporting of nodes whose layout changed
+    ///
+    /// After each `computeLayout()` or `computeLayoutWithMeasure()` call, the
+    /// nodes of the computed subtree whose final position, size or content
+    /// size differs from before the call are reported, in depth-first
+    /// pre-order. They are passed to `callback` if given, and otherwise
+    /// collected until `takeChangedNodes()` is called. The callback is not
+    /// called for passes that changed nothing.
+    ///
+    /// The callback runs in a microtask after the layout call has returned,
+    /// so it can read layouts and edit the tree. Errors it throws are not
+    /// rethrown by the layout call; they are reported like other uncaught
+    /// errors.
+    ///
+    /// Comparing layouts costs a walk over the computed subtree per pass.
+    ///
+    /// @param callback - Optional function receiving the changed nodes of each pass
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const root = tree.newLeaf(new Style({ width: 100, height: 100 }));
+    /// tree.enableChangeTracking((nodes) => {
+    ///   for (const node of nodes) {
+    ///     console.log("repaint", node, tree.getLayout(node).width);
+    ///   }
+    /// });
+    /// tree.computeLayout(root, { width: 800, height: 600 }); // repaint root 100
+    /// tree.computeLayout(root, { width: 800, height: 600 }); // no call
+    /// ```
+    #[wasm_bindgen(js_name = enableChangeTracking)]
+    pub fn enable_change_tracking(&mut self, callback: Option<JsLayoutChangeCallbackArg>) {
+        let callback = callback.map(|callback| callback.unchecked_into::<js_sys::Function>());
+        match &mut self.layout_changes {
+            Some(changes) => changes.callback = callback,
+            None => {
+                self.layout_changes = Some(LayoutChanges {
+                    callback,
+                    queued: Vec::new(),
+                    queued_set: HashSet::new(),
+                })
+            }
+        }
+    }
+
+    /// Disables layout change reporting and drops uncollected changes
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// tree.enableChangeTracking();
+    /// tree.disableChangeTracking();
+    /// ```
+    #[wasm_bindgen(js_name = disableChangeTracking)]
+    pub fn disable_change_tracking(&mut self) {
+        self.layout_changes = None;
+    }
+
+    /// Returns and clears the nodes whose layout changed
+    ///
+    /// Collects the changes of every layout pass since the previous call,
+    /// each node once. Empty when change tracking is disabled or a callback
+    /// receives the changes.
+    ///
+    /// @returns - Changed node IDs, in the order they were first found
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const child = tree.newLeaf(new Style({ width: 50, height: 50 }));
+    /// const root = tree.newWithChildren(new Style(), [child]);
+    /// tree.enableChangeTracking();
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    /// console.log(tree.takeChangedNodes()); // [root, child]
+    ///
+    /// tree.setStyle(child, new Style({ width: 80, height: 50 }));
+    /// tree.computeLayout(root, { width: 800, height: 600 });
+    /// console.log(tree.takeChangedNodes()); // [root, child]
+    /// ```
+    #[wasm_bindgen(js_name = takeChangedNodes)]
+    pub fn take_changed_nodes(&mut self) -> JsBigIntArray {
+        let queued = match &mut self.layout_changes {
+            Some(changes) => {
+                changes.queued_set.clear();
+                std::mem::take(&mut changes.queued)
+            }
+            None => Vec::new(),
+        };
+        Self::node_array(&queued)
+    }
+
+    // =========================================================================
+    // Layout Computation
+    // =========================================================================
+
+    /// Updates the stored layout of the provided node and its children
+    ///
+    /// The measure function is called for leaf nodes (nodes without children) that
+    /// require measurement according to the layout algorithm (Flexbox/Grid).
+    /// For example, this is used for text nodes or other content that has intrinsic size.
+    /// Nodes with their own function (see `setMeasureFunc()`) are measured
+    /// with it instead.
+    ///
+    /// @param node - The root node ID to compute layout for
+    /// @param availableSpace - The available space constraints
+    /// @param measureFunc - A function that measures leaf node content
+    ///
+    /// @throws `TaffyError` if the node does not exist or available space is invalid,
+    /// or `MeasureFailed` if a measure function throws or returns an invalid size
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const rootId = tree.newLeaf(new Style());
     ///
     /// const measureText = (text: string, width: number) => ({ width: 0, height: 0 });
     ///
@@ -1011,59 +2552,18 @@ impl JsTaffyTree {
         #[wasm_bindgen(js_name = "availableSpace")] available_space: JsAvailableSizeArg,
         #[wasm_bindgen(js_name = "measureFunc")] measure_func: JsMeasureFunctionArg,
     ) -> Result<(), JsValue> {
-        let js_value: JsValue = available_space.unchecked_into();
-        let js_space = match serde_wasm_bindgen::from_value::<AvailableSizeDto>(js_value) {
-            Ok(s) => s,
-            Err(_) => {
-                return Err(JsValue::from(JsTaffyError::from(
-                    NativeTaffyError::InvalidInputNode(NodeId::from(node)),
-                )));
-            }
-        };
-
-        let space: Size<AvailableSpace> = js_space.into();
+        let space = Self::available_space(available_space.into())?;
         let func: js_sys::Function = measure_func.unchecked_into();
-        let measure = |known_dimensions: Size<Option<f32>>,
-                       available_space: Size<AvailableSpace>,
-                       _node: NodeId,
-                       context: Option<&mut JsValue>,
-                       _style: &TaffyStyle::Style|
-         -> Size<f32> {
-            let this = JsValue::NULL;
-            let known_val =
-                serde_wasm_bindgen::to_value(&known_dimensions).unwrap_or(JsValue::NULL);
-            let available_dto = AvailableSizeDto {
-                width: available_space.width.into(),
-                height: available_space.height.into(),
-            };
-            let available_val =
-                serde_wasm_bindgen::to_value(&available_dto).unwrap_or(JsValue::NULL);
-            let ctx = context.cloned().unwrap_or(JsValue::UNDEFINED);
-            let style = JsStyle {
-                inner: _style.clone(),
-            };
-            let style_val = JsValue::from(style);
-            let node_id: u64 = _node.into();
-            let node_val = JsValue::from(node_id);
-            let args = js_sys::Array::new();
-            args.push(&known_val);
-            args.push(&available_val);
-            args.push(&node_val);
-            args.push(&ctx);
-            args.push(&style_val);
-            let result_val = func.apply(&this, &args).unwrap_or(JsValue::UNDEFINED);
-            serde_wasm_bindgen::from_value(result_val).unwrap_or(Size::ZERO)
-        };
-        map_void_result(
-            self.tree
-                .compute_layout_with_measure(NodeId::from(node), space, measure),
-        )
+        let root = self.existing(node)?;
+        self.track_layout_changes(root, |this| this.run_layout(root, space, Some(&func)))
     }
 
     /// Computes the layout for a subtree
     ///
     /// This is the main layout computation method. Call this on the root node
-    /// to compute layouts for all nodes in the tree.
+    /// to compute layouts for all nodes in the tree. Leaves with a measure
+    /// function (see `setMeasureFunc()`) are measured with it; other leaves
+    /// have no content size.
     ///
     /// @param node - The root node ID to compute layout for
     /// @param availableSpace - The available space constraints
@@ -1083,7 +2583,8 @@ impl JsTaffyTree {
     /// tree.computeLayout(rootId, { width: "min-content", height: "min-content" });
     /// ```
     ///
-    /// @throws `TaffyError` if the node does not exist or available space is invalid
+    /// @throws `TaffyError` if the node does not exist or available space is invalid,
+    /// or `MeasureFailed` if a measure function throws or returns an invalid size
     ///
     /// @example
     /// ```typescript
@@ -1091,22 +2592,312 @@ impl JsTaffyTree {
     /// const rootId = tree.newLeaf(new Style());
     /// tree.computeLayout(rootId, { width: 800, height: 600 });
     /// ```
-    #[wasm_bindgen(js_name = computeLayout)]
-    pub fn compute_layout(
-        &mut self,
-        node: u64,
-        #[wasm_bindgen(js_name = "availableSpace")] available_space: JsAvailableSizeArg,
-    ) -> Result<(), JsValue> {
-        let js_value: JsValue = available_space.unchecked_into();
-        match serde_wasm_bindgen::from_value::<AvailableSizeDto>(js_value) {
-            Ok(js_space) => {
-                let space: Size<AvailableSpace> = js_space.into();
-                map_void_result(self.tree.compute_layout(NodeId::from(node), space))
+    #[wasm_bindgen(js_name = computeLayout)]
+    pub fn compute_layout(
+        &mut self,
+        node: u64,
+        #[wasm_bindgen(js_name = "availableSpace")] available_space: JsAvailableSizeArg,
+    ) -> Result<(), JsValue> {
+        let space = Self::available_space(available_space.into())?;
+        let root = self.existing(node)?;
+        self.track_layout_changes(root, |this| this.run_layout(root, space, None))
+    }
+
+    // =========================================================================
+    // Validation
+    // =========================================================================
+
+    /// Checks the integrity of a subtree
+    ///
+    /// Walks the subtree depth-first and reports every problem found. Each
+    /// issue names the child `node` and the `parent` whose child list
+    /// references it:
+    ///
+    /// - `cycle`: `node` is an ancestor of `parent`, or `parent` itself
+    /// - `multipleParents`: `node` is listed as a child more than once
+    /// - `danglingChild`: `node` no longer exists in the tree
+    /// - `parentMismatch`: `node`'s recorded parent is not `parent`
+    ///
+    /// The walk does not descend into a node twice, so it terminates even
+    /// when the subtree contains cycles.
+    ///
+    /// @param root - The root node ID of the subtree to check
+    ///
+    /// @returns - The issues found; empty if the subtree is a valid tree
+    ///
+    /// @throws `TaffyError` if the root node does not exist
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const shared = tree.newLeaf(new Style());
+    /// const a = tree.newWithChildren(new Style(), [shared]);
+    /// const b = tree.newWithChildren(new Style(), [shared]);
+    /// const root = tree.newWithChildren(new Style(), [a, b]);
+    ///
+    /// for (const issue of tree.validate(root)) {
+    ///   console.log(issue.kind, issue.node, issue.parent); // "multipleParents", ...
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = validate)]
+    pub fn validate(&self, root: u64) -> Result<JsTreeIssueArray, JsValue> {
+        let root = self.existing(root)?;
+        let issues = Array::new();
+        let report = |kind: &str, node: NodeId, parent: NodeId| {
+            let issue = Object::new();
+            let _ = Reflect::set(&issue, &"kind".into(), &kind.into());
+            let _ = Reflect::set(&issue, &"node".into(), &BigInt::from(u64::from(node)));
+            let _ = Reflect::set(&issue, &"parent".into(), &BigInt::from(u64::from(parent)));
+            issues.push(&issue);
+        };
+
+        let mut visited = HashSet::from([root]);
+        let mut path = HashSet::from([root]);
+        // Nodes on the current path, with the index of their next child
+        let mut stack = vec![(root, 0usize)];
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            if *next >= self.tree.child_count(node) {
+                path.remove(&node);
+                stack.pop();
+                continue;
+            }
+            let child = self.tree.get_child_id(node, *next);
+            *next += 1;
+
+            if !self.nodes.contains(&child) {
+                report("danglingChild", child, node);
+                continue;
+            }
+            if self.tree.parent(child) != Some(node) {
+                report("parentMismatch", child, node);
+            }
+            if path.contains(&child) {
+                report("cycle", child, node);
+            } else if !visited.insert(child) {
+                report("multipleParents", child, node);
+            } else {
+                path.insert(child);
+                stack.push((child, 0));
+            }
+        }
+        Ok(issues.unchecked_into())
+    }
+
+    // =========================================================================
+    // Snapshots
+    // =========================================================================
+
+    /// Serializes a subtree to a versioned, JSON-compatible snapshot
+    ///
+    /// The snapshot records the tree structure and every node's style, using
+    /// the same keys and value formats as `Style.get()`/`Style.set()`. Node
+    /// contexts and computed layouts can optionally be included. Pass the
+    /// result to `JSON.stringify()` to store it, and to `fromSnapshot()` to
+    /// rebuild the tree.
+    ///
+    /// @param root - The root node ID of the subtree
+    /// @param options - What to include besides structure and styles
+    ///
+    /// @returns - A `TreeSnapshot` document
+    ///
+    /// @throws `TaffyError` if the node does not exist or a node is reachable
+    /// through more than one parent
+    ///
+    /// @remarks
+    /// Contexts are stored as-is, so they must be JSON-compatible for the
+    /// snapshot to survive `JSON.stringify()`. Layouts are informational only
+    /// and are ignored by `fromSnapshot()`.
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const child = tree.newLeafWithContext(new Style({ width: 50 }), { text: "hello" });
+    /// const root = tree.newWithChildren(new Style({ display: Display.Flex }), [child]);
+    /// tree.computeLayout(root, { width: 200, height: 100 });
+    ///
+    /// const snapshot = tree.toSnapshot(root, { includeContext: true, includeLayout: true });
+    /// const json = JSON.stringify(snapshot);
+    /// console.log(json);
+    /// ```
+    #[wasm_bindgen(js_name = toSnapshot)]
+    pub fn to_snapshot(
+        &self,
+        root: u64,
+        options: Option<JsSnapshotOptions>,
+    ) -> Result<JsTreeSnapshot, JsValue> {
+        let options: SnapshotOptionsDto = match options {
+            Some(o) => decode_argument("options", o.into(), "SnapshotOptions")?,
+            None => SnapshotOptionsDto::default(),
+        };
+        let order = self
+            .subtree_preorder(self.existing(root)?)
+            .map_err(|node| {
+                JsTaffyError::invalid_snapshot(format!(
+                    "node {} is reachable more than once",
+                    u64::from(node)
+                ))
+            })?;
+        let index_of: HashMap<NodeId, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect();
+
+        let nodes = Array::new();
+        for &node in &order {
+            let style = JsStyle::from(self.tree.style(node).map_err(to_js_error)?.clone());
+            let children: Vec<usize> = self
+                .tree
+                .child_ids(node)
+                .map(|child| index_of[&child])
+                .collect();
+            let context = if options.include_context {
+                self.tree.get_node_context(node)
+            } else {
+                None
+            };
+            let layout = if options.include_layout {
+                Some(JsLayout::from(self.tree.layout(node).map_err(to_js_error)?))
+            } else {
+                None
+            };
+            nodes.push(&snapshot::encode_node(
+                &style,
+                &children,
+                context,
+                layout.as_ref(),
+            ));
+        }
+        Ok(snapshot::encode_document(0, nodes).unchecked_into())
+    }
+
+    /// Rebuilds a subtree from a snapshot created by `toSnapshot()`
+    ///
+    /// New nodes are created in this tree; existing nodes are not touched.
+    /// The snapshot is fully validated before any node is created.
+    ///
+    /// @param snapshot - A `TreeSnapshot` document, e.g. from `JSON.parse()`
+    ///
+    /// @returns - The ID of the restored root node
+    ///
+    /// @throws `TaffyError` if the snapshot has an unsupported version, an
+    /// unknown style property, or does not describe a single tree
+    ///
+    /// @example
+    /// ```typescript
+    /// const source = new TaffyTree();
+    /// const child = source.newLeaf(new Style({ width: 50 }));
+    /// const root = source.newWithChildren(new Style(), [child]);
+    /// const json = JSON.stringify(source.toSnapshot(root));
+    ///
+    /// const tree = new TaffyTree();
+    /// const restoredRoot: bigint = tree.fromSnapshot(JSON.parse(json));
+    /// console.log(tree.childCount(restoredRoot)); // 1
+    /// ```
+    #[wasm_bindgen(js_name = fromSnapshot)]
+    pub fn from_snapshot(&mut self, snapshot: JsTreeSnapshot) -> Result<u64, JsValue> {
+        let (root, nodes) = snapshot::decode_document(&snapshot.into())?;
+        let mut ids = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let style = node.style.inner.clone();
+            let id = match &node.context {
+                Some(context) => self.tree.new_leaf_with_context(style, context.clone()),
+                None => self.tree.new_leaf(style),
+            };
+            let id = id.map_err(to_js_error)?;
+            ids.push(self.created(id));
+        }
+        for (node, &id) in nodes.iter().zip(&ids) {
+            if !node.children.is_empty() {
+                let children: Vec<NodeId> = node.children.iter().map(|&c| ids[c]).collect();
+                self.tree.set_children(id, &children).map_err(to_js_error)?;
+            }
+        }
+        Ok(u64::from(ids[root]))
+    }
+
+    /// Serializes a subtree to a compact binary snapshot
+    ///
+    /// The binary format records the tree structure and every node's style,
+    /// like `toSnapshot()` without contexts or layouts, but is much smaller
+    /// and faster to save and load for large trees.
+    ///
+    /// @param root - The root node ID of the subtree
+    ///
+    /// @returns - The encoded bytes
+    ///
+    /// @throws `TaffyError` if the node does not exist or a node is reachable
+    /// through more than one parent
+    ///
+    /// @example
+    /// ```typescript
+    /// const tree = new TaffyTree();
+    /// const child = tree.newLeaf(new Style({ width: 50 }));
+    /// const root = tree.newWithChildren(new Style({ display: Display.Flex }), [child]);
+    ///
+    /// const bytes: Uint8Array = tree.toBytes(root);
+    /// console.log(bytes.length);
+    /// ```
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self, root: u64) -> Result<Vec<u8>, JsValue> {
+        let order = self
+            .subtree_preorder(self.existing(root)?)
+            .map_err(|node| {
+                JsTaffyError::invalid_snapshot(format!(
+                    "node {} is reachable more than once",
+                    u64::from(node)
+                ))
+            })?;
+        let mut nodes = Vec::with_capacity(order.len());
+        for &node in &order {
+            let style = self.tree.style(node).map_err(to_js_error)?;
+            nodes.push((style, self.tree.child_count(node)));
+        }
+        Ok(binary::encode(&nodes))
+    }
+
+    /// Rebuilds a subtree from bytes created by `toBytes()`
+    ///
+    /// New nodes are created in this tree; existing nodes are not touched.
+    /// The input is fully validated before any node is created.
+    ///
+    /// @param bytes - A binary snapshot
+    ///
+    /// @returns - The ID of the restored root node
+    ///
+    /// @throws `TaffyError` if the header or version does not match, or the
+    /// input is truncated or malformed
+    ///
+    /// @example
+    /// ```typescript
+    /// const source = new TaffyTree();
+    /// const child = source.newLeaf(new Style({ width: 50 }));
+    /// const root = source.newWithChildren(new Style(), [child]);
+    /// const bytes = source.toBytes(root);
+    ///
+    /// const tree = new TaffyTree();
+    /// const restoredRoot: bigint = tree.fromBytes(bytes);
+    /// console.log(tree.childCount(restoredRoot)); // 1
+    /// ```
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(&mut self, bytes: &[u8]) -> Result<u64, JsValue> {
+        let nodes = binary::decode(bytes)?;
+        let mut ids = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let id = self
+                .tree
+                .new_leaf(node.style.clone())
+                .map_err(to_js_error)?;
+            ids.push(self.created(id));
+        }
+        for (node, &id) in nodes.iter().zip(&ids) {
+            if !node.children.is_empty() {
+                let children: Vec<NodeId> = node.children.iter().map(|&c| ids[c]).collect();
+                self.tree.set_children(id, &children).map_err(to_js_error)?;
             }
-            Err(_) => Err(JsValue::from(JsTaffyError::from(
-                NativeTaffyError::InvalidInputNode(NodeId::from(node)),
-            ))),
         }
+        Ok(u64::from(ids[0]))
     }
 
     // =========================================================================
@@ -1122,6 +2913,8 @@ impl JsTaffyTree {
     ///
     /// @returns - A string representation of the tree structure
     ///
+    /// @throws `TaffyError` if the node does not exist
+    ///
     /// @example
     /// ```typescript
     /// const tree = new TaffyTree();
@@ -1130,9 +2923,9 @@ impl JsTaffyTree {
     /// console.log(output);
     /// ```
     #[wasm_bindgen(js_name = printTree)]
-    pub fn print_tree(&self, node: u64) -> String {
+    pub fn print_tree(&self, node: u64) -> Result<String, JsValue> {
         let tree = &self.tree;
-        let root_id = NodeId::from(node);
+        let root_id = self.existing(node)?;
 
         fn print_node(
             tree: &TaffyTree<JsValue>,
@@ -1184,6 +2977,1057 @@ impl JsTaffyTree {
             result + &child_output
         }
 
-        print_node(tree, root_id, false, String::new())
+        Ok(print_node(tree, root_id, false, String::new()))
+    }
+}
+
+// =============================================================================
+// Internal Helpers
+// =============================================================================
+
+/// Layout change tracking state, see `enableChangeTracking()`
+struct LayoutChanges {
+    /// Receives the changed nodes of each pass instead of `queued`
+    callback: Option<js_sys::Function>,
+    /// Changed nodes not yet taken, in the order they were found
+    queued: Vec<NodeId>,
+    /// The members of `queued`
+    queued_set: HashSet<NodeId>,
+}
+
+/// The parts of a layout whose changes are reported
+type LayoutKey = (Point<f32>, Size<f32>, Size<f32>);
+
+/// A node of a subtree copied by `cloneSubtree()`, ready to be recreated
+struct NodeCopy {
+    style: Style,
+    context: Option<JsValue>,
+    measure: Option<js_sys::Function>,
+    /// Indices of the node's children in the copied pre-order
+    children: Vec<usize>,
+}
+
+/// A subtree copied by `cloneSubtreeInto()`, waiting for the target tree
+struct CloneTransfer {
+    /// The tree the subtree was copied from, only compared by address
+    source: *const JsTaffyTree,
+    copies: Vec<NodeCopy>,
+}
+
+thread_local! {
+    /// The subtree of the `cloneSubtreeInto()` call in progress
+    ///
+    /// The target is reached through its JavaScript object, so that the
+    /// source can check it is not itself before the target is borrowed
+    /// mutably; the bindings would otherwise reject the second borrow.
+    static CLONE_TRANSFER: RefCell<Option<CloneTransfer>> = const { RefCell::new(None) };
+}
+
+impl JsTaffyTree {
+    /// Enables or disables rounding, recording the change
+    fn set_rounding(&mut self, rounding: bool) {
+        self.journal.record(Edit::Rounding {
+            before: self.rounding,
+            after: rounding,
+        });
+        self.rounding = rounding;
+        if rounding {
+            self.tree.enable_rounding();
+        } else {
+            self.tree.disable_rounding();
+        }
+    }
+
+    /// Detaches and hides every node instead of freeing it, so that rolling
+    /// back the open transaction brings the tree back
+    fn clear_undoably(&mut self) {
+        self.journal.save_history(self.history.as_ref());
+        let parents: Vec<NodeId> = (self.nodes.iter().copied())
+            .filter(|&node| self.tree.child_count(node) > 0)
+            .collect();
+        let before = self.child_lists(&parents);
+        for &parent in &parents {
+            let _ = self.tree.set_children(parent, &[]);
+        }
+        let after = self.child_lists(&parents);
+        self.journal.record(Edit::Children { before, after });
+        for node in std::mem::take(&mut self.nodes) {
+            self.journal.record(Edit::Removed(node));
+        }
+        if let Some(history) = self.history.take() {
+            self.history = Some(History::new(history.depth()));
+            self.discard_steps(history.into_applied());
+        }
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+    }
+
+    /// Replaces a node's style, recording the edit
+    fn write_style(&mut self, node: NodeId, style: Style) -> Result<(), JsValue> {
+        self.invalidate_measure(node);
+        if !self.recording() {
+            return map_void_result(self.tree.set_style(node, style));
+        }
+        let before = self.tree.style(node).map_err(to_js_error)?.clone();
+        self.tree
+            .set_style(node, style.clone())
+            .map_err(to_js_error)?;
+        self.record(Edit::Style {
+            node,
+            before: Box::new(before),
+            after: Box::new(style),
+        });
+        Ok(())
+    }
+
+    /// Replaces a node's context, recording the edit
+    fn write_context(&mut self, node: NodeId, context: Option<JsValue>) -> Result<(), JsValue> {
+        self.invalidate_measure(node);
+        if !self.recording() {
+            return map_void_result(self.tree.set_node_context(node, context));
+        }
+        let before = self.tree.get_node_context(node).cloned();
+        self.tree
+            .set_node_context(node, context.clone())
+            .map_err(to_js_error)?;
+        self.record(Edit::Context {
+            node,
+            before,
+            after: context,
+        });
+        Ok(())
+    }
+
+    /// Runs an edit of `parent`'s children, recording it
+    ///
+    /// `attached` are the nodes the edit gives to `parent`; the child lists of
+    /// their previous parents are recorded as well.
+    fn record_links<T>(
+        &mut self,
+        parent: NodeId,
+        attached: &[NodeId],
+        edit: impl FnOnce(&mut Self) -> Result<T, JsValue>,
+    ) -> Result<T, JsValue> {
+        if !self.recording() {
+            return edit(self);
+        }
+        let mut parents = vec![parent];
+        for &child in attached {
+            if let Some(previous) = self.tree.parent(child) {
+                if !parents.contains(&previous) {
+                    parents.push(previous);
+                }
+            }
+        }
+        let before = self.child_lists(&parents);
+        let result = edit(self)?;
+        let after = self.child_lists(&parents);
+        if before != after {
+            self.record(Edit::Children { before, after });
+        }
+        Ok(result)
+    }
+
+    /// Removes a node but keeps it allocated, so that undoing restores its ID
+    ///
+    /// The node is detached from its parent and children and hidden from the
+    /// tree; it is freed once the removal can no longer be undone.
+    fn remove_undoably(&mut self, node: NodeId) -> Result<(), JsValue> {
+        let parents: Vec<NodeId> = self.tree.parent(node).into_iter().chain([node]).collect();
+        let before = self.child_lists(&parents);
+        if let Some(parent) = self.tree.parent(node) {
+            self.tree.remove_child(parent, node).map_err(to_js_error)?;
+        }
+        self.tree.set_children(node, &[]).map_err(to_js_error)?;
+        let after = self.child_lists(&parents);
+        self.nodes.remove(&node);
+        self.record(Edit::Children { before, after });
+        self.record(Edit::Removed(node));
+        Ok(())
+    }
+
+    /// Whether edits are recorded, by the history or a running batch
+    fn recording(&self) -> bool {
+        self.history.is_some() || self.journal.is_open()
+    }
+
+    /// Records an applied edit in the history and the journal
+    fn record(&mut self, edit: Edit) {
+        let Some(history) = &mut self.history else {
+            self.journal.record(edit);
+            return;
+        };
+        if self.journal.is_open() {
+            // Recording drops the redo steps, which a rollback has to restore
+            if history.can_redo() {
+                self.journal.save_history(Some(history));
+            }
+            self.journal.record(edit.clone());
+        }
+        history.record(edit);
+    }
+
+    /// Frees the nodes removed by steps that left the history
+    ///
+    /// While a batch runs, the nodes are only freed once it has succeeded.
+    fn discard_steps(&mut self, steps: impl IntoIterator<Item = history::Step>) {
+        let removed: Vec<NodeId> = steps.into_iter().flat_map(|s| s.removed_nodes()).collect();
+        if self.journal.is_open() {
+            self.journal.release(removed);
+        } else {
+            self.free_removed(removed);
+        }
+    }
+
+    /// Frees removed nodes that can no longer be brought back
+    ///
+    /// Nodes that are back in the tree, still linked to a parent or children,
+    /// or still held by the history are kept: freeing them would silently
+    /// edit the live tree or break undo. A node removed again after undoing
+    /// its removal is listed twice, but freed once.
+    fn free_removed(&mut self, removed: Vec<NodeId>) {
+        if removed.is_empty() {
+            return;
+        }
+        let held = self.history.as_ref().map(History::held_nodes);
+        let mut seen = HashSet::with_capacity(removed.len());
+        for node in removed {
+            if !seen.insert(node)
+                || self.nodes.contains(&node)
+                || held.as_ref().is_some_and(|held| held.contains(&node))
+                || self.tree.parent(node).is_some()
+                || self.tree.child_count(node) > 0
+            {
+                continue;
+            }
+            let _ = self.tree.remove(node);
+            self.measure_funcs.remove(&node);
+        }
+    }
+
+    /// Starts recording edits so that they can be rolled back
+    fn begin_scope(&mut self) {
+        self.journal.begin(self.history.as_ref());
+    }
+
+    /// Keeps the edits of the innermost scope
+    fn commit_scope(&mut self) {
+        if let Some(removed) = self.journal.commit() {
+            self.free_removed(removed);
+        }
+    }
+
+    /// Reverts the edits of the innermost scope and frees the nodes it created
+    fn rollback_scope(&mut self) {
+        let mut target = Target {
+            tree: &mut self.tree,
+            nodes: &mut self.nodes,
+            measure_funcs: &mut self.measure_funcs,
+            rounding: &mut self.rounding,
+        };
+        let Some(created) = self.journal.rollback(&mut target, &mut self.history) else {
+            return;
+        };
+        for node in created {
+            let _ = self.tree.remove(node);
+            self.measure_funcs.remove(&node);
+        }
+        if let Some(cache) = &mut self.measure_cache {
+            cache.clear();
+        }
+    }
+
+    /// Computes the layout of a subtree
+    ///
+    /// Leaves are measured with their own measure function, then as text if
+    /// enabled, then with `fallback`; otherwise they have no content size.
+    fn run_layout(
+        &mut self,
+        root: NodeId,
+        space: Size<AvailableSpace>,
+        fallback: Option<&js_sys::Function>,
+    ) -> Result<(), JsValue> {
+        if self.measure_funcs.is_empty() && fallback.is_none() && !self.text_measure {
+            return map_void_result(self.tree.compute_layout(root, space));
+        }
+        if let (Some(cache), Some(fallback)) = (&mut self.measure_cache, fallback) {
+            cache.set_fallback(fallback);
+        }
+        let funcs = &self.measure_funcs;
+        let text_measure = self.text_measure;
+        let args = &self.measure_args;
+        let mut cache = self.measure_cache.as_mut();
+        // Taffy cannot abort a layout pass, so after the first failure the
+        // remaining leaves are skipped and the error is returned afterwards
+        let mut failure: Option<(NodeId, JsValue)> = None;
+        let mut skipped = Vec::new();
+        let measure = |known_dimensions: Size<Option<f32>>,
+                       available_space: Size<AvailableSpace>,
+                       node: NodeId,
+                       context: Option<&mut JsValue>,
+                       style: &TaffyStyle::Style|
+         -> Size<f32> {
+            if failure.is_some() {
+                skipped.push(node);
+                return Size::ZERO;
+            }
+            let own = funcs.get(&node);
+            if own.is_none() && text_measure {
+                match context.as_deref().map(TextContent::from_context) {
+                    Some(Ok(Some(text))) => return text.measure(known_dimensions, available_space),
+                    Some(Err(error)) => {
+                        failure = Some((node, error.into()));
+                        return Size::ZERO;
+                    }
+                    _ => {}
+                }
+            }
+            let Some(func) = own.or(fallback) else {
+                return Size::ZERO;
+            };
+            let call = || {
+                args.call(
+                    func,
+                    known_dimensions,
+                    available_space,
+                    node,
+                    context.as_deref(),
+                    style,
+                )
+            };
+            let result = match cache.as_deref_mut() {
+                Some(cache) => cache.measure(node, known_dimensions, available_space, call),
+                None => call(),
+            };
+            result.unwrap_or_else(|cause| {
+                failure = Some((node, cause));
+                Size::ZERO
+            })
+        };
+        map_void_result(self.tree.compute_layout_with_measure(root, space, measure))?;
+
+        let Some((node, cause)) = failure else {
+            return Ok(());
+        };
+        // Leaves sized as zero must be measured again by the next pass
+        for leaf in skipped.into_iter().chain([node]) {
+            let _ = self.tree.mark_dirty(leaf);
+        }
+        Err(JsTaffyError::measure_failed(node, cause).into())
+    }
+
+    /// Marks leaves that may be measured as text dirty, so that their cached
+    /// layouts are recomputed after text measurement is toggled
+    fn mark_text_leaves_dirty(&mut self) {
+        let leaves: Vec<NodeId> = self
+            .nodes
+            .iter()
+            .copied()
+            .filter(|&node| self.tree.child_count(node) == 0)
+            .filter(|&node| self.tree.get_node_context(node).is_some())
+            .collect();
+        for node in leaves {
+            let _ = self.tree.mark_dirty(node);
+        }
+    }
+
+    /// Drops the memoized measure results of a node
+    fn invalidate_measure(&mut self, node: NodeId) {
+        if let Some(cache) = &mut self.measure_cache {
+            cache.invalidate(node);
+        }
+    }
+
+    /// Runs a layout pass and reports the nodes whose layout it changed
+    fn track_layout_changes(
+        &mut self,
+        root: NodeId,
+        compute: impl FnOnce(&mut Self) -> Result<(), JsValue>,
+    ) -> Result<(), JsValue> {
+        if self.layout_changes.is_none() {
+            return compute(self);
+        }
+        let before: HashMap<NodeId, LayoutKey> = self.layout_keys(root).into_iter().collect();
+        compute(self)?;
+        let after = self.layout_keys(root);
+        let changed: Vec<NodeId> = after
+            .into_iter()
+            .filter(|(node, key)| before.get(node) != Some(key))
+            .map(|(node, _)| node)
+            .collect();
+
+        let Some(changes) = &mut self.layout_changes else {
+            return Ok(());
+        };
+        match &changes.callback {
+            Some(_) if changed.is_empty() => {}
+            // Called once this call has returned, as the callback may use the tree
+            Some(callback) => {
+                let nodes = Self::node_array(&changed);
+                queue_microtask(&callback.bind1(&JsValue::NULL, &nodes));
+            }
+            None => {
+                for node in changed {
+                    if changes.queued_set.insert(node) {
+                        changes.queued.push(node);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the reported layout parts of a subtree, in depth-first pre-order
+    fn layout_keys(&self, root: NodeId) -> Vec<(NodeId, LayoutKey)> {
+        let mut keys = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if let Ok(layout) = self.tree.layout(node) {
+                keys.push((node, (layout.location, layout.size, layout.content_size)));
+            }
+            let children = self.tree.children(node).unwrap_or_default();
+            stack.extend(children.into_iter().rev());
+        }
+        keys
+    }
+
+    /// Converts node IDs to a JavaScript `bigint[]`
+    fn node_array(nodes: &[NodeId]) -> JsBigIntArray {
+        let array: Array = nodes
+            .iter()
+            .map(|&node| JsValue::from(BigInt::from(u64::from(node))))
+            .collect();
+        array.unchecked_into()
+    }
+
+    /// Gets the current child lists of `parents`
+    fn child_lists(&self, parents: &[NodeId]) -> Vec<(NodeId, Vec<NodeId>)> {
+        parents
+            .iter()
+            .map(|&parent| (parent, self.tree.children(parent).unwrap_or_default()))
+            .collect()
+    }
+
+    /// Records a newly created node and returns its ID
+    fn created(&mut self, id: NodeId) -> NodeId {
+        self.nodes.insert(id);
+        self.journal.created(id);
+        id
+    }
+
+    /// Creates a node with the given children, as `newWithChildren()` does
+    fn create_with_children(
+        &mut self,
+        style: &JsStyle,
+        children: &[NodeId],
+    ) -> Result<u64, JsValue> {
+        self.check_edit(None, children, false)?;
+        if !self.journal.is_open() {
+            return map_node_result(
+                self.tree
+                    .new_with_children(style.inner.clone(), children)
+                    .map(|id| self.created(id)),
+            );
+        }
+        // Attached as a recorded edit, so that rolling back gives the
+        // children back to their previous parents
+        let id = self
+            .tree
+            .new_leaf(style.inner.clone())
+            .map_err(to_js_error)?;
+        let id = self.created(id);
+        let mut parents = vec![id];
+        for &child in children {
+            if let Some(previous) = self.tree.parent(child) {
+                if !parents.contains(&previous) {
+                    parents.push(previous);
+                }
+            }
+        }
+        let before = self.child_lists(&parents);
+        self.tree.set_children(id, children).map_err(to_js_error)?;
+        let after = self.child_lists(&parents);
+        self.journal.record(Edit::Children { before, after });
+        Ok(u64::from(id))
+    }
+
+    /// Replaces the children of `parent`, as `setChildren()` does
+    fn replace_children(&mut self, parent: NodeId, children: &[NodeId]) -> Result<(), JsValue> {
+        self.check_edit(Some(parent), children, true)?;
+        self.record_links(parent, children, |this| {
+            map_void_result(this.tree.set_children(parent, children))
+        })
+    }
+
+    /// Decodes the style of a `create` or `setStyle` batch operation
+    fn batch_style(op: &BatchOp) -> Result<Option<JsStyle>, JsValue> {
+        let (BatchOp::Create { style, .. } | BatchOp::SetStyle { style, .. }) = op else {
+            return Ok(None);
+        };
+        JsStyle::new(Some(style.clone())).map(Some)
+    }
+
+    /// Runs a decoded `applyBatch()` operation
+    ///
+    /// `style` is the decoded style of `create` and `setStyle` operations, and
+    /// `created` holds, for every earlier operation, the node it created.
+    /// Values skipped by `updateStyle` operations are added to `warnings`.
+    ///
+    /// @returns - The created node for `create` operations
+    fn run_batch_op(
+        &mut self,
+        op: BatchOp,
+        style: Option<JsStyle>,
+        created: &[Option<NodeId>],
+        warnings: &mut Vec<String>,
+    ) -> Result<Option<NodeId>, JsValue> {
+        let resolve = |node: NodeRef| -> Result<u64, JsValue> {
+            let id = match node {
+                NodeRef::Id(id) => id,
+                // Decoding only accepts indices of earlier `create` operations
+                NodeRef::Created(i) => created[i].expect("create operation without a node"),
+            };
+            self.check_exists(id)?;
+            Ok(u64::from(id))
+        };
+        let resolve_all = |nodes: Vec<NodeRef>| -> Result<Vec<NodeId>, JsValue> {
+            nodes
+                .into_iter()
+                .map(|node| resolve(node).map(NodeId::from))
+                .collect()
+        };
+        // Decoding gives every `create` and `setStyle` operation a style
+        let style = style.unwrap_or_else(|| JsStyle::from(Style::default()));
+
+        match op {
+            BatchOp::Create {
+                context, children, ..
+            } => {
+                let children = resolve_all(children)?;
+                let id = match context {
+                    None if children.is_empty() => self.new_leaf(&style)?,
+                    Some(context) if children.is_empty() => {
+                        self.new_leaf_with_context(&style, context)?
+                    }
+                    context => {
+                        let id = self.create_with_children(&style, &children)?;
+                        if let Some(context) = context {
+                            self.set_node_context(id, context)?;
+                        }
+                        id
+                    }
+                };
+                return Ok(Some(NodeId::from(id)));
+            }
+            BatchOp::Insert {
+                parent,
+                child,
+                index: None,
+            } => self.add_child(resolve(parent)?, resolve(child)?)?,
+            BatchOp::Insert {
+                parent,
+                child,
+                index: Some(index),
+            } => self.insert_child_at_index(resolve(parent)?, index, resolve(child)?)?,
+            BatchOp::RemoveChild { parent, child } => {
+                self.remove_child(resolve(parent)?, resolve(child)?)?;
+            }
+            BatchOp::Remove { node } => {
+                self.remove(resolve(node)?)?;
+            }
+            BatchOp::Move {
+                node,
+                parent,
+                index,
+            } => self.move_node(resolve(node)?, resolve(parent)?, index)?,
+            BatchOp::SetChildren { parent, children } => {
+                let parent = NodeId::from(resolve(parent)?);
+                self.replace_children(parent, &resolve_all(children)?)?
+            }
+            BatchOp::SetStyle { node, .. } => self.set_style(resolve(node)?, &style)?,
+            BatchOp::UpdateStyle { node, style: props } => {
+                let node = NodeId::from(resolve(node)?);
+                let current = self.tree.style(node).map_err(to_js_error)?;
+                let (updated, skipped) = JsStyle::update(current, props, None)?;
+                warnings.extend(skipped);
+                if let Some(style) = updated {
+                    self.write_style(node, style)?;
+                }
+            }
+            BatchOp::SetContext { node, context } => {
+                self.set_node_context(resolve(node)?, context)?
+            }
+            BatchOp::MarkDirty { node } => self.mark_dirty(resolve(node)?)?,
+        }
+        Ok(None)
+    }
+
+    /// Converts a node ID argument, failing if the node does not exist
+    ///
+    /// Nodes removed while the history is enabled stay allocated until the
+    /// removal can no longer be undone, so every method taking a node ID
+    /// checks it against the tree's node set rather than relying on Taffy.
+    fn existing(&self, node: u64) -> Result<NodeId, JsValue> {
+        let node = NodeId::from(node);
+        self.check_exists(node)?;
+        Ok(node)
+    }
+
+    /// Fails if `node` does not exist in the tree
+    fn check_exists(&self, node: NodeId) -> Result<(), JsValue> {
+        if self.nodes.contains(&node) {
+            Ok(())
+        } else {
+            Err(to_js_error(NativeTaffyError::InvalidInputNode(node)))
+        }
+    }
+
+    /// Returns whether `node` is `of` or one of its ancestors
+    fn is_self_or_ancestor(&self, node: NodeId, of: NodeId) -> bool {
+        // Bounded by the node count in case the parent links already loop
+        let mut current = Some(of);
+        for _ in 0..=self.nodes.len() {
+            match current {
+                Some(id) if id == node => return true,
+                Some(id) => current = self.tree.parent(id),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Checks a structural edit
+    ///
+    /// `parent` is the node whose children change, or `None` for a node that
+    /// is being created, and `attached` are the nodes that become its
+    /// children. `detaches` is set for edits that detach `attached` from
+    /// their previous parents, like `setChildren()`. The nodes must always
+    /// exist; the remaining checks only run in strict mode.
+    fn check_edit(
+        &self,
+        parent: Option<NodeId>,
+        attached: &[NodeId],
+        detaches: bool,
+    ) -> Result<(), JsValue> {
+        if let Some(parent) = parent {
+            self.check_exists(parent)?;
+        }
+        for &child in attached {
+            self.check_exists(child)?;
+        }
+        if !self.strict {
+            return Ok(());
+        }
+        let mut seen = HashSet::with_capacity(attached.len());
+        for &child in attached {
+            let reason = if !seen.insert(child) {
+                format!("node {} is listed more than once", u64::from(child))
+            } else if let Some(parent) = parent.filter(|&p| self.is_self_or_ancestor(child, p)) {
+                format!(
+                    "node {} cannot become a child of its descendant {}",
+                    u64::from(child),
+                    u64::from(parent)
+                )
+            } else if let Some(current) = self.tree.parent(child).filter(|_| !detaches) {
+                format!(
+                    "node {} already has parent {}",
+                    u64::from(child),
+                    u64::from(current)
+                )
+            } else {
+                continue;
+            };
+            return Err(JsTaffyError::integrity_violation(reason).into());
+        }
+        Ok(())
+    }
+
+    /// Copies the nodes of a subtree in pre-order
+    fn copy_subtree(&self, root: NodeId) -> Result<Vec<NodeCopy>, JsValue> {
+        let order = self.subtree_preorder(root).map_err(|node| {
+            JsTaffyError::integrity_violation(format!(
+                "node {} is reachable more than once",
+                u64::from(node)
+            ))
+        })?;
+        let index_of: HashMap<NodeId, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect();
+        let mut copies = Vec::with_capacity(order.len());
+        for &node in &order {
+            copies.push(NodeCopy {
+                style: self.tree.style(node).map_err(to_js_error)?.clone(),
+                context: self.tree.get_node_context(node).cloned(),
+                measure: self.measure_funcs.get(&node).cloned(),
+                children: self
+                    .tree
+                    .child_ids(node)
+                    .map(|child| index_of[&child])
+                    .collect(),
+            });
+        }
+        Ok(copies)
+    }
+
+    /// Pastes the subtree of the `cloneSubtreeInto()` call in progress into
+    /// `target`
+    fn transfer_clone(target: &JsTaffyTreeArg) -> Result<u64, JsValue> {
+        let is_source = target.is_clone_source().map_err(|_| {
+            JsValue::from(JsTaffyError::invalid_argument(
+                "target",
+                target,
+                "TaffyTree",
+            ))
+        })?;
+        if is_source {
+            let error = JsTaffyError::invalid_argument(
+                "target",
+                target,
+                "TaffyTree other than the source tree",
+            );
+            return Err(error.into());
+        }
+        target.paste_clone()
+    }
+
+    /// Recreates copied nodes in this tree and returns the new root
+    ///
+    /// Nodes are created in reverse pre-order so that every node's children
+    /// exist before the node itself.
+    fn paste_subtree(&mut self, copies: Vec<NodeCopy>) -> Result<NodeId, JsValue> {
+        let mut ids = vec![NodeId::from(0u64); copies.len()];
+        for (i, copy) in copies.into_iter().enumerate().rev() {
+            let children: Vec<NodeId> = copy.children.iter().map(|&c| ids[c]).collect();
+            let id = self
+                .tree
+                .new_with_children(copy.style, &children)
+                .map_err(to_js_error)?;
+            if copy.context.is_some() {
+                self.tree
+                    .set_node_context(id, copy.context)
+                    .map_err(to_js_error)?;
+            }
+            if let Some(measure) = copy.measure {
+                self.measure_funcs.insert(id, measure);
+            }
+            ids[i] = self.created(id);
+        }
+        Ok(ids[0])
+    }
+
+    /// Looks up the scroll offset supplied for `node`, defaulting to zero
+    fn scroll_offset(offsets: Option<&Map>, node: NodeId) -> Result<Point<f32>, JsValue> {
+        let Some(offsets) = offsets else {
+            return Ok(Point::ZERO);
+        };
+        let value = offsets.get(&JsValue::from(u64::from(node)));
+        if value.is_undefined() {
+            return Ok(Point::ZERO);
+        }
+        let path = format!("scrollOffsets.get({}n)", u64::from(node));
+        let p: PointDto<f32> = decode_argument(&path, value, "Point<number>")?;
+        Ok(Point { x: p.x, y: p.y })
+    }
+
+    /// Checks that the `scrollOffsets` argument is a `Map`
+    fn scroll_offsets_map(offsets: Option<JsScrollOffsets>) -> Result<Option<Map>, JsValue> {
+        match offsets.map(|o| o.dyn_into::<Map>()) {
+            Some(Err(value)) => Err(JsTaffyError::invalid_argument(
+                "scrollOffsets",
+                &value.into(),
+                "Map<bigint, Point<number>>",
+            )
+            .into()),
+            Some(Ok(map)) => Ok(Some(map)),
+            None => Ok(None),
+        }
+    }
+
+    /// Decodes the `availableSpace` argument of a layout computation
+    ///
+    /// Each dimension is decoded separately so that the error names the
+    /// offending field.
+    fn available_space(value: JsValue) -> Result<Size<AvailableSpace>, JsValue> {
+        if !value.is_object() {
+            return Err(JsTaffyError::invalid_argument(
+                "availableSpace",
+                &value,
+                "Size<AvailableSpace>",
+            )
+            .into());
+        }
+        let dimension = |key: &str| -> Result<AvailableSpace, JsTaffyError> {
+            let field = Reflect::get(&value, &key.into()).unwrap_or(JsValue::UNDEFINED);
+            serde_wasm_bindgen::from_value::<AvailableSpaceDto>(field.clone())
+                .map(AvailableSpace::from)
+                .map_err(|_| {
+                    JsTaffyError::invalid_available_space(&format!("availableSpace.{key}"), &field)
+                })
+        };
+        Ok(Size {
+            width: dimension("width")?,
+            height: dimension("height")?,
+        })
+    }
+
+    /// Lists the subtree rooted at `root` in depth-first pre-order
+    ///
+    /// Fails with the offending node if a node is reached twice, which happens
+    /// when it is attached to more than one parent or the tree has a cycle.
+    fn subtree_preorder(&self, root: NodeId) -> Result<Vec<NodeId>, NodeId> {
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                return Err(node);
+            }
+            order.push(node);
+            let first_child = stack.len();
+            stack.extend(self.tree.child_ids(node));
+            stack[first_child..].reverse();
+        }
+        Ok(order)
+    }
+
+    /// Returns the absolute origin that `node`'s own location is relative to
+    fn subtree_origin(&self, node: NodeId, offsets: Option<&Map>) -> Result<Point<f32>, JsValue> {
+        let relative = self.tree.layout(node).map_err(to_js_error)?.location;
+        let absolute = self.absolute_location(node, offsets)?;
+        Ok(Point {
+            x: absolute.x - relative.x,
+            y: absolute.y - relative.y,
+        })
+    }
+
+    /// Returns the children of `node` in hit-testing order (topmost first)
+    fn children_topmost_first(&self, node: NodeId) -> Result<Vec<NodeId>, JsValue> {
+        let mut children = Vec::with_capacity(self.tree.child_count(node));
+        for (index, child) in self.tree.child_ids(node).enumerate() {
+            let order = self.tree.layout(child).map_err(to_js_error)?.order;
+            children.push((order, index, child));
+        }
+        children.sort_unstable_by_key(|&(order, index, _)| std::cmp::Reverse((order, index)));
+        Ok(children.into_iter().map(|(_, _, child)| child).collect())
+    }
+
+    /// Finds the deepest node under `point`, appending the path to it to `path`
+    ///
+    /// Returns `true` if `node` or one of its descendants was hit. Children are
+    /// visited even when `node` itself misses, since they may overflow it.
+    fn hit_test_node(
+        &self,
+        node: NodeId,
+        origin: Point<f32>,
+        point: Point<f32>,
+        offsets: Option<&Map>,
+        path: &mut Vec<NodeId>,
+    ) -> Result<bool, JsValue> {
+        let style = self.tree.style(node).map_err(to_js_error)?;
+        if style.display == Display::None {
+            return Ok(false);
+        }
+        let layout = self.tree.layout(node).map_err(to_js_error)?;
+        let left = origin.x + layout.location.x;
+        let top = origin.y + layout.location.y;
+        let right = left + layout.size.width;
+        let bottom = top + layout.size.height;
+
+        path.push(node);
+
+        let clipped_x = style.overflow.x != TaffyStyle::Overflow::Visible
+            && !(point.x >= left + layout.border.left && point.x < right - layout.border.right);
+        let clipped_y = style.overflow.y != TaffyStyle::Overflow::Visible
+            && !(point.y >= top + layout.border.top && point.y < bottom - layout.border.bottom);
+        if !clipped_x && !clipped_y {
+            let scroll = Self::scroll_offset(offsets, node)?;
+            let child_origin = Point {
+                x: left - scroll.x,
+                y: top - scroll.y,
+            };
+            for child in self.children_topmost_first(node)? {
+                if self.hit_test_node(child, child_origin, point, offsets, path)? {
+                    return Ok(true);
+                }
+            }
+        }
+
+        if point.x >= left && point.x < right && point.y >= top && point.y < bottom {
+            return Ok(true);
+        }
+        path.pop();
+        Ok(false)
+    }
+
+    /// Runs a hit test from `root`, returning the path from `root` to the hit node
+    fn hit_test_path_ids(
+        &self,
+        root: u64,
+        x: f32,
+        y: f32,
+        scroll_offsets: Option<JsScrollOffsets>,
+    ) -> Result<Vec<NodeId>, JsValue> {
+        let offsets = Self::scroll_offsets_map(scroll_offsets)?;
+        let root = self.existing(root)?;
+        let origin = self.subtree_origin(root, offsets.as_ref())?;
+        let mut path = Vec::new();
+        self.hit_test_node(root, origin, Point { x, y }, offsets.as_ref(), &mut path)?;
+        Ok(path)
+    }
+
+    /// Accumulates the location of `node` over all of its ancestors
+    fn absolute_location(
+        &self,
+        node: NodeId,
+        offsets: Option<&Map>,
+    ) -> Result<Point<f32>, JsValue> {
+        let mut location = self.tree.layout(node).map_err(to_js_error)?.location;
+        let mut current = node;
+        while let Some(parent) = self.tree.parent(current) {
+            let parent_location = self.tree.layout(parent).map_err(to_js_error)?.location;
+            let scroll = Self::scroll_offset(offsets, parent)?;
+            location.x += parent_location.x - scroll.x;
+            location.y += parent_location.y - scroll.y;
+            current = parent;
+        }
+        Ok(location)
+    }
+}
+
+impl Default for JsTaffyTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(tree: &mut JsTaffyTree) -> u64 {
+        tree.new_leaf(&JsStyle::from(Style::default())).unwrap()
+    }
+
+    fn children(tree: &JsTaffyTree, parent: u64) -> Vec<u64> {
+        let children = tree.tree.children(NodeId::from(parent)).unwrap();
+        children.into_iter().map(u64::from).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_restore_structure_and_removed_ids() {
+        let mut tree = JsTaffyTree::new();
+        tree.enable_history(None);
+        let root = leaf(&mut tree);
+        let (a, b, grandchild) = (leaf(&mut tree), leaf(&mut tree), leaf(&mut tree));
+        tree.add_child(root, a).unwrap();
+        tree.add_child(root, b).unwrap();
+        tree.add_child(a, grandchild).unwrap();
+        tree.checkpoint(Some("add".into()));
+        tree.move_node(b, a, Some(0)).unwrap();
+        tree.checkpoint(Some("move".into()));
+        tree.remove(a).unwrap();
+        tree.checkpoint(Some("remove".into()));
+        assert_eq!(children(&tree, root), Vec::<u64>::new());
+        assert_eq!(tree.total_node_count(), 3);
+
+        assert!(tree.undo());
+        assert_eq!(children(&tree, root), vec![a]);
+        assert_eq!(children(&tree, a), vec![b, grandchild]);
+        assert_eq!(tree.tree.parent(NodeId::from(b)), Some(NodeId::from(a)));
+        assert_eq!(tree.total_node_count(), 4);
+        assert_eq!(tree.redo_name().as_deref(), Some("remove"));
+
+        assert!(tree.undo());
+        assert_eq!(children(&tree, root), vec![a, b]);
+        assert_eq!(children(&tree, a), vec![grandchild]);
+        assert!(tree.undo());
+        assert_eq!(children(&tree, root), Vec::<u64>::new());
+        assert!(!tree.undo());
+
+        assert!(tree.redo() && tree.redo() && tree.redo());
+        assert_eq!(children(&tree, root), Vec::<u64>::new());
+        assert_eq!(tree.total_node_count(), 3);
+        assert!(!tree.redo());
+    }
+
+    #[test]
+    fn steps_beyond_the_depth_free_removed_nodes() {
+        let mut tree = JsTaffyTree::new();
+        tree.enable_history(Some(1));
+        let root = leaf(&mut tree);
+        let child = leaf(&mut tree);
+        tree.add_child(root, child).unwrap();
+        tree.remove(child).unwrap();
+        tree.checkpoint(None);
+        tree.replace_children(NodeId::from(root), &[]).unwrap();
+        let replacement = leaf(&mut tree);
+        tree.add_child(root, replacement).unwrap();
+        tree.checkpoint(None);
+
+        assert!(tree.undo());
+        assert!(!tree.undo());
+        assert_eq!(tree.total_node_count(), 2);
+    }
+
+    #[test]
+    fn rollback_frees_only_nodes_created_in_the_transaction() {
+        let mut tree = JsTaffyTree::new();
+        let root = leaf(&mut tree);
+        let kept = leaf(&mut tree);
+        tree.add_child(root, kept).unwrap();
+
+        tree.begin_transaction();
+        let created = leaf(&mut tree);
+        tree.add_child(root, created).unwrap();
+        tree.remove(kept).unwrap();
+        tree.rollback().unwrap();
+
+        assert_eq!(children(&tree, root), vec![kept]);
+        assert_eq!(tree.total_node_count(), 2);
+        assert_eq!(tree.tree.total_node_count(), 2);
+    }
+
+    #[test]
+    fn rollback_restores_history_rounding_and_cleared_nodes() {
+        let mut tree = JsTaffyTree::new();
+        tree.enable_history(None);
+        let root = leaf(&mut tree);
+        let child = leaf(&mut tree);
+        tree.add_child(root, child).unwrap();
+        tree.checkpoint(Some("add".into()));
+
+        tree.begin_transaction();
+        assert!(tree.undo());
+        tree.disable_rounding();
+        tree.clear();
+        assert_eq!(tree.total_node_count(), 0);
+        assert!(!tree.can_undo());
+        tree.rollback().unwrap();
+
+        assert_eq!(children(&tree, root), vec![child]);
+        assert_eq!(tree.total_node_count(), 2);
+        assert!(tree.rounding);
+        assert_eq!(tree.undo_name().as_deref(), Some("add"));
+        assert!(!tree.can_redo());
+        assert!(tree.undo());
+        assert_eq!(children(&tree, root), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn commit_frees_nodes_cleared_in_the_transaction() {
+        let mut tree = JsTaffyTree::new();
+        let root = leaf(&mut tree);
+        let child = leaf(&mut tree);
+        tree.add_child(root, child).unwrap();
+
+        tree.begin_transaction();
+        tree.clear();
+        tree.commit().unwrap();
+
+        assert_eq!(tree.total_node_count(), 0);
+        assert_eq!(tree.tree.total_node_count(), 0);
     }
 }
@@ -19,6 +19,7 @@
 //! | [`LengthPercentageAutoDto`] | `LengthPercentageAuto` | Length, percentage, or auto |
 //! | [`SizeDto<T>`] | `Size<T>` | Width and height pair |
 //! | [`RectDto<T>`] | `Rect<T>` | Left, right, top, bottom quad |
+//! | [`BoundingBoxDto`] | - | Viewport box for `queryRect()` |
 //! | [`AvailableSizeDto`] | `Size<AvailableSpace>` | Layout constraints |
 //! | [`AvailableSpaceDto`] | `AvailableSpace` | Single dimension constraint |
 //!
@@ -65,6 +66,12 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "MeasureFunction")]
     pub type JsMeasureFunctionArg;
 
+    /// Layout change callback type
+    ///
+    /// Used with `enableChangeTracking()` to receive the nodes whose layout changed.
+    #[wasm_bindgen(typescript_type = "LayoutChangeCallback")]
+    pub type JsLayoutChangeCallbackArg;
+
     /// Overflow point type (x and y overflow settings)
     #[wasm_bindgen(typescript_type = "Point<Overflow>")]
     pub type JsPointOverflow;
@@ -140,12 +147,76 @@ extern "C" {
     /// Array of BigInt values
     #[wasm_bindgen(typescript_type = "bigint[]")]
     pub type JsBigIntArray;
+
+    /// Box argument type for viewport queries
+    #[wasm_bindgen(typescript_type = "BoundingBox")]
+    pub type JsBoundingBoxArg;
+
+    /// Style property keys reported by `diff()`
+    #[wasm_bindgen(typescript_type = "StyleProperty[]")]
+    pub type JsStylePropertyArray;
+
+    /// Operations accepted by `applyBatch()`
+    #[wasm_bindgen(typescript_type = "BatchOp[]")]
+    pub type JsBatchOpArray;
+
+    /// Results of `applyBatch()`, aligned with its operations
+    #[wasm_bindgen(typescript_type = "(bigint | undefined)[]")]
+    pub type JsBatchResults;
+
+    /// Counters returned by `measureCacheStats()`
+    #[wasm_bindgen(typescript_type = "MeasureCacheStats | undefined")]
+    pub type JsMeasureCacheStats;
+
+    /// Options for `toSnapshot()`
+    #[wasm_bindgen(typescript_type = "SnapshotOptions")]
+    pub type JsSnapshotOptions;
+
+    /// Options for `setMeasureArgs()`
+    #[wasm_bindgen(typescript_type = "MeasureArgsOptions")]
+    pub type JsMeasureArgsOptions;
+
+    /// Options for `toCss()`
+    #[wasm_bindgen(typescript_type = "CssOptions")]
+    pub type JsCssOptions;
+
+    /// Versioned tree snapshot document
+    #[wasm_bindgen(typescript_type = "TreeSnapshot")]
+    pub type JsTreeSnapshot;
+
+    /// Integrity issues reported by `validate()`
+    #[wasm_bindgen(typescript_type = "TreeIssue[]")]
+    pub type JsTreeIssueArray;
+
+    /// Scroll offsets keyed by node ID, used for absolute coordinates
+    #[wasm_bindgen(typescript_type = "Map<bigint, Point<number>>")]
+    pub type JsScrollOffsets;
+
+    /// Target tree of `cloneSubtreeInto()`
+    ///
+    /// Taken as a plain value rather than a `TaffyTree` reference, so that
+    /// the call can reject the source tree before borrowing the target.
+    #[wasm_bindgen(typescript_type = "TaffyTree")]
+    pub type JsTaffyTreeArg;
+
+    /// Calls `TaffyTree.__isCloneSource()` on a `cloneSubtreeInto()` target
+    #[wasm_bindgen(method, catch, js_name = __isCloneSource)]
+    pub fn is_clone_source(this: &JsTaffyTreeArg) -> Result<bool, JsValue>;
+
+    /// Calls `TaffyTree.__pasteClone()` on a `cloneSubtreeInto()` target
+    #[wasm_bindgen(method, catch, js_name = __pasteClone)]
+    pub fn paste_clone(this: &JsTaffyTreeArg) -> Result<u64, JsValue>;
 }
 
 // =============================================================================
 // Dimension DTO
 // =============================================================================
 
+/// Formats a 0-100 percentage the way the DTOs serialize it, e.g. `"50%"`
+pub(crate) fn percent_string(p: f32) -> String {
+    format!("{}%", p)
+}
+
 /// Data Transfer Object for CSS dimension values
 ///
 /// @remarks
@@ -179,10 +250,7 @@ impl Serialize for DimensionDto {
     {
         match self {
             DimensionDto::Length(l) => serializer.serialize_f32(*l),
-            DimensionDto::Percent(p) => {
-                let s = format!("{}%", p);
-                serializer.serialize_str(&s)
-            }
+            DimensionDto::Percent(p) => serializer.serialize_str(&percent_string(*p)),
             DimensionDto::Auto => serializer.serialize_str("auto"),
         }
     }
@@ -220,9 +288,8 @@ impl<'de> Deserialize<'de> for DimensionDto {
             {
                 if value == "auto" {
                     Ok(DimensionDto::Auto)
-                } else if value.ends_with('%') {
+                } else if let Some(num_str) = value.strip_suffix('%') {
                     // Try parsing the number part
-                    let num_str = &value[..value.len() - 1];
                     match num_str.parse::<f32>() {
                         Ok(p) => Ok(DimensionDto::Percent(p)),
                         Err(_) => Err(E::custom("Invalid percentage value")),
@@ -261,6 +328,17 @@ impl From<Dimension> for DimensionDto {
     }
 }
 
+impl DimensionDto {
+    /// Formats the value as a CSS dimension: `"10px"`, `"50%"` or `"auto"`
+    pub(crate) fn to_css(&self) -> String {
+        match self {
+            DimensionDto::Length(l) => format!("{}px", l),
+            DimensionDto::Percent(p) => percent_string(*p),
+            DimensionDto::Auto => "auto".to_string(),
+        }
+    }
+}
+
 // =============================================================================
 // LengthPercentage DTO
 // =============================================================================
@@ -290,10 +368,7 @@ impl Serialize for LengthPercentageDto {
     {
         match self {
             LengthPercentageDto::Length(l) => serializer.serialize_f32(*l),
-            LengthPercentageDto::Percent(p) => {
-                let s = format!("{}%", p);
-                serializer.serialize_str(&s)
-            }
+            LengthPercentageDto::Percent(p) => serializer.serialize_str(&percent_string(*p)),
         }
     }
 }
@@ -328,9 +403,8 @@ impl<'de> Deserialize<'de> for LengthPercentageDto {
             where
                 E: de::Error,
             {
-                if value.ends_with('%') {
+                if let Some(num_str) = value.strip_suffix('%') {
                     // Try parsing the number part
-                    let num_str = &value[..value.len() - 1];
                     match num_str.parse::<f32>() {
                         Ok(p) => Ok(LengthPercentageDto::Percent(p)),
                         Err(_) => Err(E::custom("Invalid percentage value")),
@@ -365,6 +439,16 @@ impl From<LengthPercentage> for LengthPercentageDto {
     }
 }
 
+impl LengthPercentageDto {
+    /// Formats the value as a CSS length: `"10px"` or `"25%"`
+    pub(crate) fn to_css(&self) -> String {
+        match self {
+            LengthPercentageDto::Length(l) => format!("{}px", l),
+            LengthPercentageDto::Percent(p) => percent_string(*p),
+        }
+    }
+}
+
 // =============================================================================
 // LengthPercentageAuto DTO
 // =============================================================================
@@ -396,10 +480,7 @@ impl Serialize for LengthPercentageAutoDto {
     {
         match self {
             LengthPercentageAutoDto::Length(l) => serializer.serialize_f32(*l),
-            LengthPercentageAutoDto::Percent(p) => {
-                let s = format!("{}%", p);
-                serializer.serialize_str(&s)
-            }
+            LengthPercentageAutoDto::Percent(p) => serializer.serialize_str(&percent_string(*p)),
             LengthPercentageAutoDto::Auto => serializer.serialize_str("auto"),
         }
     }
@@ -437,9 +518,8 @@ impl<'de> Deserialize<'de> for LengthPercentageAutoDto {
             {
                 if value == "auto" {
                     Ok(LengthPercentageAutoDto::Auto)
-                } else if value.ends_with('%') {
+                } else if let Some(num_str) = value.strip_suffix('%') {
                     // Try parsing the number part
-                    let num_str = &value[..value.len() - 1];
                     match num_str.parse::<f32>() {
                         Ok(p) => Ok(LengthPercentageAutoDto::Percent(p)),
                         Err(_) => Err(E::custom("Invalid percentage value")),
@@ -481,6 +561,17 @@ impl From<LengthPercentageAuto> for LengthPercentageAutoDto {
     }
 }
 
+impl LengthPercentageAutoDto {
+    /// Formats the value as a CSS length: `"10px"`, `"25%"` or `"auto"`
+    pub(crate) fn to_css(&self) -> String {
+        match self {
+            LengthPercentageAutoDto::Length(l) => format!("{}px", l),
+            LengthPercentageAutoDto::Percent(p) => percent_string(*p),
+            LengthPercentageAutoDto::Auto => "auto".to_string(),
+        }
+    }
+}
+
 // =============================================================================
 // PointOverflow DTO
 // =============================================================================
@@ -652,6 +743,28 @@ pub struct PointDto<T> {
     pub y: T,
 }
 
+// =============================================================================
+// Bounding Box DTO
+// =============================================================================
+
+/// Data Transfer Object for an axis-aligned box
+///
+/// @example
+/// ```json
+/// { "x": 0, "y": 100, "width": 800, "height": 600 }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct BoundingBoxDto {
+    /// The left edge
+    pub x: f32,
+    /// The top edge
+    pub y: f32,
+    /// The horizontal extent
+    pub width: f32,
+    /// The vertical extent
+    pub height: f32,
+}
+
 // =============================================================================
 // Available Space DTOs
 // =============================================================================
@@ -975,8 +1088,8 @@ impl From<GridPlacementDto> for GridPlacement {
             GridPlacementDto::Auto => GridPlacement::Auto,
             GridPlacementDto::Line(idx) => GridPlacement::from_line_index(idx),
             GridPlacementDto::Span(span) => GridPlacement::from_span(span),
-            GridPlacementDto::NamedLine(idx, s) => GridPlacement::NamedLine(s.into(), idx), // NamedLine variant
-            GridPlacementDto::NamedSpan(n, s) => GridPlacement::NamedSpan(s.into(), n),
+            GridPlacementDto::NamedLine(idx, s) => GridPlacement::NamedLine(s, idx), // NamedLine variant
+            GridPlacementDto::NamedSpan(n, s) => GridPlacement::NamedSpan(s, n),
         }
     }
 }
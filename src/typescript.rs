@@ -8,9 +8,9 @@
 //! These types provide accurate TypeScript definitions for complex types that
 //! wasm-bindgen cannot automatically generate, including:
 //!
-//! - `AvailableSpace`, `Size<T>`, `Rect<T>`, `Point<T>`
+//! - `AvailableSpace`, `Size<T>`, `Rect<T>`, `Point<T>`, `BoundingBox`
 //! - `Dimension`, `LengthPercentage`, `LengthPercentageAuto`
-//! - `MeasureFunction` callback signature
+//! - `MeasureFunction` and `LayoutChangeCallback` callback signatures
 //! - Detailed grid layout info types
 //! - `GridPlacement` and `Line<T>` for grid positioning
 
@@ -110,7 +110,8 @@ export type Size<T> = {
  *                         pixels, "min-content", or "max-content".
  * @param node - The node ID (`bigint`) of the node being measured
  * @param context - User-provided context attached to the node via `newLeafWithContext()`
- * @param style - The node's current Style configuration
+ * @param style - The node's current Style configuration, unless left out with
+ *                `TaffyTree.setMeasureArgs({ style: false })`
  *
  * @returns - The measured size of the content in pixels
  *
@@ -296,6 +297,37 @@ export type Point<T> = {
   y: T;
 };
 
+/**
+ * Axis-aligned box given by its top-left corner and size.
+ *
+ * Used for viewport queries such as `TaffyTree.queryRect()`. Coordinates are
+ * in the same space as `TaffyTree.getAbsoluteLayout()`.
+ *
+ * @property x - The left edge
+ * @property y - The top edge
+ * @property width - The horizontal extent
+ * @property height - The vertical extent
+ *
+ * @example
+ * ```typescript
+ * const tree = new TaffyTree();
+ * const root = tree.newLeaf(new Style());
+ * tree.computeLayout(root, { width: 800, height: 600 });
+ *
+ * const visible = tree.queryRect(root, { x: 0, y: 0, width: 800, height: 600 });
+ * ```
+ */
+export type BoundingBox = {
+  /** The left edge */
+  x: number;
+  /** The top edge */
+  y: number;
+  /** The horizontal extent */
+  width: number;
+  /** The vertical extent */
+  height: number;
+};
+
 /**
  * Rectangle with left, right, top, and bottom values.
  *
@@ -787,10 +819,15 @@ declare module "./taffy_wasm" {
      *
      * @param props - Object mapping property keys to their values
      *
+     * @param strict - Overrides the global strict mode (`Style.enableStrictMode()`) for this call
+     *
      * @remarks
      * Only accepts valid property keys with their corresponding value types.
+     * Invalid values are skipped and recorded as warnings (see `takeWarnings()`)
+     * unless strict validation applies.
      *
      * @throws Error if any property key is unknown.
+     * @throws TaffyError with code `InvalidStyle` in strict mode if any key or value is invalid.
      *
      * @example
      * ```typescript
@@ -803,7 +840,7 @@ declare module "./taffy_wasm" {
      * });
      * ```
      */
-    set(props: StylePropertyValues): void;
+    set(props: StylePropertyValues, strict?: boolean): void;
   }
 
   interface Layout {
@@ -855,6 +892,63 @@ declare module "./taffy_wasm" {
     ): [LayoutPropertyValues[K1], LayoutPropertyValues[K2], LayoutPropertyValues[K3]];
     get<Keys extends LayoutProperty[]>(...keys: Keys): LayoutPropertyArrayValues<Keys>;
   }
+
+  interface TaffyTree {
+    /**
+     * Reads style properties of a node in a single WASM call.
+     * Mirrors `Style.get()` without copying the node's whole style.
+     *
+     * @returns Single value for one key, tuple for 2-3 keys, array for 4+ keys
+     *
+     * @throws TaffyError if the node does not exist.
+     * @throws Error if any property key is unknown.
+     *
+     * @example
+     * ```typescript
+     * const tree = new TaffyTree();
+     * const node = tree.newLeaf(new Style({ width: 100 }));
+     * const width = tree.getStyleProperty(node, "width"); // Dimension
+     * const [grow, margin] = tree.getStyleProperty(node, "flexGrow", "margin");
+     * ```
+     */
+    getStyleProperty<K extends StyleProperty>(node: bigint, ...keys: [K]): StylePropertyValues[K];
+    getStyleProperty<K1 extends StyleProperty, K2 extends StyleProperty>(
+      node: bigint,
+      ...keys: [K1, K2]
+    ): [StylePropertyValues[K1], StylePropertyValues[K2]];
+    getStyleProperty<K1 extends StyleProperty, K2 extends StyleProperty, K3 extends StyleProperty>(
+      node: bigint,
+      ...keys: [K1, K2, K3]
+    ): [StylePropertyValues[K1], StylePropertyValues[K2], StylePropertyValues[K3]];
+    getStyleProperty<Keys extends StyleProperty[]>(
+      node: bigint,
+      ...keys: Keys
+    ): StylePropertyArrayValues<Keys>;
+
+    /**
+     * Updates some style properties of a node in place, with the same key and
+     * value handling as `Style.set()`.
+     *
+     * @param node - The node ID
+     * @param props - Object mapping property keys to their values
+     * @param strict - Overrides the global strict mode (`Style.enableStrictMode()`) for this call
+     *
+     * @returns `true` if the style changed and the node was marked dirty
+     *
+     * @throws TaffyError if the node does not exist.
+     * @throws Error if any property key is unknown.
+     * @throws TaffyError with code `InvalidStyle` in strict mode if any key or value is invalid.
+     *
+     * @example
+     * ```typescript
+     * const tree = new TaffyTree();
+     * const node = tree.newLeaf(new Style());
+     * tree.updateStyle(node, { width: 100, flexGrow: 1 }); // true
+     * tree.updateStyle(node, { width: 100 }); // false
+     * ```
+     */
+    updateStyle(node: bigint, props: StylePropertyValues, strict?: boolean): boolean;
+  }
 }
 
 /**
@@ -871,4 +965,163 @@ type StylePropertyArrayValues<Keys extends StyleProperty[]> = {
 type LayoutPropertyArrayValues<Keys extends LayoutProperty[]> = {
   [K in keyof Keys]: Keys[K] extends LayoutProperty ? LayoutPropertyValues[Keys[K]] : unknown;
 };
+
+/**
+ * Options for `TaffyTree.toSnapshot()`.
+ */
+export type SnapshotOptions = {
+  /** Include each node's context value (default: `false`) */
+  includeContext?: boolean;
+  /** Include each node's computed layout (default: `false`) */
+  includeLayout?: boolean;
+};
+
+/**
+ * A single node of a `TreeSnapshot`.
+ */
+export type TreeSnapshotNode = {
+  /** Non-default style properties, in `Style.set()` format */
+  style: StylePropertyValues;
+  /** Indices of the child nodes in `TreeSnapshot.nodes` */
+  children: number[];
+  /** The node context, if requested and present */
+  context?: unknown;
+  /** The computed layout, if requested (ignored when restoring) */
+  layout?: Partial<LayoutPropertyValues>;
+};
+
+/**
+ * Versioned, JSON-compatible snapshot of a subtree.
+ *
+ * Created by `TaffyTree.toSnapshot()` and restored by `TaffyTree.fromSnapshot()`.
+ */
+export type TreeSnapshot = {
+  /** Format version, currently always `1` */
+  version: 1;
+  /** Index of the root node in `nodes` */
+  root: number;
+  /** All nodes of the subtree, in depth-first pre-order */
+  nodes: TreeSnapshotNode[];
+};
+
+/**
+ * Kind of problem reported by `TaffyTree.validate()`.
+ */
+export type TreeIssueKind = "cycle" | "multipleParents" | "danglingChild" | "parentMismatch";
+
+/**
+ * An integrity problem found by `TaffyTree.validate()`.
+ */
+export type TreeIssue = {
+  /** What is wrong */
+  kind: TreeIssueKind;
+  /** The child node the issue is about */
+  node: bigint;
+  /** The node whose child list references `node` */
+  parent: bigint;
+};
+
+/**
+ * Machine-readable code of a `TaffyError`, available as `error.code`.
+ */
+export type TaffyErrorCode =
+  | "ChildIndexOutOfBounds"
+  | "InvalidParentNode"
+  | "InvalidChildNode"
+  | "InvalidInputNode"
+  | "InvalidSnapshot"
+  | "WouldCreateCycle"
+  | "IntegrityViolation"
+  | "InvalidAvailableSpace"
+  | "InvalidArgument"
+  | "InvalidStyle"
+  | "BatchFailed"
+  | "NoTransaction"
+  | "MeasureFailed";
+
+/**
+ * Options for `TaffyTree.setMeasureArgs()`.
+ */
+export type MeasureArgsOptions = {
+  /** Reuse one `knownDimensions` and one `availableSpace` object for every call (default: `false`) */
+  reuseObjects?: boolean;
+  /** Pass a copy of the node's style as the last argument (default: `true`) */
+  style?: boolean;
+};
+
+/**
+ * How text that does not fit the available width is handled by the native
+ * text measurement of `TaffyTree.enableTextMeasure()`.
+ *
+ * - `"wrap"`: Words wrap onto further lines
+ * - `"truncate"`: Lines are cut at the available width
+ * - `"none"`: Lines keep their full width
+ */
+export type TextWrap = "wrap" | "truncate" | "none";
+
+/**
+ * Node context measured in terminal cells by `TaffyTree.enableTextMeasure()`.
+ */
+export type TextContext = {
+  /** The text; every `\n` starts a new line */
+  text: string;
+  /** How text wider than the available width is handled (default: `"wrap"`) */
+  wrap?: TextWrap;
+};
+
+/**
+ * Options for `Style.toCss()`.
+ */
+export type CssOptions = {
+  /** Leave out properties that have their default value (default: `false`) */
+  onlyNonDefault?: boolean;
+};
+
+/**
+ * A node referenced by a `BatchOp`: an existing node ID, or the index of an
+ * earlier `create` operation in the same batch.
+ */
+export type BatchNodeRef = bigint | number;
+
+/**
+ * A tree mutation run by `TaffyTree.applyBatch()`.
+ *
+ * Styles are given as `Style.set()` property objects.
+ */
+export type BatchOp =
+  | {
+      op: "create";
+      style?: StylePropertyValues;
+      context?: unknown;
+      children?: BatchNodeRef[];
+    }
+  | { op: "insert"; parent: BatchNodeRef; child: BatchNodeRef; index?: number }
+  | { op: "removeChild"; parent: BatchNodeRef; child: BatchNodeRef }
+  | { op: "remove"; node: BatchNodeRef }
+  | { op: "move"; node: BatchNodeRef; parent: BatchNodeRef; index?: number }
+  | { op: "setChildren"; parent: BatchNodeRef; children: BatchNodeRef[] }
+  | { op: "setStyle"; node: BatchNodeRef; style: StylePropertyValues }
+  | { op: "updateStyle"; node: BatchNodeRef; style: StylePropertyValues }
+  | { op: "setContext"; node: BatchNodeRef; context: unknown }
+  | { op: "markDirty"; node: BatchNodeRef };
+
+/**
+ * Callback registered with `TaffyTree.enableChangeTracking()`.
+ *
+ * @param nodes - The nodes whose position, size or content size changed in
+ *                the layout pass, in depth-first pre-order
+ */
+export type LayoutChangeCallback = (nodes: bigint[]) => void;
+
+/**
+ * Measure cache counters returned by `TaffyTree.measureCacheStats()`.
+ */
+export type MeasureCacheStats = {
+  /** Measurements answered from the cache */
+  hits: number;
+  /** Measurements that called the measure function */
+  misses: number;
+  /** Results currently cached */
+  entries: number;
+};
 "#;
@@ -6,6 +6,7 @@
 //!
 //! - [`serialize`]: Converts Rust types to `JsValue` using serde-wasm-bindgen
 //! - [`log`]: Outputs debug messages to the browser console
+//! - [`queue_microtask`]: Runs a function once the current call has returned
 //!
 //! ## Usage
 //!
@@ -79,3 +80,25 @@ extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     pub fn log(s: &str);
 }
+
+// =============================================================================
+// Scheduling
+// =============================================================================
+
+#[wasm_bindgen]
+extern "C" {
+    /// Queues a function to run once the current JavaScript task finishes
+    ///
+    /// This function is a direct binding to JavaScript's `queueMicrotask()`. It
+    /// is used to call back into JavaScript after a method has returned, when
+    /// the callback may use the object the method borrowed.
+    ///
+    /// @param callback - The function to run, called without arguments
+    ///
+    /// @example
+    /// ```rust,ignore
+    /// queue_microtask(&callback.bind1(&JsValue::NULL, &nodes));
+    /// ```
+    #[wasm_bindgen(js_name = queueMicrotask)]
+    pub fn queue_microtask(callback: &js_sys::Function);
+}
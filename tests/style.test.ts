@@ -14,6 +14,7 @@ import {
   BoxSizing,
   TextAlign,
   GridAutoFlow,
+  TaffyError,
 } from "../src/index";
 
 describe("Style Class Properties", () => {
@@ -1061,6 +1062,19 @@ describe("Style Class Properties", () => {
       expect(style.get("gridColumnEnd")).toBe(4);
     });
 
+    it("reads alignSelf and justifySelf as AlignSelf values", () => {
+      const style = new Style();
+      style.alignSelf = AlignSelf.Center;
+      style.justifySelf = AlignSelf.Start;
+
+      expect(style.get("alignSelf")).toBe(AlignSelf.Center);
+      expect(style.get("justifySelf")).toBe(AlignSelf.Start);
+
+      const copy = new Style();
+      copy.set({ alignSelf: style.get("alignSelf") });
+      expect(copy.alignSelf).toBe(AlignSelf.Center);
+    });
+
     it("returns undefined for empty keys array", () => {
       const style = new Style();
       expect(style.get()).toBeUndefined();
@@ -1785,4 +1799,236 @@ describe("Style Class Properties", () => {
       });
     });
   });
+
+  describe("Validation", () => {
+    it("skips invalid values and records warnings", () => {
+      const style = new Style();
+      style.set({ display: 42, width: "wide", flexGrow: 2 } as never);
+
+      expect(style.display).toBe(Display.Flex);
+      expect(style.flexGrow).toBe(2);
+      expect(style.takeWarnings()).toEqual([
+        "display: 42 is not a valid Display",
+        'width: "wide" is not a valid Dimension',
+      ]);
+      expect(style.takeWarnings()).toEqual([]);
+    });
+
+    it("rejects out-of-range enums from setters", () => {
+      const style = new Style();
+      style.alignSelf = 99 as AlignSelf;
+      style.justifyContent = 1.5 as JustifyContent;
+
+      expect(style.alignSelf).toBe(AlignSelf.Auto);
+      expect(style.justifyContent).toBeUndefined();
+      expect(style.takeWarnings()).toHaveLength(2);
+    });
+
+    it("throws every problem in strict mode without applying any", () => {
+      const style = new Style();
+      const props = { display: 42, widht: 10, height: 5 } as never;
+
+      let error: TaffyError | undefined;
+      try {
+        style.set(props, true);
+      } catch (e) {
+        error = e as TaffyError;
+      }
+
+      expect(error).toBeInstanceOf(TaffyError);
+      expect(error!.code).toBe("InvalidStyle");
+      expect(error!.issues).toEqual([
+        "display: 42 is not a valid Display",
+        "widht is not a style property",
+      ]);
+      expect(style.height).toBe("auto");
+      expect(style.takeWarnings()).toEqual([]);
+    });
+
+    it("throws for a non-object argument in strict mode", () => {
+      const style = new Style();
+
+      let error: TaffyError | undefined;
+      try {
+        style.set(42 as never, true);
+      } catch (e) {
+        error = e as TaffyError;
+      }
+
+      expect(error).toBeInstanceOf(TaffyError);
+      expect(error!.code).toBe("InvalidStyle");
+      expect(error!.issues).toEqual(["42 is not an object of style properties"]);
+      expect(() => style.set(42 as never, false)).not.toThrow();
+    });
+
+    it("applies the global strict mode to constructors and setters", () => {
+      Style.enableStrictMode();
+      try {
+        expect(() => new Style({ position: 7 } as never)).toThrow(TaffyError);
+
+        const style = new Style();
+        expect(() => {
+          style.margin = { left: "x" } as never;
+        }).toThrow("Invalid style: margin:");
+
+        // A per-call override still allows lenient writes
+        style.set({ flexShrink: "0" } as never, false);
+        expect(style.takeWarnings()).toHaveLength(1);
+      } finally {
+        Style.disableStrictMode();
+      }
+    });
+  });
+
+  describe("CSS Declarations", () => {
+    it("parses longhands and shorthands", () => {
+      const style = Style.fromCss(
+        "display:flex; flex-direction:column; gap: 8px 4px; margin: 0 auto",
+      );
+
+      expect(style.display).toBe(Display.Flex);
+      expect(style.flexDirection).toBe(FlexDirection.Column);
+      expect(style.gap).toEqual({ width: 4, height: 8 });
+      expect(style.margin).toEqual({
+        left: "auto",
+        right: "auto",
+        top: 0,
+        bottom: 0,
+      });
+      expect(style.takeWarnings()).toEqual([]);
+    });
+
+    it("expands flex, place-items, overflow and grid-area", () => {
+      const style = new Style();
+      style.applyCss(`
+        flex: 2 0 50%;
+        place-items: center end;
+        overflow: hidden;
+        grid-area: 2 / 1 / span 2 / 3;
+      `);
+
+      expect(style.flexGrow).toBe(2);
+      expect(style.flexShrink).toBe(0);
+      expect(style.flexBasis).toBe("50%");
+      expect(style.alignItems).toBe(AlignItems.Center);
+      expect(style.justifyItems).toBe(AlignItems.End);
+      expect(style.overflow).toEqual({ x: Overflow.Hidden, y: Overflow.Hidden });
+      expect(style.gridRow).toEqual({ start: 2, end: { span: 2 } });
+      expect(style.gridColumn).toEqual({ start: 1, end: 3 });
+    });
+
+    it("reports unparseable declarations with their position", () => {
+      const style = new Style();
+      style.applyCss("width: 10px;\n  flex-direction: sideways; widht: 5px");
+
+      expect(style.width).toBe(10);
+      expect(style.takeWarnings()).toEqual([
+        "2:3: flex-direction: `sideways` is not one of row, row-reverse, column, column-reverse",
+        "2:29: unknown property `widht`",
+      ]);
+    });
+
+    it("reports positions after comments with non-ASCII text", () => {
+      const style = Style.fromCss("/* größe */ width: 1px;\n/* ü */ widht: 2px");
+
+      expect(style.width).toBe(1);
+      expect(style.takeWarnings()).toEqual(["2:9: unknown property `widht`"]);
+    });
+
+    it("applies nothing in strict mode when a declaration is invalid", () => {
+      const style = new Style();
+      let error: TaffyError | undefined;
+      try {
+        style.applyCss("width: 10px; height: 1em", true);
+      } catch (e) {
+        error = e as TaffyError;
+      }
+
+      expect(error?.code).toBe("InvalidStyle");
+      expect(error?.issues).toEqual([
+        "1:14: height: `1em` is not a length (px or %)",
+      ]);
+      expect(style.width).toBe("auto");
+    });
+  });
+
+  describe("CSS Serialization", () => {
+    it("writes only non-default properties with shorthands", () => {
+      const style = new Style();
+      style.display = Display.Grid;
+      style.margin = { left: "auto", right: "auto", top: 0, bottom: 0 };
+      style.padding = { left: 4, right: 0, top: 0, bottom: 0 };
+      style.alignItems = AlignItems.Center;
+      style.justifyItems = AlignItems.Center;
+      style.maxSize = { width: "50%", height: "auto" };
+
+      expect(style.toCss({ onlyNonDefault: true })).toBe(
+        [
+          "display: grid;",
+          "place-items: center;",
+          "max-width: 50%;",
+          "margin: 0px auto;",
+          "padding-left: 4px;",
+        ].join("\n"),
+      );
+      expect(new Style().toCss({ onlyNonDefault: true })).toBe("");
+    });
+
+    it("writes every property by default", () => {
+      const css = new Style().toCss();
+
+      expect(css).toContain("display: flex;");
+      expect(css).toContain("max-width: none;");
+      expect(css).toContain("grid-template-areas: none;");
+    });
+
+    it("round-trips through fromCss", () => {
+      const source = `
+        display: grid;
+        grid-template-columns: [full-start] 100px repeat(2, minmax(10px, 1fr)) [full-end];
+        grid-template-areas: "head head" ". main";
+        grid-row: 2 / span 3;
+        flex: 2 0 10px;
+        gap: 4px 8%;
+      `;
+      const style = Style.fromCss(source);
+      const copy = Style.fromCss(style.toCss({ onlyNonDefault: true }));
+
+      expect(copy.toCss()).toBe(style.toCss());
+      expect(copy.gridTemplateAreas).toEqual(style.gridTemplateAreas);
+      expect(copy.gap).toEqual({ width: "8%", height: 4 });
+    });
+
+    it("rejects malformed options", () => {
+      expect(() => new Style().toCss("yes" as never)).toThrow(TaffyError);
+    });
+  });
+
+  describe("Comparison", () => {
+    it("compares every property", () => {
+      const a = Style.fromCss("display: grid; width: 100px; margin: 0 auto");
+      const b = new Style({
+        display: Display.Grid,
+        width: 100,
+        marginLeft: "auto",
+        marginRight: "auto",
+      });
+
+      expect(a.equals(b)).toBe(true);
+      expect(a.diff(b)).toEqual([]);
+
+      b.flexGrow = 1;
+      b.gridTemplateColumns = [{ min: 10, max: "1fr" }];
+      expect(a.equals(b)).toBe(false);
+      expect(b.equals(a)).toBe(false);
+      expect(a.diff(b)).toEqual(["flexGrow", "gridTemplateColumns"]);
+    });
+
+    it("reports grouped property keys", () => {
+      const a = new Style();
+      const b = new Style({ height: 10, rowGap: 4, gridRowEnd: { span: 2 } });
+
+      expect(a.diff(b)).toEqual(["size", "gap", "gridRow"]);
+    });
+  });
 });
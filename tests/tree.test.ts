@@ -0,0 +1,1905 @@
+import { describe, it, expect, beforeAll } from "vitest";
+import { setupTaffy } from "./utils";
+import {
+  TaffyTree,
+  Style,
+  Display,
+  FlexDirection,
+  Overflow,
+  Position,
+  TaffyError,
+} from "../src/index";
+
+/** Waits for the microtasks queued so far, such as change callbacks */
+function flushMicrotasks(): Promise<void> {
+  return new Promise((resolve) => queueMicrotask(resolve));
+}
+
+function thrownBy(fn: () => unknown): unknown {
+  try {
+    fn();
+  } catch (e) {
+    return e;
+  }
+  throw new Error("expected the call to throw");
+}
+
+describe("Layout Buffer", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("packs a subtree in depth-first pre-order", () => {
+    const tree = new TaffyTree();
+
+    const rootStyle = new Style();
+    rootStyle.display = Display.Flex;
+    rootStyle.flexDirection = FlexDirection.Column;
+    rootStyle.size = { width: 100, height: 100 };
+    rootStyle.padding = { left: 5, right: 5, top: 5, bottom: 5 };
+
+    const childStyle = new Style();
+    childStyle.size = { width: 50, height: 20 };
+    childStyle.margin = { left: 1, right: 2, top: 3, bottom: 4 };
+
+    const grandchild = tree.newLeaf(childStyle);
+    const child1 = tree.newWithChildren(childStyle, [grandchild]);
+    const child2 = tree.newLeaf(childStyle);
+    const root = tree.newWithChildren(rootStyle, [child1, child2]);
+
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    const buffer = tree.getLayoutsBuffer(root);
+    expect(buffer.stride).toBe(18);
+    expect(buffer.length).toBe(4);
+    expect(Array.from(buffer.nodes)).toEqual([
+      root,
+      child1,
+      grandchild,
+      child2,
+    ]);
+
+    const data = buffer.data;
+    expect(data).toBeInstanceOf(Float32Array);
+    expect(data.length).toBe(4 * 18);
+
+    const nodes = buffer.nodes;
+    for (let i = 0; i < buffer.length; i++) {
+      const layout = tree.getLayout(nodes[i]);
+      const base = i * buffer.stride;
+      expect(Array.from(data.subarray(base, base + buffer.stride))).toEqual([
+        layout.x,
+        layout.y,
+        layout.width,
+        layout.height,
+        layout.contentWidth,
+        layout.contentHeight,
+        layout.borderLeft,
+        layout.borderRight,
+        layout.borderTop,
+        layout.borderBottom,
+        layout.paddingLeft,
+        layout.paddingRight,
+        layout.paddingTop,
+        layout.paddingBottom,
+        layout.marginLeft,
+        layout.marginRight,
+        layout.marginTop,
+        layout.marginBottom,
+      ]);
+    }
+
+    // Second child sits below the first one plus its vertical margins
+    const child2Base = 3 * buffer.stride;
+    expect(data[child2Base + 1]).toBe(5 + 3 + 20 + 4 + 3);
+    expect(data[child2Base + 14]).toBe(1);
+
+    buffer.free();
+    tree.free();
+  });
+
+  it("returns a single record for a leaf", () => {
+    const tree = new TaffyTree();
+    const style = new Style();
+    style.size = { width: 30, height: 40 };
+    const leaf = tree.newLeaf(style);
+    tree.computeLayout(leaf, { width: 100, height: 100 });
+
+    const buffer = tree.getLayoutsBuffer(leaf);
+    expect(buffer.length).toBe(1);
+    expect(Array.from(buffer.data.subarray(0, 4))).toEqual([0, 0, 30, 40]);
+
+    buffer.free();
+    tree.free();
+  });
+
+  it("throws for an unknown root", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style());
+    tree.remove(node);
+
+    expect(() => tree.getLayoutsBuffer(node)).toThrow(TaffyError);
+
+    tree.free();
+  });
+});
+
+describe("Absolute Layout", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildNested(tree: TaffyTree) {
+    const rootStyle = new Style();
+    rootStyle.size = { width: 200, height: 200 };
+    rootStyle.padding = { left: 10, right: 10, top: 20, bottom: 20 };
+
+    const middleStyle = new Style();
+    middleStyle.size = { width: 100, height: 100 };
+    middleStyle.padding = { left: 5, right: 5, top: 5, bottom: 5 };
+
+    const leafStyle = new Style();
+    leafStyle.size = { width: 10, height: 10 };
+
+    const leaf = tree.newLeaf(leafStyle);
+    const middle = tree.newWithChildren(middleStyle, [leaf]);
+    const root = tree.newWithChildren(rootStyle, [middle]);
+    tree.computeLayout(root, { width: 200, height: 200 });
+    return { root, middle, leaf };
+  }
+
+  it("accumulates offsets up to the layout root", () => {
+    const tree = new TaffyTree();
+    const { root, middle, leaf } = buildNested(tree);
+
+    expect(tree.getLayout(leaf).x).toBe(5);
+    const layout = tree.getAbsoluteLayout(leaf);
+    expect(layout.x).toBe(15);
+    expect(layout.y).toBe(25);
+    expect(layout.width).toBe(10);
+
+    expect(tree.getAbsoluteLayout(middle).y).toBe(20);
+    expect(tree.getAbsoluteLayout(root).x).toBe(0);
+
+    tree.free();
+  });
+
+  it("subtracts ancestor scroll offsets but not the node's own", () => {
+    const tree = new TaffyTree();
+    const { root, middle, leaf } = buildNested(tree);
+
+    const offsets = new Map([
+      [root, { x: 0, y: 8 }],
+      [middle, { x: 3, y: 0 }],
+    ]);
+    const middleLayout = tree.getAbsoluteLayout(middle, offsets);
+    expect(middleLayout.x).toBe(10);
+    expect(middleLayout.y).toBe(12);
+
+    const leafLayout = tree.getAbsoluteLayout(leaf, offsets);
+    expect(leafLayout.x).toBe(12);
+    expect(leafLayout.y).toBe(17);
+
+    tree.free();
+  });
+
+  it("matches getAbsoluteLayout in the bulk variant", () => {
+    const tree = new TaffyTree();
+    const { root, middle, leaf } = buildNested(tree);
+    const offsets = new Map([[middle, { x: 0, y: 4 }]]);
+
+    const buffer = tree.getAbsoluteLayoutsBuffer(middle, offsets);
+    expect(Array.from(buffer.nodes)).toEqual([middle, leaf]);
+    const data = buffer.data;
+    expect(data[0]).toBe(10);
+    expect(data[1]).toBe(20);
+    expect(data[buffer.stride]).toBe(tree.getAbsoluteLayout(leaf, offsets).x);
+    expect(data[buffer.stride + 1]).toBe(21);
+
+    expect(tree.getAbsoluteLayoutsBuffer(root).length).toBe(3);
+
+    buffer.free();
+    tree.free();
+  });
+});
+
+describe("Hit Testing", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function sized(width: number, height: number): Style {
+    const style = new Style();
+    style.size = { width, height };
+    return style;
+  }
+
+  it("returns the deepest node and its ancestor path", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(sized(20, 20));
+    const panel = tree.newWithChildren(sized(50, 50), [leaf]);
+    const root = tree.newWithChildren(sized(100, 100), [panel]);
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    expect(tree.hitTest(root, 10, 10)).toBe(leaf);
+    expect(tree.hitTest(root, 30, 30)).toBe(panel);
+    expect(tree.hitTest(root, 80, 80)).toBe(root);
+    expect(tree.hitTest(root, 100, 50)).toBeUndefined();
+    expect(tree.hitTestPath(root, 10, 10)).toEqual([root, panel, leaf]);
+    expect(tree.hitTestPath(root, 150, 150)).toEqual([]);
+
+    tree.free();
+  });
+
+  it("skips display none subtrees", () => {
+    const tree = new TaffyTree();
+    const hiddenStyle = sized(50, 50);
+    hiddenStyle.display = Display.None;
+    const hidden = tree.newLeaf(hiddenStyle);
+    const root = tree.newWithChildren(sized(100, 100), [hidden]);
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    expect(tree.hitTest(root, 10, 10)).toBe(root);
+
+    tree.free();
+  });
+
+  it("honours overflow clipping", () => {
+    const tree = new TaffyTree();
+    const overflowing = sized(80, 20);
+    overflowing.flexShrink = 0;
+    const child = tree.newLeaf(overflowing);
+
+    const clipStyle = sized(40, 40);
+    clipStyle.overflow = { x: Overflow.Hidden, y: Overflow.Visible };
+    const clipper = tree.newWithChildren(clipStyle, [child]);
+    const root = tree.newWithChildren(sized(100, 100), [clipper]);
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    expect(tree.hitTest(root, 10, 10)).toBe(child);
+    expect(tree.hitTest(root, 60, 10)).toBe(root);
+
+    clipStyle.overflow = { x: Overflow.Visible, y: Overflow.Visible };
+    tree.setStyle(clipper, clipStyle);
+    tree.computeLayout(root, { width: 100, height: 100 });
+    expect(tree.hitTest(root, 60, 10)).toBe(child);
+
+    tree.free();
+  });
+
+  it("prefers the topmost overlapping sibling", () => {
+    const tree = new TaffyTree();
+    const overlay = sized(50, 50);
+    overlay.position = Position.Absolute;
+    overlay.inset = { left: 0, top: 0, right: "auto", bottom: "auto" };
+    const first = tree.newLeaf(overlay);
+    const second = tree.newLeaf(overlay);
+    const root = tree.newWithChildren(sized(100, 100), [first, second]);
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    expect(tree.hitTest(root, 10, 10)).toBe(second);
+
+    tree.free();
+  });
+
+  it("applies scroll offsets", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(sized(20, 20));
+    const root = tree.newWithChildren(sized(100, 100), [leaf]);
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    const offsets = new Map([[root, { x: 0, y: 10 }]]);
+    expect(tree.hitTest(root, 5, 15, offsets)).toBe(root);
+    expect(tree.hitTest(root, 5, 5, offsets)).toBe(leaf);
+
+    tree.free();
+  });
+});
+
+describe("Rectangle Query", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildList(tree: TaffyTree, overflow: Overflow) {
+    const rowStyle = new Style();
+    rowStyle.size = { width: 100, height: 10 };
+    rowStyle.flexShrink = 0;
+    const rows: bigint[] = [];
+    for (let i = 0; i < 10; i++) {
+      rows.push(tree.newLeaf(rowStyle));
+    }
+    const listStyle = new Style();
+    listStyle.flexDirection = FlexDirection.Column;
+    listStyle.size = { width: 100, height: 30 };
+    listStyle.overflow = { x: overflow, y: overflow };
+    const list = tree.newWithChildren(listStyle, rows);
+    tree.computeLayout(list, { width: 100, height: 30 });
+    return { list, rows };
+  }
+
+  it("returns intersecting nodes in pre-order", () => {
+    const tree = new TaffyTree();
+    const { list, rows } = buildList(tree, Overflow.Visible);
+
+    const hits = tree.queryRect(list, { x: 0, y: 15, width: 50, height: 20 });
+    expect(hits).toEqual([list, rows[1], rows[2], rows[3]]);
+
+    const leaves = tree.queryRect(
+      list,
+      { x: 0, y: 15, width: 50, height: 20 },
+      true,
+    );
+    expect(leaves).toEqual([rows[1], rows[2], rows[3]]);
+
+    // Touching edges do not count as an intersection
+    expect(
+      tree.queryRect(list, { x: 0, y: 10, width: 100, height: 10 }, true),
+    ).toEqual([rows[1]]);
+
+    tree.free();
+  });
+
+  it("prunes content clipped by overflow", () => {
+    const tree = new TaffyTree();
+    const { list, rows } = buildList(tree, Overflow.Hidden);
+
+    expect(
+      tree.queryRect(list, { x: 0, y: 0, width: 100, height: 100 }, true),
+    ).toEqual([rows[0], rows[1], rows[2]]);
+    expect(
+      tree.queryRect(list, { x: 0, y: 50, width: 100, height: 20 }),
+    ).toEqual([]);
+
+    const scrolled = tree.queryRect(
+      list,
+      { x: 0, y: 0, width: 100, height: 100 },
+      true,
+      new Map([[list, { x: 0, y: 45 }]]),
+    );
+    expect(scrolled).toEqual([rows[4], rows[5], rows[6], rows[7]]);
+
+    tree.free();
+  });
+});
+
+describe("Tree Snapshots", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("round-trips structure, styles and contexts through JSON", () => {
+    const tree = new TaffyTree();
+    const rootStyle = new Style({
+      display: Display.Flex,
+      flexDirection: FlexDirection.Column,
+      size: { width: 200, height: "auto" },
+      padding: { left: 4, right: 4, top: 2, bottom: 2 },
+    });
+    const a = tree.newLeafWithContext(new Style({ height: 20 }), { text: "a" });
+    const b = tree.newLeaf(new Style({ flexGrow: 1, marginTop: "auto" }));
+    const group = tree.newWithChildren(new Style(), [b]);
+    const root = tree.newWithChildren(rootStyle, [a, group]);
+
+    const snapshot = tree.toSnapshot(root, { includeContext: true });
+    expect(snapshot.version).toBe(1);
+    expect(snapshot.root).toBe(0);
+    expect(snapshot.nodes.map((n) => n.children)).toEqual([
+      [1, 2],
+      [],
+      [3],
+      [],
+    ]);
+    expect(snapshot.nodes[0].style.display).toBe(Display.Flex);
+    expect(snapshot.nodes[2].style).toEqual({});
+    expect(snapshot.nodes[1].context).toEqual({ text: "a" });
+    expect(snapshot.nodes[2].context).toBeUndefined();
+
+    const copy = new TaffyTree();
+    const restored = copy.fromSnapshot(JSON.parse(JSON.stringify(snapshot)));
+    tree.computeLayout(root, { width: 300, height: 300 });
+    copy.computeLayout(restored, { width: 300, height: 300 });
+
+    expect(copy.toSnapshot(restored, { includeContext: true })).toEqual(
+      snapshot,
+    );
+    expect(Array.from(copy.getLayoutsBuffer(restored).data)).toEqual(
+      Array.from(tree.getLayoutsBuffer(root).data),
+    );
+    const [restoredA] = copy.children(restored);
+    expect(copy.getNodeContext(restoredA)).toEqual({ text: "a" });
+
+    tree.free();
+    copy.free();
+  });
+
+  it("includes layouts only when requested", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style({ width: 30, height: 40 }));
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    expect(tree.toSnapshot(root).nodes[0].layout).toBeUndefined();
+    const layout = tree.toSnapshot(root, { includeLayout: true }).nodes[0]
+      .layout;
+    expect(layout?.size).toEqual({ width: 30, height: 40 });
+    expect(layout?.position).toEqual({ x: 0, y: 0 });
+
+    tree.free();
+  });
+
+  it("rejects malformed snapshots without modifying the tree", () => {
+    const tree = new TaffyTree();
+    const style = {};
+    const cases: unknown[] = [
+      { version: 2, root: 0, nodes: [{ style, children: [] }] },
+      { version: 1, root: 1, nodes: [{ style, children: [] }] },
+      { version: 1, root: 0, nodes: [{ style: { bogus: 1 }, children: [] }] },
+      { version: 1, root: 0, nodes: [{ style: { display: 9 }, children: [] }] },
+      { version: 1, root: 0, nodes: [{ style, children: [0] }] },
+      {
+        version: 1,
+        root: 0,
+        nodes: [
+          { style, children: [1, 1] },
+          { style, children: [] },
+        ],
+      },
+      {
+        version: 1,
+        root: 0,
+        nodes: [
+          { style, children: [] },
+          { style, children: [] },
+        ],
+      },
+    ];
+    for (const doc of cases) {
+      // @ts-expect-error - testing invalid documents
+      expect(thrownBy(() => tree.fromSnapshot(doc))).toBeInstanceOf(
+        TaffyError,
+      );
+    }
+    expect(tree.totalNodeCount()).toBe(0);
+
+    tree.free();
+  });
+
+  it("refuses to snapshot a node with two parents", () => {
+    const tree = new TaffyTree();
+    const shared = tree.newLeaf(new Style());
+    const left = tree.newWithChildren(new Style(), [shared]);
+    const right = tree.newWithChildren(new Style(), [shared]);
+    const root = tree.newWithChildren(new Style(), [left, right]);
+
+    const error = thrownBy(() => tree.toSnapshot(root)) as TaffyError;
+    expect(error).toBeInstanceOf(TaffyError);
+    expect(error.message).toContain("reachable more than once");
+
+    tree.free();
+  });
+});
+
+describe("Binary Snapshots", () => {
+  it("round-trips structure and styles", () => {
+    const source = new TaffyTree();
+    const a = source.newLeaf(new Style({ width: 30, flexGrow: 2 }));
+    const b = source.newLeaf(
+      new Style({ gridColumn: { start: 1, end: { span: 2 } } }),
+    );
+    const root = source.newWithChildren(
+      new Style({
+        display: Display.Grid,
+        padding: { left: 4, right: "10%", top: 0, bottom: 0 },
+      }),
+      [a, b],
+    );
+    const bytes = source.toBytes(root);
+    expect(bytes).toBeInstanceOf(Uint8Array);
+    expect(Array.from(bytes.slice(0, 4))).toEqual([0x54, 0x41, 0x46, 0x59]);
+
+    const tree = new TaffyTree();
+    const restored = tree.fromBytes(bytes);
+    expect(tree.toSnapshot(restored)).toEqual(source.toSnapshot(root));
+
+    source.free();
+    tree.free();
+  });
+
+  it("rejects truncated and mismatched input without modifying the tree", () => {
+    const source = new TaffyTree();
+    const root = source.newWithChildren(new Style(), [
+      source.newLeaf(new Style({ width: 10 })),
+    ]);
+    const bytes = source.toBytes(root);
+
+    const tree = new TaffyTree();
+    const wrongVersion = bytes.slice();
+    wrongVersion[4] = 99;
+    const cases = [
+      bytes.slice(0, bytes.length - 1),
+      new Uint8Array([1, 2, 3]),
+      wrongVersion,
+    ];
+    for (const input of cases) {
+      expect(thrownBy(() => tree.fromBytes(input))).toBeInstanceOf(TaffyError);
+    }
+    const error = thrownBy(() => tree.fromBytes(wrongVersion)) as TaffyError;
+    expect(error.message).toContain("unsupported version 99");
+    expect(tree.totalNodeCount()).toBe(0);
+
+    source.free();
+    tree.free();
+  });
+});
+
+describe("Subtree Cloning", () => {
+  it("copies styles, child order and contexts within a tree", () => {
+    const tree = new TaffyTree();
+    const context = { text: "first" };
+    const first = tree.newLeafWithContext(new Style({ width: 10 }), context);
+    const second = tree.newLeaf(new Style({ width: 20 }));
+    const row = tree.newWithChildren(new Style({ display: Display.Flex }), [
+      first,
+      second,
+    ]);
+    const list = tree.newWithChildren(new Style(), [row]);
+
+    const copy = tree.cloneSubtree(row);
+    expect(copy).not.toBe(row);
+    expect(tree.parent(copy)).toBeUndefined();
+    expect(tree.toSnapshot(copy, { includeContext: true })).toEqual(
+      tree.toSnapshot(row, { includeContext: true }),
+    );
+    const copiedFirst = tree.getChildAtIndex(copy, 0);
+    expect(copiedFirst).not.toBe(first);
+    expect(tree.getNodeContext(copiedFirst)).toBe(context);
+    expect(tree.childCount(list)).toBe(1);
+
+    tree.free();
+  });
+
+  it("copies into another tree without modifying the source", () => {
+    const source = new TaffyTree();
+    const root = source.newWithChildren(new Style({ height: 40 }), [
+      source.newLeaf(new Style({ width: 10 })),
+    ]);
+    const target = new TaffyTree();
+
+    const copy = source.cloneSubtreeInto(target, root);
+    expect(target.totalNodeCount()).toBe(2);
+    expect(source.totalNodeCount()).toBe(2);
+    expect(target.toSnapshot(copy)).toEqual(source.toSnapshot(root));
+
+    source.free();
+    target.free();
+  });
+
+  it("rejects the source tree as the target and stays usable", () => {
+    const tree = new TaffyTree();
+    const root = tree.newWithChildren(new Style(), [tree.newLeaf(new Style())]);
+
+    const error = thrownBy(() => tree.cloneSubtreeInto(tree, root)) as TaffyError;
+    expect(error).toBeInstanceOf(TaffyError);
+    expect(error.code).toBe("InvalidArgument");
+    expect(tree.totalNodeCount()).toBe(2);
+
+    const copy = tree.cloneSubtree(root);
+    tree.addChild(root, copy);
+    expect(tree.childCount(root)).toBe(2);
+    expect(tree.totalNodeCount()).toBe(4);
+
+    tree.free();
+  });
+
+  it("rejects a target that is not a tree", () => {
+    const source = new TaffyTree();
+    const root = source.newLeaf(new Style());
+
+    const error = thrownBy(() =>
+      source.cloneSubtreeInto({} as TaffyTree, root),
+    ) as TaffyError;
+    expect(error.code).toBe("InvalidArgument");
+    expect(source.totalNodeCount()).toBe(1);
+
+    source.free();
+  });
+
+  it("reports nodes with two parents as an integrity violation", () => {
+    const tree = new TaffyTree();
+    const shared = tree.newLeaf(new Style());
+    const left = tree.newWithChildren(new Style(), [shared]);
+    const right = tree.newWithChildren(new Style(), [shared]);
+    const root = tree.newWithChildren(new Style(), [left, right]);
+
+    const error = thrownBy(() => tree.cloneSubtree(root)) as TaffyError;
+    expect(error.code).toBe("IntegrityViolation");
+    expect(error.message).toContain(`node ${shared} is reachable more than once`);
+    expect(tree.totalNodeCount()).toBe(4);
+
+    tree.free();
+  });
+});
+
+describe("Moving Nodes", () => {
+  it("reparents a node at the requested index", () => {
+    const tree = new TaffyTree();
+    const item = tree.newLeaf(new Style());
+    const from = tree.newWithChildren(new Style(), [item]);
+    const a = tree.newLeaf(new Style());
+    const b = tree.newLeaf(new Style());
+    const to = tree.newWithChildren(new Style(), [a, b]);
+
+    tree.moveNode(item, to, 1);
+    expect(tree.children(from)).toEqual([]);
+    expect(tree.children(to)).toEqual([a, item, b]);
+    expect(tree.parent(item)).toBe(to);
+
+    tree.free();
+  });
+
+  it("appends when no index is given and reorders within a parent", () => {
+    const tree = new TaffyTree();
+    const a = tree.newLeaf(new Style());
+    const b = tree.newLeaf(new Style());
+    const c = tree.newLeaf(new Style());
+    const parent = tree.newWithChildren(new Style(), [a, b, c]);
+
+    tree.moveNode(a, parent);
+    expect(tree.children(parent)).toEqual([b, c, a]);
+    tree.moveNode(a, parent, 0);
+    expect(tree.children(parent)).toEqual([a, b, c]);
+
+    tree.free();
+  });
+
+  it("rejects cycles and bad indices without changing the tree", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(new Style());
+    const inner = tree.newWithChildren(new Style(), [leaf]);
+    const root = tree.newWithChildren(new Style(), [inner]);
+
+    const cycle = thrownBy(() => tree.moveNode(inner, leaf)) as TaffyError;
+    expect(cycle).toBeInstanceOf(TaffyError);
+    expect(cycle.message).toContain("cycle");
+    expect(thrownBy(() => tree.moveNode(root, root))).toBeInstanceOf(
+      TaffyError,
+    );
+    expect(thrownBy(() => tree.moveNode(leaf, root, 5))).toBeInstanceOf(
+      TaffyError,
+    );
+    expect(tree.children(root)).toEqual([inner]);
+    expect(tree.children(inner)).toEqual([leaf]);
+
+    tree.free();
+  });
+});
+
+describe("Tree Validation", () => {
+  it("reports nothing for a well-formed tree", () => {
+    const tree = new TaffyTree();
+    const root = tree.newWithChildren(new Style(), [
+      tree.newLeaf(new Style()),
+      tree.newWithChildren(new Style(), [tree.newLeaf(new Style())]),
+    ]);
+    expect(tree.validate(root)).toEqual([]);
+
+    tree.free();
+  });
+
+  it("reports shared, dangling and cyclic children", () => {
+    const tree = new TaffyTree();
+    const shared = tree.newLeaf(new Style());
+    const doomed = tree.newLeaf(new Style());
+    const a = tree.newWithChildren(new Style(), [shared, doomed]);
+    const b = tree.newWithChildren(new Style(), [shared, doomed]);
+    const root = tree.newWithChildren(new Style(), [a, b]);
+    tree.remove(doomed);
+    tree.addChild(shared, a);
+
+    const kinds = tree.validate(root).map((issue) => issue.kind);
+    expect(kinds).toContain("multipleParents");
+    expect(kinds).toContain("danglingChild");
+    expect(kinds).toContain("cycle");
+    expect(kinds).toContain("parentMismatch");
+
+    tree.free();
+  });
+
+  it("rejects invalid edits in strict mode", () => {
+    const tree = new TaffyTree();
+    tree.enableStrictMode();
+    const child = tree.newLeaf(new Style());
+    const parent = tree.newWithChildren(new Style(), [child]);
+    const other = tree.newLeaf(new Style());
+    const removed = tree.newLeaf(new Style());
+    tree.remove(removed);
+
+    const attempts = [
+      () => tree.addChild(other, child),
+      () => tree.addChild(child, parent),
+      () => tree.addChild(parent, parent),
+      () => tree.addChild(parent, removed),
+      () => tree.setChildren(other, [child, child]),
+      () => tree.newWithChildren(new Style(), [child]),
+      () => tree.remove(removed),
+    ];
+    for (const attempt of attempts) {
+      const error = thrownBy(attempt) as TaffyError;
+      expect(error).toBeInstanceOf(TaffyError);
+    }
+    expect(tree.validate(parent)).toEqual([]);
+
+    // setChildren detaches children from their previous parent
+    tree.setChildren(other, [child]);
+    expect(tree.children(parent)).toEqual([]);
+    expect(tree.validate(other)).toEqual([]);
+
+    tree.disableStrictMode();
+    tree.addChild(parent, child);
+    expect(tree.validate(other)).toEqual([
+      { kind: "parentMismatch", node: child, parent: other },
+    ]);
+
+    tree.free();
+  });
+});
+
+describe("Error Details", () => {
+  it("exposes a code and the offending indices", () => {
+    const tree = new TaffyTree();
+    const parent = tree.newWithChildren(new Style(), [
+      tree.newLeaf(new Style()),
+    ]);
+
+    const error = thrownBy(() =>
+      tree.getChildAtIndex(parent, 3),
+    ) as TaffyError;
+    expect(error.code).toBe("ChildIndexOutOfBounds");
+    expect(error.parent).toBe(parent);
+    expect(error.childIndex).toBe(3);
+    expect(error.childCount).toBe(1);
+    expect(error.node).toBeUndefined();
+
+    tree.free();
+  });
+
+  it("names the nodes of a rejected move", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(new Style());
+    const root = tree.newWithChildren(new Style(), [leaf]);
+
+    const error = thrownBy(() => tree.moveNode(root, leaf)) as TaffyError;
+    expect(error.code).toBe("WouldCreateCycle");
+    expect(error.node).toBe(root);
+    expect(error.parent).toBe(leaf);
+    expect(error.childIndex).toBeUndefined();
+
+    tree.free();
+  });
+
+  it("behaves like a JavaScript Error", () => {
+    const tree = new TaffyTree();
+    const error = thrownBy(() =>
+      tree.fromBytes(new Uint8Array([0])),
+    ) as TaffyError;
+
+    expect(error).toBeInstanceOf(TaffyError);
+    expect(error).toBeInstanceOf(Error);
+    expect(error.name).toBe("TaffyError");
+    expect(error.code).toBe("InvalidSnapshot");
+    expect(error.stack).toContain(`TaffyError: ${error.message}`);
+    expect(String(error)).toBe(`TaffyError: ${error.message}`);
+
+    tree.free();
+  });
+});
+
+describe("Argument Errors", () => {
+  it("names the malformed available space field", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+
+    const error = thrownBy(() =>
+      tree.computeLayout(root, { width: "fit-content", height: 100 } as never),
+    ) as TaffyError;
+    expect(error.code).toBe("InvalidAvailableSpace");
+    expect(error.message).toBe(
+      'availableSpace.width: "fit-content" is not a valid AvailableSpace',
+    );
+    expect(error.node).toBeUndefined();
+
+    tree.free();
+  });
+
+  it("reports other malformed arguments as InvalidArgument", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+
+    const children = thrownBy(() =>
+      tree.setChildren(root, ["a"] as never),
+    ) as TaffyError;
+    expect(children.code).toBe("InvalidArgument");
+    expect(children.message).toContain("children");
+
+    const offsets = thrownBy(() =>
+      tree.hitTest(root, 0, 0, {} as never),
+    ) as TaffyError;
+    expect(offsets.code).toBe("InvalidArgument");
+    expect(offsets.message).toContain("scrollOffsets");
+
+    tree.free();
+  });
+});
+
+describe("Conditional Style Updates", () => {
+  it("skips dirtying when the style is unchanged", () => {
+    const tree = new TaffyTree();
+    const child = tree.newLeaf(new Style({ width: 50 }));
+    const root = tree.newWithChildren(new Style(), [child]);
+    tree.computeLayout(root, { width: 200, height: 200 });
+
+    expect(tree.setStyleIfChanged(child, new Style({ width: 50 }))).toBe(false);
+    expect(tree.dirty(child)).toBe(false);
+    expect(tree.dirty(root)).toBe(false);
+
+    expect(tree.setStyleIfChanged(child, new Style({ width: 80 }))).toBe(true);
+    expect(tree.dirty(child)).toBe(true);
+    expect(tree.dirty(root)).toBe(true);
+    expect(tree.getStyle(child).width).toBe(80);
+
+    tree.free();
+  });
+
+  it("throws for unknown nodes", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style());
+    tree.remove(node);
+
+    expect(() => tree.setStyleIfChanged(node, new Style())).toThrow(TaffyError);
+
+    tree.free();
+  });
+});
+
+describe("Partial Style Updates", () => {
+  it("updates properties in place and dirties only on change", () => {
+    const tree = new TaffyTree();
+    const child = tree.newLeaf(new Style({ width: 50 }));
+    const root = tree.newWithChildren(new Style(), [child]);
+    tree.computeLayout(root, { width: 200, height: 200 });
+
+    expect(tree.updateStyle(child, { width: 50 })).toBe(false);
+    expect(tree.dirty(root)).toBe(false);
+
+    expect(tree.updateStyle(child, { width: 100, flexGrow: 1 })).toBe(true);
+    expect(tree.dirty(root)).toBe(true);
+    expect(tree.getStyle(child).get("width", "flexGrow")).toEqual([100, 1]);
+
+    tree.free();
+  });
+
+  it("leaves the style unchanged when strict validation fails", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style({ width: 50 }));
+
+    const error = thrownBy(() =>
+      tree.updateStyle(node, { width: 80, display: 9 } as never, true),
+    ) as TaffyError;
+    expect(error.code).toBe("InvalidStyle");
+    expect(tree.getStyleProperty(node, "width")).toBe(50);
+
+    expect(() => tree.updateStyle(node, { widht: 1 } as never)).toThrow(
+      "Unknown property path for set: widht",
+    );
+
+    tree.free();
+  });
+
+  it("records skipped values as warnings of the tree", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style({ width: 50 }));
+
+    expect(tree.updateStyle(node, { width: "wide", flexGrow: 2 } as never)).toBe(
+      true,
+    );
+    expect(tree.updateStyle(node, 42 as never)).toBe(false);
+    expect(tree.takeWarnings()).toEqual([
+      'updateStyle(): width: "wide" is not a valid Dimension',
+      "updateStyle(): 42 is not an object of style properties",
+    ]);
+    expect(tree.takeWarnings()).toEqual([]);
+
+    const error = thrownBy(() =>
+      tree.updateStyle(node, 42 as never, true),
+    ) as TaffyError;
+    expect(error.code).toBe("InvalidStyle");
+
+    tree.free();
+  });
+
+  it("reads properties like Style.get", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(
+      new Style({ display: Display.Grid, width: "50%", marginLeft: 8 }),
+    );
+
+    expect(tree.getStyleProperty(node, "display")).toBe(Display.Grid);
+    expect(tree.getStyleProperty(node, "width", "marginLeft")).toEqual([
+      "50%",
+      8,
+    ]);
+    expect(tree.getStyleProperty(node, "margin")).toEqual(
+      tree.getStyle(node).get("margin"),
+    );
+
+    tree.remove(node);
+    expect(() => tree.getStyleProperty(node, "width")).toThrow(TaffyError);
+
+    tree.free();
+  });
+
+  it("keeps the tree usable after an unknown property path", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style({ width: 50 }));
+
+    expect(() => tree.getStyleProperty(node, "widht" as never)).toThrow(
+      "Unknown property path: widht",
+    );
+    expect(() => tree.getStyle(node).get("widht" as never)).toThrow(
+      "Unknown property path: widht",
+    );
+
+    expect(tree.updateStyle(node, { height: 20 })).toBe(true);
+    tree.computeLayout(node, { width: 100, height: 100 });
+    expect(tree.getLayout(node).width).toBe(50);
+    expect(tree.getLayout(node).height).toBe(20);
+
+    tree.free();
+  });
+
+  it("applies overlapping properties in order", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style({ width: 50 }));
+
+    expect(
+      tree.updateStyle(node, {
+        width: 80,
+        size: { width: 50, height: "auto" },
+      }),
+    ).toBe(false);
+    expect(
+      tree.updateStyle(node, { size: { width: 10, height: 20 }, width: 30 }),
+    ).toBe(true);
+    expect(tree.getStyleProperty(node, "width", "height")).toEqual([30, 20]);
+
+    tree.free();
+  });
+});
+
+describe("Batched Mutations", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("creates and links nodes referenced by op index", () => {
+    const tree = new TaffyTree();
+    const existing = tree.newLeaf(new Style({ height: 10 }));
+
+    const results = tree.applyBatch([
+      { op: "create", style: { display: Display.Flex, width: 200 } },
+      { op: "create", context: { text: "a" } },
+      { op: "insert", parent: 0, child: 1 },
+      { op: "insert", parent: 0, child: existing, index: 0 },
+      { op: "updateStyle", node: 1, style: { flexGrow: 1 } },
+      { op: "create", style: { width: 10 }, children: [] },
+    ]);
+
+    expect(results).toHaveLength(6);
+    const [root, label] = results as bigint[];
+    expect(typeof root).toBe("bigint");
+    expect(results[2]).toBeUndefined();
+    expect(tree.children(root)).toEqual([existing, label]);
+    expect(tree.getNodeContext(label)).toEqual({ text: "a" });
+    expect(tree.getStyleProperty(label, "flexGrow")).toBe(1);
+    expect(tree.totalNodeCount()).toBe(4);
+
+    tree.free();
+  });
+
+  it("rolls back every operation when one fails", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style({ width: 100 }));
+
+    const error = thrownBy(() =>
+      tree.applyBatch([
+        { op: "create" },
+        { op: "insert", parent: root, child: 0 },
+        { op: "setStyle", node: root, style: { width: 300 } },
+        { op: "insert", parent: root, child: 0, index: 5 },
+      ]),
+    ) as TaffyError;
+
+    expect(error).toBeInstanceOf(TaffyError);
+    expect(error.code).toBe("BatchFailed");
+    expect(error.opIndex).toBe(3);
+    expect((error.cause as TaffyError).code).toBe("ChildIndexOutOfBounds");
+    expect(tree.totalNodeCount()).toBe(1);
+    expect(tree.childCount(root)).toBe(0);
+    expect(tree.getStyleProperty(root, "width")).toBe(100);
+
+    tree.free();
+  });
+
+  it("restores removed nodes and frees created nodes on failure", () => {
+    const tree = new TaffyTree();
+    const child = tree.newLeaf(new Style({ width: 10 }));
+    const root = tree.newWithChildren(new Style(), [child]);
+
+    const error = thrownBy(() =>
+      tree.applyBatch([
+        { op: "create", children: [child] },
+        { op: "remove", node: child },
+        { op: "setChildren", parent: root, children: [0] },
+        { op: "insert", parent: root, child: 12345n },
+      ]),
+    ) as TaffyError;
+
+    expect(error.opIndex).toBe(3);
+    expect(tree.totalNodeCount()).toBe(2);
+    expect(tree.children(root)).toEqual([child]);
+    expect(tree.parent(child)).toBe(root);
+    expect(tree.getStyleProperty(child, "width")).toBe(10);
+    expect(tree.validate(root)).toEqual([]);
+
+    tree.free();
+  });
+
+  it("decodes every operation before running any", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+    tree.computeLayout(root, { width: 100, height: 100 });
+
+    const error = thrownBy(() =>
+      tree.applyBatch([
+        { op: "markDirty", node: root },
+        { op: "create", style: { widht: 1 } as never },
+      ]),
+    ) as TaffyError;
+
+    expect(error.opIndex).toBe(1);
+    expect(tree.dirty(root)).toBe(false);
+
+    tree.free();
+  });
+
+  it("records skipped style values as warnings of the tree", () => {
+    const tree = new TaffyTree();
+
+    const [node] = tree.applyBatch([
+      { op: "create", style: { width: "wide" } as never },
+      { op: "updateStyle", node: 0, style: { display: 42 } as never },
+    ]);
+
+    expect(tree.totalNodeCount()).toBe(1);
+    expect(tree.takeWarnings()).toEqual([
+      'applyBatch(): ops[0]: width: "wide" is not a valid Dimension',
+      "applyBatch(): ops[1]: display: 42 is not a valid Display",
+    ]);
+
+    expect(() =>
+      tree.applyBatch([
+        { op: "setStyle", node: node!, style: { width: "wide" } as never },
+        { op: "remove", node: 12345n },
+      ]),
+    ).toThrow(TaffyError);
+    expect(tree.takeWarnings()).toEqual([]);
+
+    tree.free();
+  });
+
+  it("reports malformed operations before running them", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+
+    const unknownOp = thrownBy(() =>
+      tree.applyBatch([{ op: "create" }, { op: "explode" } as never]),
+    ) as TaffyError;
+    expect(unknownOp.code).toBe("BatchFailed");
+    expect(unknownOp.opIndex).toBe(1);
+    expect((unknownOp.cause as TaffyError).code).toBe("InvalidArgument");
+    expect(tree.totalNodeCount()).toBe(1);
+
+    const forwardRef = thrownBy(() =>
+      tree.applyBatch([{ op: "insert", parent: root, child: 1 }, { op: "create" }]),
+    ) as TaffyError;
+    expect(forwardRef.opIndex).toBe(0);
+    expect((forwardRef.cause as TaffyError).message).toMatch(/^ops\[0\]\.child: /);
+
+    expect(() => tree.applyBatch({} as never)).toThrow("ops");
+
+    tree.free();
+  });
+
+  it("rejects references to removed nodes", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style());
+
+    const error = thrownBy(() =>
+      tree.applyBatch([
+        { op: "remove", node },
+        { op: "markDirty", node },
+      ]),
+    ) as TaffyError;
+    expect(error.opIndex).toBe(1);
+    expect((error.cause as TaffyError).code).toBe("InvalidInputNode");
+    expect(tree.totalNodeCount()).toBe(1);
+
+    tree.free();
+  });
+});
+
+describe("Transactions", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("restores structure, styles and contexts on rollback", () => {
+    const tree = new TaffyTree();
+    const child = tree.newLeafWithContext(new Style({ width: 10 }), "before");
+    const root = tree.newWithChildren(new Style(), [child]);
+
+    tree.beginTransaction();
+    const added = tree.newLeaf(new Style());
+    tree.addChild(root, added);
+    tree.setStyle(child, new Style({ width: 50 }));
+    tree.setNodeContext(child, "after");
+    tree.remove(child);
+    tree.rollback();
+
+    expect(tree.transactionDepth()).toBe(0);
+    expect(tree.children(root)).toEqual([child]);
+    expect(tree.getStyleProperty(child, "width")).toBe(10);
+    expect(tree.getNodeContext(child)).toBe("before");
+    expect(tree.totalNodeCount()).toBe(2);
+    tree.enableStrictMode();
+    expect(() => tree.addChild(root, added)).toThrow(TaffyError);
+
+    tree.free();
+  });
+
+  it("keeps changes on commit", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+
+    tree.beginTransaction();
+    const child = tree.newLeaf(new Style());
+    tree.addChild(root, child);
+    tree.commit();
+
+    expect(tree.children(root)).toEqual([child]);
+    expect(tree.transactionDepth()).toBe(0);
+
+    tree.free();
+  });
+
+  it("rolls back nested transactions independently", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+
+    tree.beginTransaction();
+    const outer = tree.newLeaf(new Style());
+    tree.addChild(root, outer);
+    tree.beginTransaction();
+    tree.addChild(root, tree.newLeaf(new Style()));
+    expect(tree.transactionDepth()).toBe(2);
+    tree.rollback();
+
+    expect(tree.children(root)).toEqual([outer]);
+    tree.rollback();
+    expect(tree.childCount(root)).toBe(0);
+
+    tree.free();
+  });
+
+  it("restores the history, measure functions and rounding on rollback", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory();
+    const root = tree.newLeaf(new Style());
+    const leaf = tree.newLeaf(new Style());
+    const measure = () => ({ width: 10, height: 10 });
+    tree.setMeasureFunc(leaf, measure);
+    tree.addChild(root, leaf);
+    tree.checkpoint("Add leaf");
+
+    tree.beginTransaction();
+    tree.undo();
+    tree.clearMeasureFunc(leaf);
+    tree.disableRounding();
+    tree.clear();
+    tree.rollback();
+
+    expect(tree.children(root)).toEqual([leaf]);
+    expect(tree.undoName()).toBe("Add leaf");
+    expect(tree.canRedo()).toBe(false);
+    expect(tree.clearMeasureFunc(leaf)).toBe(true);
+
+    tree.free();
+  });
+
+  it("only invalidates nodes created inside the rolled back transaction", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style());
+    const child = tree.newLeaf(new Style());
+    tree.addChild(root, child);
+
+    tree.beginTransaction();
+    const created = tree.newWithChildren(new Style(), [tree.newLeaf(new Style())]);
+    tree.remove(child);
+    tree.rollback();
+
+    expect(tree.children(root)).toEqual([child]);
+    expect(tree.totalNodeCount()).toBe(2);
+    expect(() => tree.childCount(created)).toThrow(TaffyError);
+
+    tree.free();
+  });
+
+  it("throws NoTransaction without an open transaction", () => {
+    const tree = new TaffyTree();
+
+    const commitError = thrownBy(() => tree.commit()) as TaffyError;
+    expect(commitError.code).toBe("NoTransaction");
+    expect(commitError.message).toBe("commit() called without an open transaction");
+    expect(() => tree.rollback()).toThrow("rollback() called without an open transaction");
+
+    tree.free();
+  });
+});
+
+describe("Undo History", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("undoes and redoes checkpointed steps", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory();
+    const root = tree.newLeaf(new Style());
+    const item = tree.newLeafWithContext(new Style({ width: 10 }), "a");
+
+    tree.addChild(root, item);
+    tree.checkpoint("Add item");
+    tree.setStyle(item, new Style({ width: 20 }));
+    tree.setNodeContext(item, "b");
+    tree.checkpoint("Edit item");
+
+    expect(tree.undoName()).toBe("Edit item");
+    expect(tree.undo()).toBe(true);
+    expect(tree.getStyleProperty(item, "width")).toBe(10);
+    expect(tree.getNodeContext(item)).toBe("a");
+    expect(tree.redoName()).toBe("Edit item");
+
+    expect(tree.undo()).toBe(true);
+    expect(tree.childCount(root)).toBe(0);
+    expect(tree.canUndo()).toBe(false);
+    expect(tree.undo()).toBe(false);
+
+    expect(tree.redo()).toBe(true);
+    expect(tree.redo()).toBe(true);
+    expect(tree.children(root)).toEqual([item]);
+    expect(tree.getNodeContext(item)).toBe("b");
+    expect(tree.canRedo()).toBe(false);
+
+    tree.free();
+  });
+
+  it("brings removed nodes back under their original ids", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory();
+    const child = tree.newLeaf(new Style());
+    const middle = tree.newWithChildren(new Style(), [child]);
+    const root = tree.newWithChildren(new Style(), [middle]);
+
+    tree.remove(middle);
+    expect(tree.totalNodeCount()).toBe(2);
+    expect(tree.parent(child)).toBeUndefined();
+
+    tree.undo();
+    expect(tree.totalNodeCount()).toBe(3);
+    expect(tree.children(root)).toEqual([middle]);
+    expect(tree.children(middle)).toEqual([child]);
+
+    tree.free();
+  });
+
+  it("drops redo steps on new edits and old steps beyond the depth", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory(2);
+    const node = tree.newLeaf(new Style());
+
+    for (const width of [1, 2, 3]) {
+      tree.updateStyle(node, { width });
+      tree.checkpoint(`width ${width}`);
+    }
+    expect(tree.undo()).toBe(true);
+    expect(tree.undo()).toBe(true);
+    expect(tree.undo()).toBe(false);
+    expect(tree.getStyleProperty(node, "width")).toBe(1);
+
+    tree.redo();
+    tree.updateStyle(node, { height: 5 });
+    expect(tree.canRedo()).toBe(false);
+
+    tree.free();
+  });
+
+  it("rejects removed nodes kept for undo", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory();
+    const root = tree.newLeaf(new Style());
+    const node = tree.newLeafWithContext(new Style({ width: 10 }), "a");
+    tree.remove(node);
+
+    const attempts = [
+      () => tree.addChild(root, node),
+      () => tree.setChildren(root, [node]),
+      () => tree.setStyle(node, new Style()),
+      () => tree.updateStyle(node, { width: 20 }),
+      () => tree.setNodeContext(node, "b"),
+      () => tree.getNodeContext(node),
+      () => tree.getStyle(node),
+      () => tree.getLayout(node),
+      () => tree.childCount(node),
+      () => tree.parent(node),
+      () => tree.markDirty(node),
+      () => tree.remove(node),
+    ];
+    for (const attempt of attempts) {
+      const error = thrownBy(attempt) as TaffyError;
+      expect(error).toBeInstanceOf(TaffyError);
+    }
+
+    tree.undo();
+    expect(tree.getNodeContext(node)).toBe("a");
+
+    tree.free();
+  });
+
+  it("keeps nodes once their removal leaves the history", () => {
+    const tree = new TaffyTree();
+    tree.enableHistory(1);
+    const root = tree.newLeaf(new Style());
+    const child = tree.newLeaf(new Style());
+    tree.addChild(root, child);
+
+    tree.remove(child);
+    tree.checkpoint();
+    expect(() => tree.addChild(root, child)).toThrow(TaffyError);
+    tree.checkpoint();
+    const other = tree.newLeaf(new Style());
+    tree.addChild(root, other);
+    tree.checkpoint();
+
+    expect(tree.children(root)).toEqual([other]);
+    expect(tree.validate(root)).toEqual([]);
+    expect(() => tree.getStyle(child)).toThrow(TaffyError);
+
+    tree.free();
+  });
+
+  it("records nothing while disabled", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style());
+    tree.setStyle(node, new Style({ width: 5 }));
+
+    expect(tree.checkpoint("ignored")).toBe(false);
+    expect(tree.undo()).toBe(false);
+    expect(tree.getStyleProperty(node, "width")).toBe(5);
+
+    tree.free();
+  });
+});
+
+describe("Layout Change Tracking", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("collects changed nodes until taken", () => {
+    const tree = new TaffyTree();
+    const a = tree.newLeaf(new Style({ width: 50, height: 50 }));
+    const b = tree.newLeaf(new Style({ width: 50, height: 50 }));
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, width: 400, height: 100 }),
+      [a, b],
+    );
+    tree.enableChangeTracking();
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.takeChangedNodes()).toEqual([root, a, b]);
+    expect(tree.takeChangedNodes()).toEqual([]);
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.takeChangedNodes()).toEqual([]);
+
+    tree.setStyle(a, new Style({ width: 80, height: 50 }));
+    tree.computeLayout(root, { width: 800, height: 600 });
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.takeChangedNodes()).toEqual([root, a, b]);
+
+    tree.free();
+  });
+
+  it("delivers changes of each pass to a callback", async () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style({ width: 100, height: 100 }));
+    const calls: bigint[][] = [];
+    tree.enableChangeTracking((nodes) => calls.push(nodes));
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    tree.computeLayout(root, { width: 800, height: 600 });
+    tree.updateStyle(root, { width: 120 });
+    tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, () => ({
+      width: 0,
+      height: 0,
+    }));
+    expect(calls).toEqual([]);
+
+    await flushMicrotasks();
+    expect(calls).toEqual([[root], [root]]);
+    expect(tree.takeChangedNodes()).toEqual([]);
+
+    tree.free();
+  });
+
+  it("lets the callback read layouts and edit the tree", async () => {
+    const tree = new TaffyTree();
+    const child = tree.newLeaf(new Style({ width: 50, height: 50 }));
+    const root = tree.newWithChildren(new Style({ display: Display.Flex }), [
+      child,
+    ]);
+    const widths: number[] = [];
+    tree.enableChangeTracking((nodes) => {
+      for (const node of nodes) {
+        widths.push(tree.getLayout(node).width);
+      }
+      tree.updateStyle(child, { height: 60 });
+    });
+
+    tree.computeLayout(root, { width: 200, height: 100 });
+    await flushMicrotasks();
+    expect(widths).toEqual([200, 50]);
+    expect(tree.dirty(root)).toBe(true);
+
+    tree.computeLayout(root, { width: 200, height: 100 });
+    await flushMicrotasks();
+    expect(tree.getLayout(child).height).toBe(60);
+
+    tree.free();
+  });
+
+  it("reports nothing while disabled", () => {
+    const tree = new TaffyTree();
+    const root = tree.newLeaf(new Style({ width: 100, height: 100 }));
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.takeChangedNodes()).toEqual([]);
+
+    tree.enableChangeTracking();
+    tree.disableChangeTracking();
+    tree.computeLayout(root, { width: 300, height: 600 });
+    expect(tree.takeChangedNodes()).toEqual([]);
+
+    tree.free();
+  });
+});
+
+describe("Per-Node Measure Functions", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  it("measures leaves in computeLayout", () => {
+    const tree = new TaffyTree();
+    const text = tree.newLeafWithContext(new Style(), { text: "hello" });
+    const image = tree.newLeaf(new Style());
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, flexDirection: FlexDirection.Column }),
+      [text, image],
+    );
+    const seen: unknown[] = [];
+    tree.setMeasureFunc(text, (_known, _available, node, context) => {
+      seen.push([node, context]);
+      return { width: 40, height: 10 };
+    });
+    tree.setMeasureFunc(image, () => ({ width: 64, height: 48 }));
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+
+    expect(seen[0]).toEqual([text, { text: "hello" }]);
+    expect(tree.getLayout(text).height).toBe(10);
+    expect(tree.getLayout(image).height).toBe(48);
+
+    tree.free();
+  });
+
+  it("falls back to the computeLayoutWithMeasure callback", () => {
+    const tree = new TaffyTree();
+    const own = tree.newLeaf(new Style());
+    const other = tree.newLeaf(new Style());
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, flexDirection: FlexDirection.Column }),
+      [own, other],
+    );
+    tree.setMeasureFunc(own, () => ({ width: 10, height: 30 }));
+
+    tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, () => ({
+      width: 10,
+      height: 5,
+    }));
+    expect(tree.getLayout(own).height).toBe(30);
+    expect(tree.getLayout(other).height).toBe(5);
+
+    expect(tree.clearMeasureFunc(own)).toBe(true);
+    expect(tree.clearMeasureFunc(own)).toBe(false);
+    expect(tree.dirty(own)).toBe(true);
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.getLayout(own).height).toBe(0);
+
+    tree.free();
+  });
+
+  it("copies measure functions with cloneSubtree", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(new Style());
+    tree.setMeasureFunc(leaf, () => ({ width: 20, height: 20 }));
+
+    const copy = tree.cloneSubtree(leaf);
+    tree.computeLayout(copy, { width: 800, height: 600 });
+    expect(tree.getLayout(copy).width).toBe(20);
+
+    tree.free();
+  });
+
+  it("rejects unknown nodes", () => {
+    const tree = new TaffyTree();
+    const node = tree.newLeaf(new Style());
+    tree.remove(node);
+
+    expect(() => tree.setMeasureFunc(node, () => ({ width: 0, height: 0 }))).toThrow(
+      TaffyError,
+    );
+
+    tree.free();
+  });
+});
+
+describe("Measure Cache", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildRow(tree: TaffyTree, measure: () => { width: number; height: number }) {
+    const text = tree.newLeafWithContext(new Style({ flexGrow: 1 }), { text: "a" });
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, width: "100%" }),
+      [text, tree.newLeaf(new Style({ width: 50 }))],
+    );
+    tree.setMeasureFunc(text, measure);
+    return { root, text };
+  }
+
+  it("reuses results for identical constraints", () => {
+    const tree = new TaffyTree();
+    let calls = 0;
+    const { root, text } = buildRow(tree, () => {
+      calls++;
+      return { width: 40, height: 10 };
+    });
+    tree.enableMeasureCache();
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    tree.computeLayout(root, { width: 400, height: 600 });
+    const stats = tree.measureCacheStats()!;
+    expect(stats).toEqual({ hits: 0, misses: calls, entries: calls });
+
+    tree.computeLayout(root, { width: 800, height: 600 });
+    expect(tree.measureCacheStats()!.misses).toBe(calls);
+    expect(tree.measureCacheStats()!.hits).toBeGreaterThan(0);
+    expect(tree.getLayout(text).height).toBe(10);
+
+    tree.free();
+  });
+
+  it("drops a node's results when it changes", () => {
+    const tree = new TaffyTree();
+    let calls = 0;
+    const { root, text } = buildRow(tree, () => {
+      calls++;
+      return { width: 40, height: 10 };
+    });
+    tree.enableMeasureCache();
+    tree.computeLayout(root, { width: 800, height: 600 });
+
+    for (const change of [
+      () => tree.markDirty(text),
+      () => tree.setNodeContext(text, { text: "b" }),
+      () => tree.setStyle(text, new Style({ flexGrow: 2 })),
+    ]) {
+      const before = calls;
+      change();
+      expect(tree.measureCacheStats()!.entries).toBe(0);
+      tree.computeLayout(root, { width: 800, height: 600 });
+      expect(calls).toBeGreaterThan(before);
+    }
+
+    tree.clearMeasureCache();
+    expect(tree.measureCacheStats()!.entries).toBe(0);
+    tree.disableMeasureCache();
+    expect(tree.measureCacheStats()).toBeUndefined();
+
+    tree.free();
+  });
+
+  it("drops all results when the layout callback changes", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(new Style());
+    const root = tree.newWithChildren(new Style({ width: 100 }), [leaf]);
+    const small = () => ({ width: 10, height: 10 });
+    const large = () => ({ width: 50, height: 50 });
+    tree.enableMeasureCache();
+
+    tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, small);
+    const entries = tree.measureCacheStats()!.entries;
+    expect(entries).toBeGreaterThan(0);
+    tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, small);
+    expect(tree.measureCacheStats()!.entries).toBe(entries);
+
+    tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, large);
+    expect(tree.measureCacheStats()!.entries).toBe(0);
+
+    tree.free();
+  });
+});
+
+describe("Measure Errors", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildColumn(tree: TaffyTree) {
+    const first = tree.newLeaf(new Style());
+    const second = tree.newLeaf(new Style());
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, flexDirection: FlexDirection.Column }),
+      [first, second],
+    );
+    return { root, first, second };
+  }
+
+  it("rethrows the first exception with the node", () => {
+    const tree = new TaffyTree();
+    const { root, first, second } = buildColumn(tree);
+    const thrown = new Error("font not loaded");
+    let secondCalls = 0;
+    tree.setMeasureFunc(first, () => {
+      throw thrown;
+    });
+    tree.setMeasureFunc(second, () => {
+      secondCalls++;
+      return { width: 10, height: 10 };
+    });
+
+    const error = thrownBy(() =>
+      tree.computeLayout(root, { width: 800, height: 600 }),
+    ) as TaffyError;
+    expect(error.code).toBe("MeasureFailed");
+    expect(error.node).toBe(first);
+    expect(error.cause).toBe(thrown);
+    expect(error.message).toContain("font not loaded");
+    expect(secondCalls).toBe(0);
+
+    tree.free();
+  });
+
+  it("rejects results that are not finite sizes", () => {
+    const tree = new TaffyTree();
+    const { root, second } = buildColumn(tree);
+
+    for (const result of [{ width: NaN, height: 1 }, { width: 1 }, null, 42]) {
+      const error = thrownBy(() =>
+        tree.computeLayoutWithMeasure(root, { width: 800, height: 600 }, (_k, _a, node) =>
+          node === second ? (result as { width: number; height: number }) : { width: 1, height: 1 },
+        ),
+      ) as TaffyError;
+      expect(error.code).toBe("MeasureFailed");
+      expect(error.node).toBe(second);
+      expect((error.cause as TaffyError).code).toBe("InvalidArgument");
+    }
+
+    tree.free();
+  });
+
+  it("measures again after a failed pass", () => {
+    const tree = new TaffyTree();
+    const { root, first, second } = buildColumn(tree);
+    let fail = true;
+    tree.setMeasureFunc(first, () => {
+      if (fail) throw new Error("not ready");
+      return { width: 20, height: 30 };
+    });
+    tree.setMeasureFunc(second, () => ({ width: 20, height: 40 }));
+
+    expect(() => tree.computeLayout(root, { width: 800, height: 600 })).toThrow();
+    fail = false;
+    tree.computeLayout(root, { width: 800, height: 600 });
+
+    expect(tree.getLayout(first).height).toBe(30);
+    expect(tree.getLayout(second).height).toBe(40);
+    expect(tree.getLayout(root).height).toBe(70);
+
+    tree.free();
+  });
+});
+
+describe("Measure Arguments", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildRow(tree: TaffyTree) {
+    const texts = [0, 1, 2].map((i) => tree.newLeafWithContext(new Style(), { chars: i + 1 }));
+    const root = tree.newWithChildren(new Style({ display: Display.Flex }), texts);
+    return { root, texts };
+  }
+
+  it("passes new objects and the style by default", () => {
+    const tree = new TaffyTree();
+    const { root } = buildRow(tree);
+    const seen: unknown[][] = [];
+    tree.computeLayoutWithMeasure(root, { width: 800, height: "max-content" }, (...args) => {
+      seen.push(args);
+      return { width: 10, height: 10 };
+    });
+
+    expect(seen.length).toBeGreaterThan(1);
+    expect(seen[0][0]).not.toBe(seen[1][0]);
+    expect(seen[0][4]).toBeInstanceOf(Style);
+
+    tree.free();
+  });
+
+  it("reuses size objects and leaves out the style", () => {
+    const tree = new TaffyTree();
+    const { root, texts } = buildRow(tree);
+    tree.setMeasureArgs({ reuseObjects: true, style: false });
+    const seen: unknown[][] = [];
+    const available: unknown[] = [];
+    tree.computeLayoutWithMeasure(root, { width: 800, height: "max-content" }, (...args) => {
+      seen.push(args);
+      available.push({ ...(args[1] as object) });
+      const chars = (args[3] as { chars: number }).chars;
+      return { width: chars * 10, height: 10 };
+    });
+
+    expect(seen.length).toBeGreaterThan(1);
+    expect(seen[0][0]).toBe(seen[1][0]);
+    expect(seen[0][1]).toBe(seen[1][1]);
+    expect(seen[0]).toHaveLength(4);
+    for (const space of available) {
+      expect(Object.keys(space as object).sort()).toEqual(["height", "width"]);
+    }
+    expect(texts.map((text) => tree.getLayout(text).width)).toEqual([10, 20, 30]);
+
+    tree.free();
+  });
+
+  it("resets omitted options to their defaults", () => {
+    const tree = new TaffyTree();
+    const leaf = tree.newLeaf(new Style());
+    tree.setMeasureArgs({ style: false });
+    tree.setMeasureArgs({});
+    let style: unknown;
+    tree.setMeasureFunc(leaf, (_known, _available, _node, _context, s) => {
+      style = s;
+      return { width: 1, height: 1 };
+    });
+    tree.computeLayout(leaf, { width: 800, height: 600 });
+
+    expect(style).toBeInstanceOf(Style);
+    expect(() => tree.setMeasureArgs({ style: "no" } as never)).toThrow(TaffyError);
+
+    tree.free();
+  });
+});
+
+describe("Text Measurement", () => {
+  beforeAll(async () => {
+    await setupTaffy();
+  });
+
+  function buildText(tree: TaffyTree, context: unknown, width: number | "auto" = "auto") {
+    const text = tree.newLeafWithContext(new Style(), context);
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, flexDirection: FlexDirection.Column, width }),
+      [text],
+    );
+    return { root, text };
+  }
+
+  it("wraps text contexts in terminal cells", () => {
+    const tree = new TaffyTree();
+    tree.enableTextMeasure();
+    const { root, text } = buildText(tree, { text: "the quick brown fox", wrap: "wrap" }, 10);
+
+    tree.computeLayout(root, { width: 80, height: 24 });
+    expect(tree.getLayout(text).width).toBe(10);
+    expect(tree.getLayout(text).height).toBe(2);
+
+    tree.free();
+  });
+
+  it("sizes by longest word and longest line", () => {
+    const tree = new TaffyTree();
+    tree.enableTextMeasure();
+    const { root } = buildText(tree, { text: "hello wide 世界\nok" });
+
+    tree.computeLayout(root, { width: "min-content", height: "max-content" });
+    expect(tree.getLayout(root).width).toBe(5);
+    expect(tree.getLayout(root).height).toBe(4);
+
+    tree.computeLayout(root, { width: "max-content", height: "max-content" });
+    expect(tree.getLayout(root).width).toBe(15);
+    expect(tree.getLayout(root).height).toBe(2);
+
+    tree.free();
+  });
+
+  it("truncates or keeps lines without wrapping", () => {
+    const tree = new TaffyTree();
+    tree.enableTextMeasure();
+    const truncated = tree.newLeafWithContext(new Style({ flexShrink: 0 }), {
+      text: "hello world",
+      wrap: "truncate",
+    });
+    const unwrapped = tree.newLeafWithContext(new Style({ flexShrink: 0 }), {
+      text: "hello world",
+      wrap: "none",
+    });
+    const root = tree.newWithChildren(
+      new Style({ display: Display.Flex, flexDirection: FlexDirection.Column, width: 5 }),
+      [truncated, unwrapped],
+    );
+
+    tree.computeLayout(root, { width: 80, height: 24 });
+    expect(tree.getLayout(truncated).height).toBe(1);
+    expect(tree.getLayout(unwrapped).height).toBe(1);
+
+    tree.free();
+  });
+
+  it("prefers measure functions and reports invalid wrap modes", () => {
+    const tree = new TaffyTree();
+    const { root, text } = buildText(tree, { text: "hello" });
+
+    tree.computeLayout(root, { width: 80, height: 24 });
+    expect(tree.getLayout(text).height).toBe(0);
+
+    tree.enableTextMeasure();
+    tree.computeLayout(root, { width: 80, height: 24 });
+    expect(tree.getLayout(text).height).toBe(1);
+
+    tree.setMeasureFunc(text, () => ({ width: 1, height: 7 }));
+    tree.computeLayout(root, { width: 80, height: 24 });
+    expect(tree.getLayout(text).height).toBe(7);
+
+    tree.clearMeasureFunc(text);
+    tree.setNodeContext(text, { text: "hello", wrap: "clip" });
+    const error = thrownBy(() => tree.computeLayout(root, { width: 80, height: 24 })) as TaffyError;
+    expect(error.code).toBe("MeasureFailed");
+    expect(error.node).toBe(text);
+    expect((error.cause as TaffyError).code).toBe("InvalidArgument");
+
+    tree.free();
+  });
+});